@@ -0,0 +1,290 @@
+use crate::color::{Color, ColorScheme, ANSI_COLOR_NAMES};
+
+/// The minimum contrast ratio of the foreground on the background (WCAG AA for normal text).
+pub const MIN_TEXT_CONTRAST: f64 = 4.5;
+
+/// The contrast ratio of the foreground on the background for WCAG AAA.
+pub const ENHANCED_TEXT_CONTRAST: f64 = 7.0;
+
+/// The minimum contrast ratio of ANSI colors on the background (WCAG AA for large text).
+pub const MIN_ANSI_CONTRAST: f64 = 3.0;
+
+/// The minimum color difference (CIE76 ΔE) for colors to be told apart at a glance.
+pub const MIN_DELTA_E: f64 = 10.0;
+
+/// Indices of the chromatic ANSI colors, which are used to convey meaning.
+const CHROMATIC_COLORS: [usize; 12] = [1, 2, 3, 4, 5, 6, 9, 10, 11, 12, 13, 14];
+
+/// Pairs of ANSI colors that people with color vision deficiencies commonly confuse.
+const CONFUSED_PAIRS: [(usize, usize); 4] = [(1, 2), (4, 5), (9, 10), (12, 13)];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Pass,
+    Warning,
+    Failure,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Category {
+    Contrast,
+    ColorBlindness,
+    ColorDifference,
+}
+
+impl Category {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Category::Contrast => "Contrast",
+            Category::ColorBlindness => "Color blindness",
+            Category::ColorDifference => "Color difference",
+        }
+    }
+}
+
+/// A type of color vision deficiency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Deficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl Deficiency {
+    pub fn all() -> [Deficiency; 3] {
+        [
+            Deficiency::Protanopia,
+            Deficiency::Deuteranopia,
+            Deficiency::Tritanopia,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Deficiency::Protanopia => "protanopia",
+            Deficiency::Deuteranopia => "deuteranopia",
+            Deficiency::Tritanopia => "tritanopia",
+        }
+    }
+
+    /// Simulates how the color looks with the deficiency.
+    ///
+    /// Uses the matrices of Machado, Oliveira and Fernandes (2009) at full severity.
+    pub fn simulate(&self, color: &Color) -> Color {
+        let matrix = match self {
+            Deficiency::Protanopia => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            Deficiency::Deuteranopia => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            Deficiency::Tritanopia => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+        };
+        let rgb = color.linear_rgb();
+        Color::from_linear_rgb(matrix.map(|row| {
+            row.iter()
+                .zip(rgb.iter())
+                .map(|(factor, component)| factor * component)
+                .sum()
+        }))
+    }
+}
+
+/// The contrast ratio of a color on the color that it's drawn on.
+#[derive(Debug, PartialEq)]
+pub struct Contrast {
+    /// What is drawn, like `foreground`, `red` or `cursor text`.
+    pub slot: String,
+    pub ratio: f64,
+    /// Whether it's default text, which needs more contrast than ANSI colors.
+    pub is_text: bool,
+    /// Whether it's usually not text but a background, like black in a dark color scheme.
+    pub is_exempt: bool,
+}
+
+/// Contrast ratios of the foreground and the ANSI colors on the background, and of the cursor
+/// text on the cursor if the color scheme has them.
+pub fn contrast_ratios(scheme: &ColorScheme) -> Vec<Contrast> {
+    let background = scheme.background();
+    let mut contrasts = vec![Contrast {
+        slot: "foreground".to_owned(),
+        ratio: scheme.foreground().contrast_ratio(background),
+        is_text: true,
+        is_exempt: false,
+    }];
+    // Black is a background in dark color schemes, and white is in light ones.
+    let exempt: &[usize] = if scheme.is_dark() { &[0] } else { &[7, 15] };
+    for (index, color) in scheme.ansi_colors().iter().enumerate() {
+        contrasts.push(Contrast {
+            slot: ANSI_COLOR_NAMES[index].to_owned(),
+            ratio: color.contrast_ratio(background),
+            is_text: false,
+            is_exempt: exempt.contains(&index),
+        });
+    }
+    if let (Some(cursor), Some(cursor_text)) = (scheme.cursor(), scheme.cursor_text()) {
+        contrasts.push(Contrast {
+            slot: "cursor text".to_owned(),
+            ratio: cursor_text.contrast_ratio(cursor),
+            is_text: true,
+            is_exempt: false,
+        });
+    }
+    contrasts
+}
+
+/// The result of a single accessibility check.
+#[derive(Debug, PartialEq)]
+pub struct Check {
+    pub category: Category,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Accessibility checks of a color scheme.
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    /// Runs all the checks on a color scheme.
+    pub fn new(scheme: &ColorScheme) -> Self {
+        let mut checks = Vec::new();
+        check_contrast(scheme, &mut checks);
+        check_color_blindness(scheme, &mut checks);
+        check_color_difference(scheme, &mut checks);
+        Report { checks }
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| check.severity == severity)
+            .count()
+    }
+
+    /// Whether the color scheme has no failures. Warnings are allowed.
+    pub fn passed(&self) -> bool {
+        self.count(Severity::Failure) == 0
+    }
+}
+
+fn check_contrast(scheme: &ColorScheme, checks: &mut Vec<Check>) {
+    let background = scheme.background();
+
+    let ratio = scheme.foreground().contrast_ratio(background);
+    let severity = if ratio < MIN_TEXT_CONTRAST {
+        Severity::Failure
+    } else if ratio < ENHANCED_TEXT_CONTRAST {
+        Severity::Warning
+    } else {
+        Severity::Pass
+    };
+    checks.push(Check {
+        category: Category::Contrast,
+        severity,
+        message: format!(
+            "foreground on background: {:.2}:1 (AA {}:1, AAA {}:1)",
+            ratio, MIN_TEXT_CONTRAST, ENHANCED_TEXT_CONTRAST
+        ),
+    });
+
+    let colors = scheme.ansi_colors();
+    for &index in CHROMATIC_COLORS.iter() {
+        let ratio = colors[index].contrast_ratio(background);
+        if ratio < MIN_ANSI_CONTRAST {
+            checks.push(Check {
+                category: Category::Contrast,
+                severity: Severity::Warning,
+                message: format!(
+                    "{} on background: {:.2}:1 (below {}:1)",
+                    ANSI_COLOR_NAMES[index], ratio, MIN_ANSI_CONTRAST
+                ),
+            });
+        }
+    }
+}
+
+fn check_color_blindness(scheme: &ColorScheme, checks: &mut Vec<Check>) {
+    let colors = scheme.ansi_colors();
+    for deficiency in Deficiency::all().iter() {
+        let confused: Vec<_> = CONFUSED_PAIRS
+            .iter()
+            .filter_map(|&(a, b)| {
+                let delta_e = deficiency
+                    .simulate(colors[a])
+                    .delta_e(&deficiency.simulate(colors[b]));
+                if delta_e < MIN_DELTA_E {
+                    Some((a, b, delta_e))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        if confused.is_empty() {
+            checks.push(Check {
+                category: Category::ColorBlindness,
+                severity: Severity::Pass,
+                message: format!(
+                    "{}: red/green and blue/magenta are distinct",
+                    deficiency.name()
+                ),
+            });
+        }
+        for (a, b, delta_e) in confused {
+            checks.push(Check {
+                category: Category::ColorBlindness,
+                severity: Severity::Failure,
+                message: format!(
+                    "{}: {} / {} ΔE {:.1} (below {})",
+                    deficiency.name(),
+                    ANSI_COLOR_NAMES[a],
+                    ANSI_COLOR_NAMES[b],
+                    delta_e,
+                    MIN_DELTA_E
+                ),
+            });
+        }
+    }
+}
+
+fn check_color_difference(scheme: &ColorScheme, checks: &mut Vec<Check>) {
+    let colors = scheme.ansi_colors();
+    let mut similar = 0;
+    // Compare normal colors with each other and bright colors with each other. Normal and bright
+    // variants of the same color are meant to be similar.
+    for group in CHROMATIC_COLORS.chunks(6) {
+        for (i, &a) in group.iter().enumerate() {
+            for &b in group[i + 1..].iter() {
+                let delta_e = colors[a].delta_e(colors[b]);
+                if delta_e < MIN_DELTA_E {
+                    similar += 1;
+                    checks.push(Check {
+                        category: Category::ColorDifference,
+                        severity: Severity::Warning,
+                        message: format!(
+                            "{} / {}: ΔE {:.1} (below {})",
+                            ANSI_COLOR_NAMES[a], ANSI_COLOR_NAMES[b], delta_e, MIN_DELTA_E
+                        ),
+                    });
+                }
+            }
+        }
+    }
+    if similar == 0 {
+        checks.push(Check {
+            category: Category::ColorDifference,
+            severity: Severity::Pass,
+            message: "all chromatic colors are distinct".to_string(),
+        });
+    }
+}
@@ -0,0 +1,190 @@
+use crate::color::ParseError;
+use anyhow::{Context, Result};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Paths of the config that alacritty looks for, in the order of priority.
+pub fn config_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        let dir = PathBuf::from(dir);
+        paths.push(dir.join("alacritty").join("alacritty.toml"));
+        paths.push(dir.join("alacritty.toml"));
+    }
+    if let Some(home) = dirs::home_dir() {
+        paths.push(
+            home.join(".config")
+                .join("alacritty")
+                .join("alacritty.toml"),
+        );
+        paths.push(home.join(".alacritty.toml"));
+    }
+    // `%APPDATA%` on Windows.
+    if let Some(dir) = dirs::config_dir() {
+        let path = dir.join("alacritty").join("alacritty.toml");
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// The config that alacritty reads, or the first path to look for if there is none yet.
+pub fn config_path() -> Option<PathBuf> {
+    let paths = config_paths();
+    paths
+        .iter()
+        .find(|path| path.is_file())
+        .or_else(|| paths.first())
+        .cloned()
+}
+
+/// Makes a config import the theme file, in `general.import` or `import` of alacritty before
+/// 0.14 if the config already has it.
+///
+/// Imports of other themes in the same directory are removed, and so are inline `[colors]` tables,
+/// which would override the imported colors. Other lines are kept as they are.
+pub fn import_theme(config: &str, theme: &Path) -> Result<String> {
+    let table: toml::Table = config.parse().context(ParseError::TOMLParse)?;
+    let existing = table
+        .get("general")
+        .and_then(|general| general.get("import"))
+        .or_else(|| table.get("import"))
+        .and_then(toml::Value::as_array);
+    let theme_dir = theme.parent();
+    let mut imports: Vec<String> = existing
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str)
+        .filter(|import| expand_home(import).parent() != theme_dir)
+        .map(str::to_owned)
+        .collect();
+    imports.push(theme.display().to_string());
+    let quoted: Vec<String> = imports.iter().map(|import| quote(import)).collect();
+    let import_line = format!("import = [{}]", quoted.join(", "));
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut section = String::new();
+    let mut replaced = false;
+    let mut general_header = None;
+    let mut open_brackets = 0;
+    // Comments and blank lines at the end of colors, which may be about the next section.
+    let mut pending: Vec<String> = Vec::new();
+    let is_colors = |section: &str| section == "colors" || section.starts_with("colors.");
+    for line in config.lines() {
+        // Continuation of a multiline `import` array that was replaced.
+        if open_brackets > 0 {
+            open_brackets += bracket_depth(line);
+            continue;
+        }
+        let trimmed = line.trim();
+        if let Some(name) = section_name(trimmed) {
+            section = name;
+            if !is_colors(&section) {
+                lines.append(&mut pending);
+            }
+            if section == "general" {
+                general_header = Some(lines.len());
+            }
+        }
+        if is_colors(&section) {
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                pending.push(line.to_owned());
+            } else {
+                pending.clear();
+            }
+            continue;
+        }
+        let key = trimmed.split('=').next().unwrap_or_default().trim();
+        let is_import = trimmed.contains('=')
+            && ((section.is_empty() && (key == "import" || key == "general.import"))
+                || (section == "general" && key == "import"));
+        if is_import && !replaced {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let prefix = if key == "general.import" {
+                "general."
+            } else {
+                ""
+            };
+            lines.push(format!("{}{}{}", indent, prefix, import_line));
+            replaced = true;
+            open_brackets = bracket_depth(line);
+            continue;
+        }
+        lines.push(line.to_owned());
+    }
+
+    if !replaced {
+        match general_header {
+            Some(index) => lines.insert(index + 1, import_line),
+            None => {
+                // Keys before the first section would belong to `[general]` if it were inserted at
+                // the top.
+                if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                    lines.push(String::new());
+                }
+                lines.push("[general]".to_owned());
+                lines.push(import_line);
+            }
+        }
+    }
+    // Don't leave blank lines where the colors were.
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    let start = lines
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .unwrap_or(lines.len());
+    let mut output = lines[start..].join("\n");
+    output.push('\n');
+    Ok(output)
+}
+
+/// The name of a table like `colors.primary` of a header line like `[colors.primary]`.
+fn section_name(line: &str) -> Option<String> {
+    let line = line.split('#').next().unwrap_or_default().trim();
+    let name = line
+        .strip_prefix("[[")
+        .and_then(|rest| rest.strip_suffix("]]"))
+        .or_else(|| {
+            line.strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+        })?;
+    let parts: Vec<&str> = name
+        .split('.')
+        .map(|part| part.trim().trim_matches(|c| c == '"' || c == '\''))
+        .collect();
+    Some(parts.join("."))
+}
+
+/// Opened brackets minus closed brackets outside strings and comments.
+fn bracket_depth(line: &str) -> i32 {
+    let mut depth = 0;
+    let mut quote = None;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => break,
+            (None, '[') => depth += 1,
+            (None, ']') => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// A basic string of TOML.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Expands `~/` at the start of a path like alacritty does for imports.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
@@ -0,0 +1,61 @@
+use std::process::Command;
+
+/// Whether the system prefers dark or light colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Appearance {
+    Dark,
+    Light,
+}
+
+impl Appearance {
+    pub fn name(self) -> &'static str {
+        match self {
+            Appearance::Dark => "dark",
+            Appearance::Light => "light",
+        }
+    }
+}
+
+/// Reads the appearance from the output of `defaults read -g AppleInterfaceStyle` on macOS.
+///
+/// The key exists only in dark mode, so a failed command means light mode.
+pub fn from_macos_defaults(success: bool, output: &str) -> Appearance {
+    if success && output.trim().eq_ignore_ascii_case("dark") {
+        Appearance::Dark
+    } else {
+        Appearance::Light
+    }
+}
+
+/// Reads the appearance from the output of `gsettings get org.gnome.desktop.interface
+/// color-scheme` on GNOME, which is like `'prefer-dark'`.
+///
+/// Returns `None` for unknown values.
+pub fn from_gnome_color_scheme(output: &str) -> Option<Appearance> {
+    match output.trim().trim_matches('\'') {
+        "prefer-dark" => Some(Appearance::Dark),
+        "default" | "prefer-light" => Some(Appearance::Light),
+        _ => None,
+    }
+}
+
+/// Detects the current appearance of macOS or GNOME, or returns `None` if neither of them tells.
+pub fn detect() -> Option<Appearance> {
+    if cfg!(target_os = "macos") {
+        let output = Command::new("defaults")
+            .args(["read", "-g", "AppleInterfaceStyle"])
+            .output()
+            .ok()?;
+        return Some(from_macos_defaults(
+            output.status.success(),
+            &String::from_utf8_lossy(&output.stdout),
+        ));
+    }
+
+    let output = Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "color-scheme"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    from_gnome_color_scheme(&String::from_utf8_lossy(&output.stdout))
+}
@@ -1,19 +1,101 @@
 use anyhow::{Context, Result};
-use regex::Regex;
-use xml::{Element, Xml};
+use std::collections::HashMap;
+use std::str::FromStr;
 
+use crate::plist;
+
+#[derive(Clone)]
 pub enum ColorSchemeFormat {
     ITerm,
     Mintty,
     Gogh,
+    // Can contain multiple color schemes.
+    ITermDynamicProfiles,
+    WindowsTerminal,
+    Xresources,
+    // Palettes of designers. Colors are assigned to the slots heuristically.
+    Gpl,
+    Ase,
+    // Configs of alacritty until 0.12. Only the `colors` section is read.
+    AlacrittyYaml,
+    // Configs and themes of alacritty from 0.13. Only the `colors` table is read.
+    AlacrittyToml,
+    Konsole,
+    // Settings or color themes of VS Code.
+    VSCode,
+    WezTerm,
+    // Sessions exported from the registry. Can contain multiple color schemes.
+    PuTTY,
+    // Profiles of Terminal.app on macOS in XML or binary property lists.
+    TerminalApp,
+    Xfce4Terminal,
+    // Output of `dconf dump` with profiles of GNOME Terminal or Tilix. Can contain multiple color
+    // schemes.
+    Dconf,
+    Foot,
+    Ghostty,
+    // `colors.properties` of Termux.
+    Termux,
+    // `config.h` of st.
+    St,
+    // Hyper themes and `.hyper.js` in JavaScript.
+    Hyper,
+    Rio,
 }
 
 impl ColorSchemeFormat {
+    // Names taken by `from_string()`
+    pub const NAMES: [&'static str; 23] = [
+        "iterm",
+        "mintty",
+        "gogh",
+        "iterm-dynamic-profiles",
+        "windows-terminal",
+        "xresources",
+        "gpl",
+        "ase",
+        "alacritty-yaml",
+        "alacritty-toml",
+        "konsole",
+        "vscode",
+        "wezterm",
+        "putty",
+        "terminal-app",
+        "xfce4-terminal",
+        "dconf",
+        "foot",
+        "ghostty",
+        "termux",
+        "st",
+        "hyper",
+        "rio",
+    ];
+
     pub fn from_string(s: &str) -> Option<Self> {
         match s {
             "iterm" => Some(Self::ITerm),
             "mintty" => Some(Self::Mintty),
             "gogh" => Some(Self::Gogh),
+            "iterm-dynamic-profiles" => Some(Self::ITermDynamicProfiles),
+            "windows-terminal" => Some(Self::WindowsTerminal),
+            "xresources" => Some(Self::Xresources),
+            "gpl" => Some(Self::Gpl),
+            "ase" => Some(Self::Ase),
+            "alacritty-yaml" => Some(Self::AlacrittyYaml),
+            "alacritty-toml" => Some(Self::AlacrittyToml),
+            "konsole" => Some(Self::Konsole),
+            "vscode" => Some(Self::VSCode),
+            "wezterm" => Some(Self::WezTerm),
+            "putty" => Some(Self::PuTTY),
+            "terminal-app" => Some(Self::TerminalApp),
+            "xfce4-terminal" => Some(Self::Xfce4Terminal),
+            "dconf" => Some(Self::Dconf),
+            "foot" => Some(Self::Foot),
+            "ghostty" => Some(Self::Ghostty),
+            "termux" => Some(Self::Termux),
+            "st" => Some(Self::St),
+            "hyper" => Some(Self::Hyper),
+            "rio" => Some(Self::Rio),
             _ => None,
         }
     }
@@ -25,12 +107,82 @@ impl ColorSchemeFormat {
             Some(Self::Mintty)
         } else if s.ends_with(".sh") {
             Some(Self::Gogh)
+        } else if s.ends_with("alacritty.yml") || s.ends_with("alacritty.yaml") {
+            Some(Self::AlacrittyYaml)
+        } else if s.ends_with(".terminal") {
+            Some(Self::TerminalApp)
+        } else if s.ends_with("foot.ini") {
+            Some(Self::Foot)
+        } else if s.ends_with(".theme") {
+            Some(Self::Xfce4Terminal)
+        } else if s.ends_with(".properties") {
+            Some(Self::Termux)
+        } else if s.ends_with("config.h") || s.ends_with("config.def.h") {
+            Some(Self::St)
+        } else if s.ends_with("hyper.js") {
+            Some(Self::Hyper)
+        } else if s.ends_with(".reg") {
+            Some(Self::PuTTY)
+        } else if s.ends_with(".colorscheme") {
+            Some(Self::Konsole)
+        } else if s.ends_with("Xresources") || s.ends_with("xresources") || s.ends_with("Xdefaults")
+        {
+            Some(Self::Xresources)
+        } else if s.ends_with(".gpl") {
+            Some(Self::Gpl)
+        } else if s.ends_with(".ase") {
+            Some(Self::Ase)
+        } else {
+            None
+        }
+    }
+
+    // Guesses the format of JSON, YAML and TOML files, which can't be told by their names
+    pub fn from_content(content: &str) -> Option<Self> {
+        if content.lines().any(|line| line.starts_with("color_01:")) {
+            return Some(Self::Gogh);
+        }
+        if content.lines().any(|line| line.starts_with("colors:")) {
+            return Some(Self::AlacrittyYaml);
+        }
+        if content.lines().any(|line| line.starts_with("palette=[")) {
+            return Some(Self::Dconf);
+        }
+        if content
+            .lines()
+            .any(|line| parse_ghostty_palette(line).is_some())
+        {
+            return Some(Self::Ghostty);
+        }
+        if let Ok(table) = content.parse::<toml::Table>() {
+            let colors = table.get("colors")?.as_table()?;
+            // Rio has colors right under `colors`, and Alacritty has them in tables like
+            // `colors.primary`.
+            let is_rio = ["foreground", "background", "black", "light-black"]
+                .iter()
+                .any(|key| colors.get(*key).is_some_and(toml::Value::is_str));
+            return if colors.contains_key("ansi") || colors.contains_key("brights") {
+                Some(Self::WezTerm)
+            } else if is_rio {
+                Some(Self::Rio)
+            } else {
+                Some(Self::AlacrittyToml)
+            };
+        }
+        let value = json::parse(&strip_json_comments(content)).ok()?;
+        if vscode_colors(&value).is_some() {
+            Some(Self::VSCode)
+        } else if value.has_key("Profiles") {
+            Some(Self::ITermDynamicProfiles)
+        } else if value.has_key("schemes") || value.is_array() || value.has_key("brightBlack") {
+            Some(Self::WindowsTerminal)
         } else {
             None
         }
     }
 }
 
+#[derive(Clone)]
 pub enum AlacrittyConfigFormat {
     // Until 0.12.
     Yaml,
@@ -46,8 +198,174 @@ impl AlacrittyConfigFormat {
             _ => None,
         }
     }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Yaml => "yml",
+            Self::Toml => "toml",
+        }
+    }
+}
+
+// Formats of configs that color schemes can be converted into
+#[derive(Clone)]
+pub enum OutputFormat {
+    Alacritty(AlacrittyConfigFormat),
+    // Also read by sway.
+    I3,
+    Polybar,
+    Waybar,
+    Rofi,
+    Dunst,
+    Mako,
+    JetBrains,
+    // A registry file for the console host of Windows, like cmd.exe.
+    CmdReg,
+    Kitty,
+    WindowsTerminal,
+    WindowsTerminalFragment,
+    WezTerm,
+    Foot,
+    Ghostty,
+    Konsole,
+    ITerm,
+    // Also read by wsltty.
+    Mintty,
+    Tmux,
+    VSCode,
+    Neovim,
+    Base16,
+    // Escape sequences that change the colors of the running terminal.
+    Osc,
+    Fzf,
+    Gogh,
+}
+
+impl Default for OutputFormat {
+    // Alacritty reads TOML from 0.13.
+    fn default() -> Self {
+        Self::Alacritty(AlacrittyConfigFormat::Toml)
+    }
+}
+
+impl OutputFormat {
+    // Names taken by `from_string()`, without aliases
+    pub const NAMES: [&'static str; 27] = [
+        "toml",
+        "yaml",
+        "kitty",
+        "json",
+        "wezterm",
+        "foot",
+        "ghostty",
+        "konsole",
+        "iterm",
+        "mintty",
+        "vscode",
+        "neovim",
+        "tmux",
+        "i3",
+        "polybar",
+        "waybar",
+        "rofi",
+        "dunst",
+        "mako",
+        "fzf",
+        "jetbrains",
+        "cmd",
+        "base16",
+        "gogh",
+        "osc",
+        "windows-terminal",
+        "windows-terminal-fragment",
+    ];
+
+    pub fn from_string(s: &str) -> Option<Self> {
+        match s {
+            "i3" | "sway" => Some(Self::I3),
+            "polybar" => Some(Self::Polybar),
+            "waybar" => Some(Self::Waybar),
+            "rofi" => Some(Self::Rofi),
+            "dunst" => Some(Self::Dunst),
+            "mako" => Some(Self::Mako),
+            "jetbrains" => Some(Self::JetBrains),
+            "cmd" => Some(Self::CmdReg),
+            "kitty" => Some(Self::Kitty),
+            // A color scheme of Windows Terminal, which is the most common JSON for color schemes.
+            "windows-terminal" | "json" => Some(Self::WindowsTerminal),
+            "windows-terminal-fragment" => Some(Self::WindowsTerminalFragment),
+            "wezterm" => Some(Self::WezTerm),
+            "foot" => Some(Self::Foot),
+            "ghostty" => Some(Self::Ghostty),
+            "konsole" => Some(Self::Konsole),
+            "iterm" => Some(Self::ITerm),
+            "mintty" | "wsltty" => Some(Self::Mintty),
+            "tmux" => Some(Self::Tmux),
+            "vscode" => Some(Self::VSCode),
+            "neovim" | "nvim" => Some(Self::Neovim),
+            "base16" => Some(Self::Base16),
+            "osc" => Some(Self::Osc),
+            "fzf" => Some(Self::Fzf),
+            "gogh" => Some(Self::Gogh),
+            _ => AlacrittyConfigFormat::from_string(s).map(Self::Alacritty),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Alacritty(format) => format.extension(),
+            Self::I3 => "conf",
+            Self::Polybar => "ini",
+            Self::Waybar => "css",
+            Self::Rofi => "rasi",
+            Self::Dunst => "dunstrc",
+            Self::Mako => "conf",
+            Self::JetBrains => "icls",
+            Self::CmdReg => "reg",
+            Self::Kitty => "conf",
+            Self::WindowsTerminal | Self::WindowsTerminalFragment => "json",
+            Self::WezTerm => "toml",
+            Self::Foot => "ini",
+            // Ghostty themes don't have an extension, but configs are usually named `config`.
+            Self::Ghostty => "conf",
+            Self::Konsole => "colorscheme",
+            Self::ITerm => "itermcolors",
+            Self::Mintty => "minttyrc",
+            Self::Tmux => "conf",
+            Self::VSCode => "json",
+            Self::Neovim => "lua",
+            Self::Base16 => "yaml",
+            Self::Osc => "txt",
+            Self::Fzf => "sh",
+            Self::Gogh => "sh",
+        }
+    }
+
+    // A line of comment in the syntax of the format. `None` if the format doesn't allow comments
+    // before the content
+    pub fn comment(&self, text: &str) -> Option<String> {
+        match self {
+            Self::Waybar => Some(format!("/* {} */", text)),
+            // settings.json of VS Code allows comments.
+            Self::Rofi | Self::VSCode => Some(format!("// {}", text)),
+            Self::Neovim => Some(format!("-- {}", text)),
+            Self::JetBrains => Some(format!("<!-- {} -->", text.replace("--", "- -"))),
+            // The header or the shebang must be the first line.
+            Self::CmdReg | Self::Gogh => None,
+            // Nothing can come before the XML declaration.
+            Self::ITerm => None,
+            // JSON doesn't have comments.
+            Self::WindowsTerminal | Self::WindowsTerminalFragment => None,
+            // Comments would be printed on the terminal.
+            Self::Osc => None,
+            _ => Some(format!("# {}", text)),
+        }
+    }
 }
 
+// The X11 color database from X.Org
+const X11_COLORS: &str = include_str!("rgb.txt");
+
 #[derive(thiserror::Error, Debug, PartialEq)]
 pub enum ParseError {
     // -- Generic errors
@@ -74,22 +392,73 @@ pub enum ParseError {
     #[error("root dict was not found")]
     NoRootDict,
 
-    #[error("cannot extract text from: {0}")]
-    NotCharacterNode(Box<Xml>),
+    #[error("unexpected element: {0}")]
+    UnexpectedElement(String),
 
     #[error("unknown color component: {0}")]
     UnknownColorComponent(String),
+
+    #[error("invalid property list data")]
+    InvalidPlistData,
+
+    // -- Errors of formats with multiple color schemes
+    #[error("invalid JSON")]
+    JSONParse,
+
+    #[error("invalid TOML")]
+    TOMLParse,
+
+    #[error("no color schemes were found")]
+    NoColorSchemes,
+
+    #[error("expected a single color scheme, but found {0}")]
+    MultipleColorSchemes(usize),
+
+    // -- Palette parse errors
+    #[error("invalid palette: {0}")]
+    InvalidPalette(String),
+
+    #[error("palette has no colors")]
+    EmptyPalette,
+
+    #[error("unsupported color model: {0}")]
+    UnsupportedColorModel(String),
 }
 
-#[derive(Debug, Default, PartialEq)]
+// Components have 16 bits so that formats with higher precision than 8 bits don't lose it.
+// Components are rounded to the precision of each output format when serialized.
+#[derive(Debug, Default, PartialEq, Clone)]
 pub struct Color {
-    pub red: u8,
-    pub green: u8,
-    pub blue: u8,
+    pub red: u16,
+    pub green: u16,
+    pub blue: u16,
 }
 
 impl Color {
+    // From 8-bit components, which are scaled so that 255 becomes 65535
+    pub fn from_rgb8(red: u8, green: u8, blue: u8) -> Self {
+        Color {
+            red: u16::from(red) * 257,
+            green: u16::from(green) * 257,
+            blue: u16::from(blue) * 257,
+        }
+    }
+
+    // From components in the range of 0.0 to 1.0 like iTerm 2 uses
+    pub fn from_real_rgb(red: f64, green: f64, blue: f64) -> Self {
+        let component = |real: f64| (real.clamp(0.0, 1.0) * 65535.0).round() as u16;
+        Color {
+            red: component(red),
+            green: component(green),
+            blue: component(blue),
+        }
+    }
+
+    // From `r,g,b` in decimal, or any X11 color specification that mintty also accepts
     pub fn from_mintty_color(s: &str) -> Result<Self> {
+        if !s.contains(',') {
+            return Color::from_x11(s);
+        }
         let rgb: Vec<_> = s.split(',').collect();
         if rgb.len() != 3 {
             return Err(ParseError::InvalidColorFormat(s.to_owned()).into());
@@ -97,53 +466,813 @@ impl Color {
         let red = parse_int(rgb[0])?;
         let green = parse_int(rgb[1])?;
         let blue = parse_int(rgb[2])?;
-        Ok(Color { red, green, blue })
+        Ok(Color::from_rgb8(red, green, blue))
     }
 
     pub fn from_gogh_color(s: &str) -> Result<Self> {
         let red = parse_hex(&s[1..3])?;
         let green = parse_hex(&s[3..5])?;
         let blue = parse_hex(&s[5..7])?;
+        Ok(Color::from_rgb8(red, green, blue))
+    }
+
+    // From `#rrggbb` or CSS-style `#rgb`. Alpha in `#rrggbbaa` or `#rgba` is ignored.
+    pub fn from_hex(s: &str) -> Result<Self> {
+        let hex = s
+            .strip_prefix('#')
+            .filter(|hex| hex.is_ascii())
+            .ok_or_else(|| ParseError::InvalidColorFormat(s.to_owned()))?;
+        match hex.len() {
+            3 | 4 => {
+                // Each digit is repeated: `#abc` is `#aabbcc`.
+                let component =
+                    |index: usize| -> Result<u8> { Ok(parse_hex(&hex[index..index + 1])? * 0x11) };
+                Ok(Color::from_rgb8(
+                    component(0)?,
+                    component(1)?,
+                    component(2)?,
+                ))
+            }
+            6 | 8 => Ok(Color::from_rgb8(
+                parse_hex(&hex[0..2])?,
+                parse_hex(&hex[2..4])?,
+                parse_hex(&hex[4..6])?,
+            )),
+            _ => Err(ParseError::InvalidColorFormat(s.to_owned()).into()),
+        }
+    }
+
+    // From CSS-style `rgb(r, g, b)` or `rgba(r, g, b, a)` with integers or percentages. Alpha
+    // is ignored.
+    pub fn from_css_rgb(s: &str) -> Result<Self> {
+        let invalid = || ParseError::InvalidColorFormat(s.to_owned());
+        let arguments = s
+            .strip_prefix("rgba(")
+            .or_else(|| s.strip_prefix("rgb("))
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(invalid)?;
+        let components: Vec<&str> = arguments
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|component| !component.is_empty())
+            .collect();
+        if components.len() != 3 && components.len() != 4 {
+            return Err(invalid().into());
+        }
+
+        let mut rgb = [0; 3];
+        for (value, component) in rgb.iter_mut().zip(components) {
+            *value = match component.strip_suffix('%') {
+                Some(percentage) => {
+                    let percentage = percentage.parse::<f64>().context(ParseError::ParseFloat)?;
+                    // CSS colors have 8 bits per component.
+                    let value = (percentage.clamp(0.0, 100.0) / 100.0 * 255.0).round();
+                    value as u16 * 257
+                }
+                None => u16::from(parse_int(component)?) * 257,
+            };
+        }
+        let [red, green, blue] = rgb;
+        Ok(Color { red, green, blue })
+    }
+
+    // From an X11 color specification: a color name from rgb.txt, `rgb:r/g/b` with 1 to 4 hex
+    // digits per component, or `#rgb` with the same number of digits per component
+    //
+    // Unlike X, `#rgb` is scaled in the same way as `rgb:r/g/b` because that's how people expect
+    // `#rrggbb` to work.
+    pub fn from_x11(s: &str) -> Result<Self> {
+        let invalid = || ParseError::InvalidColorFormat(s.to_owned());
+        let components: Vec<&str> = if let Some(rgb) = s.strip_prefix("rgb:") {
+            rgb.split('/').collect()
+        } else if let Some(hex) = s.strip_prefix('#') {
+            let length = hex.len() / 3;
+            if !hex.is_ascii() || length == 0 || hex.len() % 3 != 0 {
+                return Err(invalid().into());
+            }
+            vec![&hex[..length], &hex[length..2 * length], &hex[2 * length..]]
+        } else {
+            return Color::from_x11_name(s).ok_or_else(|| invalid().into());
+        };
+
+        if components.len() != 3 {
+            return Err(invalid().into());
+        }
+        let mut rgb = [0; 3];
+        for (value, component) in rgb.iter_mut().zip(components) {
+            if component.is_empty() || component.len() > 4 {
+                return Err(invalid().into());
+            }
+            // Scale the value so that the largest one with the number of digits becomes 65535.
+            let max = (1u32 << (4 * component.len())) - 1;
+            let raw = u32::from(parse_hex16(component)?);
+            *value = ((raw * 65535 + max / 2) / max) as u16;
+        }
+        let [red, green, blue] = rgb;
         Ok(Color { red, green, blue })
     }
 
+    // Looks up a color name in the X11 color database (rgb.txt). Case and spaces are ignored.
+    pub fn from_x11_name(name: &str) -> Option<Self> {
+        let normalize = |s: &str| -> String {
+            s.chars()
+                .filter(|c| !c.is_whitespace())
+                .flat_map(char::to_lowercase)
+                .collect()
+        };
+        let name = normalize(name);
+        X11_COLORS
+            .lines()
+            .filter(|line| !line.starts_with('!'))
+            .find_map(|line| {
+                let mut fields = line.split_whitespace();
+                let red = fields.next()?.parse().ok()?;
+                let green = fields.next()?.parse().ok()?;
+                let blue = fields.next()?.parse().ok()?;
+                let color_name: String = fields.collect();
+                if normalize(&color_name) == name {
+                    Some(Color::from_rgb8(red, green, blue))
+                } else {
+                    None
+                }
+            })
+    }
+
+    // Components reduced to 8 bits
+    pub fn to_rgb8(&self) -> (u8, u8, u8) {
+        let component = |value: u16| ((u32::from(value) * 255 + 32767) / 65535) as u8;
+        (
+            component(self.red),
+            component(self.green),
+            component(self.blue),
+        )
+    }
+
     pub fn to_hex(&self) -> String {
-        format!("0x{:>02x}{:>02x}{:>02x}", self.red, self.green, self.blue)
+        let (red, green, blue) = self.to_rgb8();
+        format!("0x{:>02x}{:>02x}{:>02x}", red, green, blue)
+    }
+
+    // `#rrggbb` as in CSS
+    pub fn to_css_hex(&self) -> String {
+        let (red, green, blue) = self.to_rgb8();
+        format!("#{:>02x}{:>02x}{:>02x}", red, green, blue)
+    }
+
+    // `rgb:rrrr/gggg/bbbb` of X11, which escape sequences of terminals take, with all the 16 bits
+    pub fn to_x11_rgb(&self) -> String {
+        format!("rgb:{:04x}/{:04x}/{:04x}", self.red, self.green, self.blue)
+    }
+
+    // Index of the nearest color of the xterm 256-color palette, looking only at the 6x6x6 color
+    // cube and the grayscale ramp because terminals change the first 16 colors
+    pub fn to_xterm_256(&self) -> u8 {
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        (16..=255u8)
+            .map(|index| {
+                let color = if index < 232 {
+                    let i = usize::from(index - 16);
+                    Color::from_rgb8(LEVELS[i / 36], LEVELS[i / 6 % 6], LEVELS[i % 6])
+                } else {
+                    let level = 8 + (index - 232) * 10;
+                    Color::from_rgb8(level, level, level)
+                };
+                (index, self.delta_e(&color))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .unwrap_or(16)
+    }
+
+    // `r,g,b` in decimal as in minttyrc and Konsole color schemes
+    pub fn to_mintty_color(&self) -> String {
+        let (red, green, blue) = self.to_rgb8();
+        format!("{},{},{}", red, green, blue)
     }
 
     pub fn to_24bit_be(&self) -> String {
-        format!("\x1b[48;2;{};{};{}m", self.red, self.green, self.blue)
+        let (red, green, blue) = self.to_rgb8();
+        format!("\x1b[48;2;{};{};{}m", red, green, blue)
+    }
+
+    pub fn to_24bit_fe(&self) -> String {
+        let (red, green, blue) = self.to_rgb8();
+        format!("\x1b[38;2;{};{};{}m", red, green, blue)
     }
 
     pub fn to_24bit_preview(&self) -> String {
-        format!("\x1b[38;2;{};{};{}m●", self.red, self.green, self.blue)
+        let (red, green, blue) = self.to_rgb8();
+        format!("\x1b[38;2;{};{};{}m●", red, green, blue)
+    }
+
+    // CIE L*a*b* coordinates under the D65 white point
+    pub fn to_lab(&self) -> (f64, f64, f64) {
+        let [red, green, blue] = self.linear_rgb();
+        let x = (0.4124 * red + 0.3576 * green + 0.1805 * blue) / 0.950_47;
+        let y = 0.2126 * red + 0.7152 * green + 0.0722 * blue;
+        let z = (0.0193 * red + 0.1192 * green + 0.9505 * blue) / 1.088_83;
+
+        let f = |t: f64| {
+            let delta: f64 = 6.0 / 29.0;
+            if t > delta.powi(3) {
+                t.cbrt()
+            } else {
+                t / (3.0 * delta.powi(2)) + 4.0 / 29.0
+            }
+        };
+        let (fx, fy, fz) = (f(x), f(y), f(z));
+        (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+
+    // Perceptual difference between two colors (CIE76 ΔE*ab)
+    pub fn delta_e(&self, other: &Color) -> f64 {
+        let (l1, a1, b1) = self.to_lab();
+        let (l2, a2, b2) = other.to_lab();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    // Relative luminance as defined by WCAG 2
+    pub fn relative_luminance(&self) -> f64 {
+        let [red, green, blue] = self.linear_rgb();
+        0.2126 * red + 0.7152 * green + 0.0722 * blue
+    }
+
+    // WCAG 2 contrast ratio between two colors, from 1.0 to 21.0
+    pub fn contrast_ratio(&self, other: &Color) -> f64 {
+        let luminance = self.relative_luminance();
+        let other_luminance = other.relative_luminance();
+        let (lighter, darker) = if luminance > other_luminance {
+            (luminance, other_luminance)
+        } else {
+            (other_luminance, luminance)
+        };
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    // Hue in degrees, saturation and lightness in the range of 0.0 to 1.0
+    pub fn to_hsl(&self) -> (f64, f64, f64) {
+        let red = f64::from(self.red) / 65535.0;
+        let green = f64::from(self.green) / 65535.0;
+        let blue = f64::from(self.blue) / 65535.0;
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+        if delta == 0.0 {
+            return (0.0, 0.0, lightness);
+        }
+
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == red {
+            60.0 * ((green - blue) / delta).rem_euclid(6.0)
+        } else if max == green {
+            60.0 * ((blue - red) / delta + 2.0)
+        } else {
+            60.0 * ((red - green) / delta + 4.0)
+        };
+        (hue, saturation, lightness)
+    }
+
+    // Inverse of `to_hsl()`
+    pub fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let h = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+        let (red, green, blue) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = lightness - chroma / 2.0;
+        Color::from_real_rgb(red + m, green + m, blue + m)
+    }
+
+    // Mixes the other color into this color in sRGB. The ratio is of the other color
+    pub fn mix(&self, other: &Color, ratio: f64) -> Self {
+        let component =
+            |a: u16, b: u16| (f64::from(a) * (1.0 - ratio) + f64::from(b) * ratio).round() as u16;
+        Color {
+            red: component(self.red, other.red),
+            green: component(self.green, other.green),
+            blue: component(self.blue, other.blue),
+        }
+    }
+
+    // Inverse of `linear_rgb()`. Components out of the range are clamped.
+    pub(crate) fn from_linear_rgb(rgb: [f64; 3]) -> Self {
+        let [red, green, blue] = rgb.map(|component| {
+            let c = component.clamp(0.0, 1.0);
+            let gamma = if c <= 0.003_130_8 {
+                c * 12.92
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            };
+            (gamma * 65535.0).round() as u16
+        });
+        Color { red, green, blue }
+    }
+
+    // sRGB components with the gamma removed, in the range of 0.0 to 1.0
+    pub(crate) fn linear_rgb(&self) -> [f64; 3] {
+        [self.red, self.green, self.blue].map(|component| {
+            let c = f64::from(component) / 65535.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        })
+    }
+}
+
+// Accepts the notations of `from_hex()` and `from_css_rgb()`, and `0xrrggbb` of `to_hex()`.
+impl FromStr for Color {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Some(hex) = s.strip_prefix("0x") {
+            Color::from_hex(&format!("#{}", hex))
+        } else if s.starts_with('#') {
+            Color::from_hex(s)
+        } else if s.starts_with("rgb") {
+            Color::from_css_rgb(s)
+        } else {
+            Err(ParseError::InvalidColorFormat(s.to_owned()).into())
+        }
     }
 }
 
 fn parse_int(s: &str) -> Result<u8> {
-    Ok(s.parse::<u8>().context(ParseError::ParseInt)?)
+    s.parse::<u8>().context(ParseError::ParseInt)
 }
 
 fn parse_hex(s: &str) -> Result<u8> {
-    Ok(u8::from_str_radix(s, 16).context(ParseError::ParseInt)?)
+    u8::from_str_radix(s, 16).context(ParseError::ParseInt)
+}
+
+/// Parses a line like `export COLOR_01="#44475a"` or `color_01: '#44475a'` in YAML into the name
+/// and the color.
+///
+/// `export`, quotes and spaces around `=` are optional. Returns `None` for lines that are not
+/// assignments of `#rrggbb` colors, like `export CURSOR_COLOR="$FOREGROUND_COLOR"`.
+fn parse_gogh_line(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start();
+    let line = match line.strip_prefix("export") {
+        Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+        _ => line,
+    };
+    let separator = line.find(['=', ':'])?;
+    let (name, value) = (&line[..separator], &line[separator + 1..]);
+    let name = name.trim_end();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    let value = value.trim_start();
+    let value = match value.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let value = &value[1..];
+            &value[..value.find(quote)?]
+        }
+        _ => value
+            .split(|c: char| c.is_whitespace() || c == ';')
+            .next()?,
+    };
+    let hex = value.strip_prefix('#')?;
+    if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some((name, value))
+    } else {
+        None
+    }
+}
+
+// Collects strings in nested tables with their dotted keys, like `primary.background`
+fn collect_toml_strings<'a>(
+    table: &'a toml::Table,
+    prefix: &str,
+    entries: &mut Vec<(String, &'a str)>,
+) {
+    for (key, value) in table {
+        let key = format!("{}{}", prefix, key);
+        match value {
+            toml::Value::String(s) => entries.push((key, s)),
+            toml::Value::Table(table) => collect_toml_strings(table, &format!("{}.", key), entries),
+            _ => {}
+        }
+    }
+}
+
+// Reads an NSColor archived by NSKeyedArchiver. RGB colors have components as text like
+// `0.1 0.2 0.3`, and grayscale colors have only the white
+fn unarchive_ns_color(data: &[u8]) -> Result<Color> {
+    let archive = plist::parse_binary(data)?;
+    let objects = archive
+        .get("$objects")
+        .and_then(plist::Value::as_array)
+        .ok_or(ParseError::NoRootDict)?;
+    let root = match archive.get("$top").and_then(|top| top.get("root")) {
+        Some(plist::Value::Uid(index)) => objects.get(*index as usize),
+        _ => None,
+    }
+    .ok_or(ParseError::NoRootDict)?;
+
+    let components = |key: &str| -> Option<Vec<f64>> {
+        let text = String::from_utf8_lossy(root.get(key)?.as_data()?).into_owned();
+        text.trim_end_matches('\0')
+            .split_whitespace()
+            .map(|component| component.parse().ok())
+            .collect()
+    };
+    if let Some(rgb) = components("NSRGB").filter(|rgb| rgb.len() >= 3) {
+        return Ok(Color::from_real_rgb(rgb[0], rgb[1], rgb[2]));
+    }
+    if let Some(white) = components("NSWhite").filter(|white| !white.is_empty()) {
+        return Ok(Color::from_real_rgb(white[0], white[0], white[0]));
+    }
+    Err(ParseError::InvalidColorFormat("NSColor".to_owned()).into())
+}
+
+// Parses a line of a Ghostty palette like `palette = 0=#21222c` into the index and the color
+fn parse_ghostty_palette(line: &str) -> Option<(usize, &str)> {
+    let (key, value) = line.split_once('=')?;
+    if key.trim() != "palette" {
+        return None;
+    }
+    let (index, color) = value.split_once('=')?;
+    Some((index.trim().parse().ok()?, color.trim()))
+}
+
+// Ghostty takes hex with or without `#`, and X11 color names
+fn ghostty_color(value: &str) -> Result<Color> {
+    if value.len() == 6 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+        Color::from_hex(&format!("#{}", value))
+    } else {
+        Color::from_x11(value)
+    }
+}
+
+// A value assigned in JavaScript, which is a string literal or a name of another variable
+enum JavaScriptValue<'a> {
+    String(&'a str),
+    Name(&'a str),
+}
+
+// Assignments like `black: '#000000'` and `const black = "#000000"` in JavaScript, in the order of
+// appearance. Other expressions are skipped
+fn javascript_assignments(content: &str) -> Vec<(&str, JavaScriptValue<'_>)> {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '$';
+    let mut assignments = Vec::new();
+    for (index, c) in content.char_indices() {
+        if c != ':' && c != '=' {
+            continue;
+        }
+        let before = content[..index].trim_end();
+        let before = before.strip_suffix(['\'', '"']).unwrap_or(before);
+        let name_start = before
+            .rfind(|c: char| !is_name_char(c))
+            .map_or(0, |i| i + 1);
+        let name = &before[name_start..];
+
+        let after = content[index + 1..].trim_start();
+        // Skip `==` and `=>`.
+        if name.is_empty() || after.starts_with(['=', '>']) {
+            continue;
+        }
+        let value = match after.chars().next() {
+            Some(quote @ ('\'' | '"' | '`')) => {
+                after[1..].split(quote).next().map(JavaScriptValue::String)
+            }
+            Some(_) => after
+                .split(|c: char| !is_name_char(c))
+                .next()
+                .filter(|name| !name.is_empty())
+                .map(JavaScriptValue::Name),
+            None => None,
+        };
+        if let Some(value) = value {
+            assignments.push((name, value));
+        }
+    }
+    assignments
+}
+
+// Strings of GVariant in dconf are quoted like `'rgb(0,0,0)'`
+fn unquote_gvariant(value: &str) -> &str {
+    value.trim_matches(|c| c == '\'' || c == '"')
+}
+
+// Session names in the registry are URL-encoded, like `Default%20Settings`
+fn decode_putty_session_name(name: &str) -> String {
+    let mut bytes = Vec::new();
+    let mut rest = name.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let decoded = match tail {
+            [high, low, ..] if byte == b'%' => std::str::from_utf8(&[*high, *low])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match decoded {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn wezterm_color(value: &toml::Value) -> Result<Color> {
+    let s = value
+        .as_str()
+        .ok_or_else(|| ParseError::InvalidColorFormat(format!("{:?}", value)))?;
+    css_or_x11_color(s)
+}
+
+// Parses `rgb()` of CSS or X11 colors, which GTK and some terminals take
+fn css_or_x11_color(s: &str) -> Result<Color> {
+    if s.starts_with("rgb(") || s.starts_with("rgba(") {
+        Color::from_css_rgb(s)
+    } else {
+        Color::from_x11(s)
+    }
+}
+
+// The object of colors in VS Code settings or a color theme, if it has terminal colors
+fn vscode_colors(root: &json::JsonValue) -> Option<&json::JsonValue> {
+    let colors = if root.has_key("workbench.colorCustomizations") {
+        &root["workbench.colorCustomizations"]
+    } else {
+        &root["colors"]
+    };
+    colors
+        .entries()
+        .any(|(key, _)| key.starts_with("terminal"))
+        .then_some(colors)
+}
+
+// Removes a comment, which starts with `#` after a whitespace outside quotes
+fn strip_yaml_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && previous.is_whitespace() => return &line[..i],
+            None => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+fn unquote_yaml(value: &str) -> &str {
+    for quote in ['"', '\''].iter() {
+        if let Some(inner) = value
+            .strip_prefix(*quote)
+            .and_then(|v| v.strip_suffix(*quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+/// Whether a minttyrc key is of a color, like `Red` or `CursorColour`, rather than an option like
+/// `BoldAsColour`.
+fn is_mintty_color_key(name: &str) -> bool {
+    let ansi_name = name.strip_prefix("Bold").unwrap_or(name);
+    let ansi_names = [
+        "Black", "Red", "Green", "Yellow", "Blue", "Magenta", "Cyan", "White",
+    ];
+    ansi_names.contains(&ansi_name) || (name.ends_with("Colour") && !name.ends_with("AsColour"))
+}
+
+fn parse_hex16(s: &str) -> Result<u16> {
+    u16::from_str_radix(s, 16).context(ParseError::ParseInt)
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16> {
+    let field = bytes
+        .get(offset..offset + 2)
+        .ok_or_else(|| ParseError::InvalidPalette("unexpected end of file".to_owned()))?;
+    Ok(u16::from_be_bytes([field[0], field[1]]))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32> {
+    Ok(u32::from(read_u16(bytes, offset)?) << 16 | u32::from(read_u16(bytes, offset + 2)?))
 }
 
-fn extract_text(element: &Element) -> Result<&str> {
-    let first = &element.children[0];
-    match first {
-        Xml::CharacterNode(ref text) => Ok(text),
-        _ => Err(ParseError::NotCharacterNode(Box::new(first.to_owned())).into()),
+// Removes comments and trailing commas, which are allowed in JSON files of Windows Terminal.
+fn strip_json_comments(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            result.push(c);
+            match c {
+                '\\' => result.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                result.push(c);
+            }
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|&next| next != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            ('}', _) | (']', _) => {
+                let trimmed_length = result.trim_end().len();
+                if result[..trimmed_length].ends_with(',') {
+                    result.truncate(trimmed_length - 1);
+                }
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
     }
+    result
 }
 
-fn extract_real_color(element: &Element) -> Result<u8> {
-    let real_value = extract_text(element)?
-        .parse::<f32>()
-        .context(ParseError::ParseFloat)?;
-    let int_value = (real_value * 255.0) as u8;
-    Ok(int_value)
+// The only color scheme of a parse result
+fn single(mut schemes: Vec<(Option<String>, ColorScheme)>) -> Result<ColorScheme> {
+    match schemes.len() {
+        0 => Err(ParseError::NoColorSchemes.into()),
+        1 => Ok(schemes.remove(0).1),
+        count => Err(ParseError::MultipleColorSchemes(count).into()),
+    }
 }
 
+// Sections of alacritty's colors other than text, with their keys and extended color names
+const ALACRITTY_UI_COLORS: [(&str, [(&str, &str); 2]); 5] = [
+    (
+        "selection",
+        [
+            ("text", "selection_foreground"),
+            ("background", "selection_background"),
+        ],
+    ),
+    (
+        "search.matches",
+        [
+            ("foreground", "search_match_foreground"),
+            ("background", "search_match_background"),
+        ],
+    ),
+    (
+        "search.focused_match",
+        [
+            ("foreground", "search_focused_match_foreground"),
+            ("background", "search_focused_match_background"),
+        ],
+    ),
+    (
+        "hints.start",
+        [
+            ("foreground", "hint_start_foreground"),
+            ("background", "hint_start_background"),
+        ],
+    ),
+    (
+        "hints.end",
+        [
+            ("foreground", "hint_end_foreground"),
+            ("background", "hint_end_background"),
+        ],
+    ),
+];
+
+// Keys of the ANSI colors in Windows Terminal color schemes
+const WINDOWS_TERMINAL_COLOR_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "purple",
+    "cyan",
+    "white",
+    "brightBlack",
+    "brightRed",
+    "brightGreen",
+    "brightYellow",
+    "brightBlue",
+    "brightPurple",
+    "brightCyan",
+    "brightWhite",
+];
+
+// Keys of the ANSI colors in `colors` of Hyper
+const HYPER_COLOR_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "lightBlack",
+    "lightRed",
+    "lightGreen",
+    "lightYellow",
+    "lightBlue",
+    "lightMagenta",
+    "lightCyan",
+    "lightWhite",
+];
+
+// Names of the ANSI colors in VS Code after `terminal.ansi`, and in Terminal.app between `ANSI`
+// and `Color`
+const PASCAL_CASE_COLOR_NAMES: [&str; 16] = [
+    "Black",
+    "Red",
+    "Green",
+    "Yellow",
+    "Blue",
+    "Magenta",
+    "Cyan",
+    "White",
+    "BrightBlack",
+    "BrightRed",
+    "BrightGreen",
+    "BrightYellow",
+    "BrightBlue",
+    "BrightMagenta",
+    "BrightCyan",
+    "BrightWhite",
+];
+
+// Attribute keys of the ANSI colors in JetBrains IDEs
+const JETBRAINS_CONSOLE_COLOR_NAMES: [&str; 16] = [
+    "CONSOLE_BLACK_OUTPUT",
+    "CONSOLE_RED_OUTPUT",
+    "CONSOLE_GREEN_OUTPUT",
+    "CONSOLE_YELLOW_OUTPUT",
+    "CONSOLE_BLUE_OUTPUT",
+    "CONSOLE_MAGENTA_OUTPUT",
+    "CONSOLE_CYAN_OUTPUT",
+    "CONSOLE_GRAY_OUTPUT",
+    "CONSOLE_DARKGRAY_OUTPUT",
+    "CONSOLE_RED_BRIGHT_OUTPUT",
+    "CONSOLE_GREEN_BRIGHT_OUTPUT",
+    "CONSOLE_YELLOW_BRIGHT_OUTPUT",
+    "CONSOLE_BLUE_BRIGHT_OUTPUT",
+    "CONSOLE_MAGENTA_BRIGHT_OUTPUT",
+    "CONSOLE_CYAN_BRIGHT_OUTPUT",
+    "CONSOLE_WHITE_OUTPUT",
+];
+
+// Indices of the ANSI colors in the order of the Windows console color table, which has blue in
+// the lowest bit instead of red
+const CONSOLE_COLOR_ORDER: [usize; 16] = [0, 4, 2, 6, 1, 5, 3, 7, 8, 12, 10, 14, 9, 13, 11, 15];
+
+// Names of the ANSI colors in the order of `ColorScheme::ansi_colors()`
+pub const ANSI_COLOR_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright black",
+    "bright red",
+    "bright green",
+    "bright yellow",
+    "bright blue",
+    "bright magenta",
+    "bright cyan",
+    "bright white",
+];
+
 #[derive(Default)]
 pub struct ColorScheme {
     foreground: Color,
@@ -168,22 +1297,48 @@ pub struct ColorScheme {
     bright_magenta: Color,
     bright_cyan: Color,
     bright_white: Color,
+
+    // Colors of other things than text that only some formats have, like `bold` and `underline`
+    extended_colors: Vec<(String, Color)>,
 }
 
 impl ColorScheme {
+    // From colors in the order of `ANSI_COLOR_NAMES`, without cursor colors
+    pub fn from_colors(foreground: Color, background: Color, ansi_colors: [Color; 16]) -> Self {
+        let mut scheme = ColorScheme {
+            foreground,
+            background,
+            ..ColorScheme::default()
+        };
+        for (index, color) in Vec::from(ansi_colors).into_iter().enumerate() {
+            if let Some(slot) = scheme.ansi_color_mut(index) {
+                *slot = color;
+            }
+        }
+        scheme
+    }
+
     // From a mintty color theme (.minttyrc)
     pub fn from_minttyrc(content: &str) -> Result<Self> {
         let mut scheme = ColorScheme::default();
         for line in content.lines() {
-            let components: Vec<&str> = line.split('=').collect();
-            if components.len() != 2 {
-                return Err(ParseError::InvalidLineFormat(line.to_owned()).into());
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::InvalidLineFormat(line.to_owned()))?;
+            let (name, value) = (name.trim(), value.trim());
+            // Skip options like `BoldAsColour=yes` and `Font=Consolas` in full config files.
+            if !is_mintty_color_key(name) {
+                continue;
             }
-            let name = components[0];
-            let color = Color::from_mintty_color(components[1])?;
+            let color = Color::from_mintty_color(value)?;
             match name {
                 "ForegroundColour" => scheme.foreground = color,
                 "BackgroundColour" => scheme.background = color,
+                "CursorColour" => scheme.cursor = Some(color),
                 "Black" => scheme.black = color,
                 "Red" => scheme.red = color,
                 "Green" => scheme.green = color,
@@ -200,7 +1355,17 @@ impl ColorScheme {
                 "BoldMagenta" => scheme.bright_magenta = color,
                 "BoldCyan" => scheme.bright_cyan = color,
                 "BoldWhite" => scheme.bright_white = color,
-                _ => return Err(ParseError::UnknownColorName(name.to_owned()).into()),
+                "IMECursorColour" => scheme.set_extended_color("ime_cursor", color),
+                "BoldColour" => scheme.set_extended_color("bold", color),
+                "UnderlineColour" => scheme.set_extended_color("underline", color),
+                "HoverColour" => scheme.set_extended_color("hover", color),
+                "HighlightForegroundColour" => {
+                    scheme.set_extended_color("selection_foreground", color)
+                }
+                "HighlightBackgroundColour" => {
+                    scheme.set_extended_color("selection_background", color)
+                }
+                _ => scheme.set_extended_color(name, color),
             }
         }
         Ok(scheme)
@@ -210,118 +1375,1203 @@ impl ColorScheme {
     pub fn from_iterm(content: &str) -> Result<Self> {
         let mut scheme = ColorScheme::default();
 
-        let root = content.parse::<Element>().context(ParseError::XMLParse)?;
-        let root_dict: &Element = root
-            .get_children("dict", None)
-            .nth(0)
-            .ok_or(ParseError::NoRootDict)?;
-
-        let keys = root_dict.get_children("key", None);
-        let values = root_dict.get_children("dict", None);
-        for (key, value) in keys.zip(values) {
-            let color_name = extract_text(key)?;
-
-            let mut color = Color::default();
-            // Extract element pairs like <key/><real/><key/><real/><key/><real/>
-            // `element.get_children()` doesn't work well here because there might be
-            //  a pattern like <key/><real/><key/><string/><key/><real/>.
-            //  In this case, we want to ignore the second pair (<key/><string/>).
-            let element_nodes = value
-                .children
-                .iter()
-                .flat_map(|child| match child {
-                    Xml::ElementNode(elem) => Some(elem),
-                    _ => None,
-                })
-                .collect::<Vec<_>>();
-            for pair in element_nodes.chunks(2) {
-                if let [color_key, color_value] = pair {
-                    let component_name = extract_text(color_key)?;
-                    match component_name {
-                        "Red Component" => color.red = extract_real_color(color_value)?,
-                        "Green Component" => color.green = extract_real_color(color_value)?,
-                        "Blue Component" => color.blue = extract_real_color(color_value)?,
-                        "Alpha Component" => {}
-                        "Color Space" => {}
-                        _ => {
-                            return Err(ParseError::UnknownColorComponent(
-                                component_name.to_owned(),
-                            )
-                            .into());
-                        }
-                    };
-                }
-            }
+        let root = plist::parse(content)?;
+        let root_dict = root.as_dict().ok_or(ParseError::NoRootDict)?;
+        for (color_name, value) in root_dict {
+            // Skip non-color entries, which some exported themes have.
+            let components = match value.as_dict() {
+                Some(components) => components,
+                None => continue,
+            };
 
-            match color_name {
-                "Ansi 0 Color" => scheme.black = color,
-                "Ansi 1 Color" => scheme.red = color,
-                "Ansi 2 Color" => scheme.green = color,
-                "Ansi 3 Color" => scheme.yellow = color,
-                "Ansi 4 Color" => scheme.blue = color,
-                "Ansi 5 Color" => scheme.magenta = color,
-                "Ansi 6 Color" => scheme.cyan = color,
-                "Ansi 7 Color" => scheme.white = color,
-                "Ansi 8 Color" => scheme.bright_black = color,
-                "Ansi 9 Color" => scheme.bright_red = color,
-                "Ansi 10 Color" => scheme.bright_green = color,
-                "Ansi 11 Color" => scheme.bright_yellow = color,
-                "Ansi 12 Color" => scheme.bright_blue = color,
-                "Ansi 13 Color" => scheme.bright_magenta = color,
-                "Ansi 14 Color" => scheme.bright_cyan = color,
-                "Ansi 15 Color" => scheme.bright_white = color,
-                "Background Color" => scheme.background = color,
-                "Foreground Color" => scheme.foreground = color,
-                "Cursor Color" => scheme.cursor = Some(color),
-                "Cursor Text Color" => scheme.cursor_text = Some(color),
-                _ => (),
+            let mut rgb = [0.0; 3];
+            for (component_name, component) in components {
+                let index = match component_name.as_str() {
+                    "Red Component" => 0,
+                    "Green Component" => 1,
+                    "Blue Component" => 2,
+                    "Alpha Component" | "Color Space" => continue,
+                    _ => {
+                        return Err(
+                            ParseError::UnknownColorComponent(component_name.to_owned()).into()
+                        );
+                    }
+                };
+                rgb[index] = component.as_f64().ok_or(ParseError::ParseFloat)?;
             }
+            let color = Color::from_real_rgb(rgb[0], rgb[1], rgb[2]);
+
+            scheme.set_iterm_color(color_name, color);
         }
 
         Ok(scheme)
     }
 
-    // From a gogh color theme file (.sh)
-    pub fn from_gogh(content: &str) -> Result<Self> {
-        // Match against export XXX="yyy"
-        let pattern = Regex::new(r#"export ([A-Z0-9_]+)="(#[0-9a-fA-F]{6})""#).unwrap();
-        let mut scheme = ColorScheme::default();
-        for line in content.lines() {
-            if let Some(caps) = pattern.captures(line) {
-                let name = caps.get(1).unwrap().as_str();
-                let color = Color::from_gogh_color(caps.get(2).unwrap().as_str())?;
-                match name {
-                    "FOREGROUND_COLOR" => scheme.foreground = color,
-                    "BACKGROUND_COLOR" => scheme.background = color,
-                    "COLOR_01" => scheme.black = color,
-                    "COLOR_02" => scheme.red = color,
-                    "COLOR_03" => scheme.green = color,
-                    "COLOR_04" => scheme.yellow = color,
-                    "COLOR_05" => scheme.blue = color,
-                    "COLOR_06" => scheme.magenta = color,
-                    "COLOR_07" => scheme.cyan = color,
-                    "COLOR_08" => scheme.white = color,
-                    "COLOR_09" => scheme.bright_black = color,
-                    "COLOR_10" => scheme.bright_red = color,
-                    "COLOR_11" => scheme.bright_green = color,
-                    "COLOR_12" => scheme.bright_yellow = color,
-                    "COLOR_13" => scheme.bright_blue = color,
-                    "COLOR_14" => scheme.bright_magenta = color,
-                    "COLOR_15" => scheme.bright_cyan = color,
-                    "COLOR_16" => scheme.bright_white = color,
-                    _ => {}
+    // From iTerm 2 dynamic profiles (.json), which can contain multiple profiles
+    pub fn from_iterm_dynamic_profiles(content: &str) -> Result<Vec<(String, Self)>> {
+        let root = json::parse(content).context(ParseError::JSONParse)?;
+        let mut schemes = Vec::new();
+        for profile in root["Profiles"].members() {
+            let mut scheme = ColorScheme::default();
+            for (key, value) in profile.entries() {
+                if !value.has_key("Red Component") {
+                    continue;
                 }
+                let component = |name: &str| value[name].as_f64().ok_or(ParseError::ParseFloat);
+                let color = Color::from_real_rgb(
+                    component("Red Component")?,
+                    component("Green Component")?,
+                    component("Blue Component")?,
+                );
+                scheme.set_iterm_color(key, color);
             }
+            let name = profile["Name"].as_str().unwrap_or_default().to_owned();
+            schemes.push((name, scheme));
         }
-        Ok(scheme)
+        Ok(schemes)
     }
 
-    // Output YAML that can be used as a color theme in .alacritty.yml
-    pub fn to_yaml(&self) -> String {
-        let cursor_colors = match (&self.cursor_text, &self.cursor) {
-            (Some(cursor_text), Some(cursor)) => format!(
-                "
-  # Cursor colors
+    // From a color scheme object of Windows Terminal, like `{"name": "Dracula", "black": ...}`.
+    // Also accepts settings with only one color scheme
+    pub fn from_windows_terminal(content: &str) -> Result<Self> {
+        let schemes = ColorScheme::from_windows_terminal_settings(content)?;
+        single(
+            schemes
+                .into_iter()
+                .map(|(name, scheme)| (Some(name), scheme))
+                .collect(),
+        )
+    }
+
+    // From Windows Terminal settings (settings.json), an array of color schemes, or a single
+    // color scheme
+    pub fn from_windows_terminal_settings(content: &str) -> Result<Vec<(String, Self)>> {
+        let root = json::parse(&strip_json_comments(content)).context(ParseError::JSONParse)?;
+        let scheme_values: Vec<&json::JsonValue> = if root.has_key("schemes") {
+            root["schemes"].members().collect()
+        } else if root.is_array() {
+            root.members().collect()
+        } else if root.has_key("background") || root.has_key("black") {
+            vec![&root]
+        } else {
+            Vec::new()
+        };
+
+        let mut schemes = Vec::new();
+        for value in scheme_values {
+            let mut scheme = ColorScheme::default();
+            for (key, color_value) in value.entries() {
+                let color = match color_value.as_str() {
+                    Some(hex) if hex.starts_with('#') => Color::from_hex(hex)?,
+                    _ => continue,
+                };
+                match key {
+                    "foreground" => scheme.foreground = color,
+                    "background" => scheme.background = color,
+                    "cursorColor" => scheme.cursor = Some(color),
+                    "selectionBackground" => {
+                        scheme.set_extended_color("selection_background", color)
+                    }
+                    _ => {
+                        let index = WINDOWS_TERMINAL_COLOR_NAMES.iter().position(|&n| n == key);
+                        if let Some(slot) = index.and_then(|index| scheme.ansi_color_mut(index)) {
+                            *slot = color;
+                        }
+                    }
+                }
+            }
+            let name = value["name"].as_str().unwrap_or_default().to_owned();
+            schemes.push((name, scheme));
+        }
+        Ok(schemes)
+    }
+
+    // From X resources with one set of colors (.Xresources). When resources have multiple
+    // prefixes, the wildcard (`*`) colors are taken. Use `from_xresources_by_prefix()` for the
+    // colors of the other prefixes
+    pub fn from_xresources(content: &str) -> Result<Self> {
+        let mut schemes = ColorScheme::from_xresources_by_prefix(content)?;
+        match schemes.first() {
+            None => Err(ParseError::NoColorSchemes.into()),
+            Some((prefix, _)) if prefix == "*" || schemes.len() == 1 => Ok(schemes.remove(0).1),
+            Some(_) => Err(ParseError::MultipleColorSchemes(schemes.len()).into()),
+        }
+    }
+
+    // From X resources (.Xresources), by prefix
+    //
+    // Each resource prefix such as `URxvt` or `XTerm` makes a color scheme. Like X does, colors
+    // that are not specified for a prefix fall back to the wildcard (`*`) ones.
+    pub fn from_xresources_by_prefix(content: &str) -> Result<Vec<(String, Self)>> {
+        let mut prefixes: Vec<(String, Vec<(String, Color)>)> = Vec::new();
+        // Macros of the C preprocessor, which themes like Solarized use to name colors.
+        let mut defines: Vec<(&str, &str)> = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if let Some(define) = line.strip_prefix("#define") {
+                let mut words = define.split_whitespace();
+                if let (Some(name), Some(value)) = (words.next(), words.next()) {
+                    defines.push((name, value));
+                }
+                continue;
+            }
+            if line.is_empty() || line.starts_with('!') || line.starts_with('#') {
+                continue;
+            }
+            let (resource, value) = line
+                .split_once(':')
+                .ok_or_else(|| ParseError::InvalidLineFormat(line.to_owned()))?;
+            let resource = resource.trim();
+            let split_at = resource.rfind(['.', '*']).map_or(0, |i| i + 1);
+            let (prefix, name) = resource.split_at(split_at);
+            if ColorScheme::default().xresources_color_mut(name).is_none() {
+                continue;
+            }
+            let prefix = match prefix.trim_end_matches(['.', '*']) {
+                "" => "*",
+                prefix => prefix,
+            };
+            let value = value.trim();
+            let value = defines
+                .iter()
+                .rev()
+                .find(|(name, _)| *name == value)
+                .map_or(value, |(_, defined)| *defined);
+            let color = Color::from_x11(value)?;
+
+            match prefixes.iter_mut().find(|(p, _)| p == prefix) {
+                Some((_, colors)) => colors.push((name.to_owned(), color)),
+                None => prefixes.push((prefix.to_owned(), vec![(name.to_owned(), color)])),
+            }
+        }
+
+        // List the wildcard color scheme first.
+        prefixes.sort_by_key(|(prefix, _)| prefix != "*");
+        let wildcard_colors = match prefixes.first() {
+            Some((prefix, colors)) if prefix == "*" => colors.clone(),
+            _ => Vec::new(),
+        };
+        let mut schemes = Vec::new();
+        for (prefix, colors) in prefixes {
+            let mut scheme = ColorScheme::default();
+            // Prefixed colors override the wildcard ones.
+            for (name, color) in wildcard_colors.iter().chain(colors.iter()) {
+                if let Some(slot) = scheme.xresources_color_mut(name) {
+                    *slot = color.clone();
+                }
+            }
+            schemes.push((prefix, scheme));
+        }
+        Ok(schemes)
+    }
+
+    // From content in the given format that contains exactly one color scheme
+    pub fn parse(content: &str, format: &ColorSchemeFormat) -> Result<Self> {
+        single(ColorScheme::parse_all(content, format)?)
+    }
+
+    // Same as `parse()`, but also accepts binary formats
+    pub fn parse_bytes(content: &[u8], format: &ColorSchemeFormat) -> Result<Self> {
+        single(ColorScheme::parse_all_bytes(content, format)?)
+    }
+
+    // Same as `parse_all()`, but also accepts binary formats
+    pub fn parse_all_bytes(
+        content: &[u8],
+        format: &ColorSchemeFormat,
+    ) -> Result<Vec<(Option<String>, Self)>> {
+        match format {
+            ColorSchemeFormat::Ase => Ok(vec![(None, ColorScheme::from_ase(content)?)]),
+            ColorSchemeFormat::TerminalApp => {
+                Ok(vec![(None, ColorScheme::from_terminal_app(content)?)])
+            }
+            _ => {
+                // Registry files exported by regedit are in UTF-16.
+                if let Some(utf16) = content.strip_prefix(&[0xff, 0xfe]) {
+                    let units: Vec<u16> = utf16
+                        .chunks_exact(2)
+                        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                        .collect();
+                    let content = String::from_utf16(&units).context("File is not in UTF-16")?;
+                    return ColorScheme::parse_all(&content, format);
+                }
+                let content = std::str::from_utf8(content).context("File is not in UTF-8")?;
+                ColorScheme::parse_all(content, format)
+            }
+        }
+    }
+
+    // All color schemes in content of the given format with their names. Color schemes of
+    // formats that don't have names and files that contain only one color scheme are not named.
+    pub fn parse_all(
+        content: &str,
+        format: &ColorSchemeFormat,
+    ) -> Result<Vec<(Option<String>, Self)>> {
+        let named_schemes = match format {
+            ColorSchemeFormat::ITerm => return Ok(vec![(None, ColorScheme::from_iterm(content)?)]),
+            ColorSchemeFormat::Mintty => {
+                return Ok(vec![(None, ColorScheme::from_minttyrc(content)?)])
+            }
+            ColorSchemeFormat::Gogh => return Ok(vec![(None, ColorScheme::from_gogh(content)?)]),
+            ColorSchemeFormat::AlacrittyYaml => {
+                return Ok(vec![(None, ColorScheme::from_alacritty_yaml(content)?)])
+            }
+            ColorSchemeFormat::AlacrittyToml => {
+                return Ok(vec![(None, ColorScheme::from_alacritty_toml(content)?)])
+            }
+            ColorSchemeFormat::Konsole => {
+                return Ok(vec![(None, ColorScheme::from_konsole(content)?)])
+            }
+            ColorSchemeFormat::Xfce4Terminal => {
+                return Ok(vec![(None, ColorScheme::from_xfce4_terminal(content)?)])
+            }
+            ColorSchemeFormat::Foot => return Ok(vec![(None, ColorScheme::from_foot(content)?)]),
+            ColorSchemeFormat::Ghostty => {
+                return Ok(vec![(None, ColorScheme::from_ghostty(content)?)])
+            }
+            ColorSchemeFormat::Termux => {
+                return Ok(vec![(None, ColorScheme::from_termux(content)?)])
+            }
+            ColorSchemeFormat::St => return Ok(vec![(None, ColorScheme::from_st(content)?)]),
+            ColorSchemeFormat::Hyper => return Ok(vec![(None, ColorScheme::from_hyper(content)?)]),
+            ColorSchemeFormat::Rio => return Ok(vec![(None, ColorScheme::from_rio(content)?)]),
+            ColorSchemeFormat::VSCode => {
+                return Ok(vec![(None, ColorScheme::from_vscode(content)?)])
+            }
+            ColorSchemeFormat::WezTerm => {
+                return Ok(vec![(None, ColorScheme::from_wezterm(content)?)])
+            }
+            ColorSchemeFormat::Gpl => return Ok(vec![(None, ColorScheme::from_gpl(content)?)]),
+            ColorSchemeFormat::Ase => {
+                return Ok(vec![(None, ColorScheme::from_ase(content.as_bytes())?)])
+            }
+            ColorSchemeFormat::TerminalApp => {
+                return Ok(vec![(
+                    None,
+                    ColorScheme::from_terminal_app(content.as_bytes())?,
+                )])
+            }
+            ColorSchemeFormat::ITermDynamicProfiles => {
+                ColorScheme::from_iterm_dynamic_profiles(content)?
+            }
+            ColorSchemeFormat::WindowsTerminal => {
+                ColorScheme::from_windows_terminal_settings(content)?
+            }
+            ColorSchemeFormat::Dconf => {
+                let mut schemes = ColorScheme::from_dconf_profiles(content)?;
+                if schemes.len() == 1 {
+                    return Ok(vec![(None, schemes.remove(0).1)]);
+                }
+                schemes
+            }
+            ColorSchemeFormat::PuTTY => {
+                let mut schemes = ColorScheme::from_putty_sessions(content)?;
+                if schemes.len() == 1 {
+                    return Ok(vec![(None, schemes.remove(0).1)]);
+                }
+                schemes
+            }
+            ColorSchemeFormat::Xresources => {
+                let mut schemes = ColorScheme::from_xresources_by_prefix(content)?;
+                if schemes.len() == 1 {
+                    return Ok(vec![(None, schemes.remove(0).1)]);
+                }
+                schemes
+            }
+        };
+        Ok(named_schemes
+            .into_iter()
+            .map(|(name, scheme)| (Some(name), scheme))
+            .collect())
+    }
+
+    // From a GIMP palette (.gpl). Colors are assigned to the slots by their names or heuristically.
+    pub fn from_gpl(content: &str) -> Result<Self> {
+        let mut lines = content.lines();
+        if lines.next().map(str::trim) != Some("GIMP Palette") {
+            return Err(
+                ParseError::InvalidPalette("missing GIMP Palette header".to_owned()).into(),
+            );
+        }
+        let mut colors = Vec::new();
+        for line in lines {
+            let line = line.trim();
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("Name:")
+                || line.starts_with("Columns:")
+            {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let mut component = || {
+                fields
+                    .next()
+                    .ok_or_else(|| ParseError::InvalidLineFormat(line.to_owned()).into())
+                    .and_then(parse_int)
+            };
+            let color = Color::from_rgb8(component()?, component()?, component()?);
+            let name = fields.collect::<Vec<_>>().join(" ");
+            colors.push((name, color));
+        }
+        crate::extract::from_palette(&colors).ok_or_else(|| ParseError::EmptyPalette.into())
+    }
+
+    // From an Adobe Swatch Exchange file (.ase). Colors are assigned like `from_gpl()`.
+    pub fn from_ase(content: &[u8]) -> Result<Self> {
+        if !content.starts_with(b"ASEF") {
+            return Err(ParseError::InvalidPalette("missing ASEF signature".to_owned()).into());
+        }
+        let mut colors = Vec::new();
+        // Skip the signature, the version and the number of blocks.
+        let mut offset = 12;
+        while offset < content.len() {
+            let block_type = read_u16(content, offset)?;
+            let length = read_u32(content, offset + 2)? as usize;
+            let block = content
+                .get(offset + 6..offset + 6 + length)
+                .ok_or_else(|| ParseError::InvalidPalette("unexpected end of file".to_owned()))?;
+            offset += 6 + length;
+            // Other blocks are the starts and the ends of groups.
+            if block_type != 0x0001 {
+                continue;
+            }
+
+            // The name is in UTF-16 with the terminating null.
+            let name_length = read_u16(block, 0)? as usize;
+            let name_units = (0..name_length)
+                .map(|i| read_u16(block, 2 + 2 * i))
+                .collect::<Result<Vec<_>>>()?;
+            let name = String::from_utf16_lossy(&name_units)
+                .trim_end_matches('\0')
+                .to_owned();
+            let body = &block[2 + 2 * name_length..];
+            let model = body
+                .get(..4)
+                .ok_or_else(|| ParseError::InvalidPalette("unexpected end of file".to_owned()))?;
+            let values: Vec<f64> = body[4..]
+                .chunks_exact(4)
+                .map(|value| {
+                    f64::from(f32::from_be_bytes([value[0], value[1], value[2], value[3]]))
+                })
+                .collect();
+            let color = match (model, values.as_slice()) {
+                (b"RGB ", [red, green, blue, ..]) => Color::from_real_rgb(*red, *green, *blue),
+                (b"Gray", [gray, ..]) => Color::from_real_rgb(*gray, *gray, *gray),
+                (b"CMYK", [cyan, magenta, yellow, black, ..]) => Color::from_real_rgb(
+                    (1.0 - cyan) * (1.0 - black),
+                    (1.0 - magenta) * (1.0 - black),
+                    (1.0 - yellow) * (1.0 - black),
+                ),
+                _ => {
+                    let model = String::from_utf8_lossy(model).trim().to_owned();
+                    return Err(ParseError::UnsupportedColorModel(model).into());
+                }
+            };
+            colors.push((name, color));
+        }
+        crate::extract::from_palette(&colors).ok_or_else(|| ParseError::EmptyPalette.into())
+    }
+
+    // From a gogh color theme file, either a shell script (.sh) or YAML (.yml) of newer themes
+    pub fn from_gogh(content: &str) -> Result<Self> {
+        let mut scheme = ColorScheme::default();
+        for line in content.lines() {
+            if let Some((name, value)) = parse_gogh_line(line) {
+                let color = Color::from_gogh_color(value)?;
+                match name.to_ascii_uppercase().as_str() {
+                    "FOREGROUND_COLOR" | "FOREGROUND" => scheme.foreground = color,
+                    "BACKGROUND_COLOR" | "BACKGROUND" => scheme.background = color,
+                    "CURSOR_COLOR" | "CURSOR" => scheme.cursor = Some(color),
+                    "COLOR_01" => scheme.black = color,
+                    "COLOR_02" => scheme.red = color,
+                    "COLOR_03" => scheme.green = color,
+                    "COLOR_04" => scheme.yellow = color,
+                    "COLOR_05" => scheme.blue = color,
+                    "COLOR_06" => scheme.magenta = color,
+                    "COLOR_07" => scheme.cyan = color,
+                    "COLOR_08" => scheme.white = color,
+                    "COLOR_09" => scheme.bright_black = color,
+                    "COLOR_10" => scheme.bright_red = color,
+                    "COLOR_11" => scheme.bright_green = color,
+                    "COLOR_12" => scheme.bright_yellow = color,
+                    "COLOR_13" => scheme.bright_blue = color,
+                    "COLOR_14" => scheme.bright_magenta = color,
+                    "COLOR_15" => scheme.bright_cyan = color,
+                    "COLOR_16" => scheme.bright_white = color,
+                    _ => {}
+                }
+            }
+        }
+        Ok(scheme)
+    }
+
+    // Parse the `colors` section of an alacritty YAML config. Other sections and colors that take
+    // the colors of cells, like `CellForeground`, are ignored
+    pub fn from_alacritty_yaml(content: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+        // Keys of the mappings that the current line is in, with their indentation.
+        let mut parents: Vec<(usize, &str)> = Vec::new();
+        for line in content.lines() {
+            let line = strip_yaml_comment(line).trim_end();
+            let key_start = line.len() - line.trim_start().len();
+            let (key, value) = match line.trim_start().split_once(':') {
+                // Items of lists like `indexed_colors` and text of multi-line strings.
+                Some((key, _)) if key.starts_with('-') => continue,
+                Some((key, value)) => (unquote_yaml(key.trim()), unquote_yaml(value.trim())),
+                None => continue,
+            };
+            while matches!(parents.last(), Some((indent, _)) if *indent >= key_start) {
+                parents.pop();
+            }
+            // Mappings can have anchors to refer to them from other places.
+            if value.is_empty() || value.starts_with('&') {
+                parents.push((key_start, key));
+                continue;
+            }
+            if parents.first().map(|(_, key)| *key) == Some("colors") {
+                let path: Vec<&str> = parents[1..].iter().map(|(_, key)| *key).collect();
+                entries.push((format!("{}.{}", path.join("."), key), value));
+            }
+        }
+        ColorScheme::from_alacritty_colors(&entries)
+    }
+
+    // Parse the `colors` table of an alacritty TOML config or theme. Other tables and colors that
+    // take the colors of cells, like `CellForeground`, are ignored
+    pub fn from_alacritty_toml(content: &str) -> Result<Self> {
+        let root: toml::Table = content.parse().context(ParseError::TOMLParse)?;
+        let mut entries = Vec::new();
+        if let Some(colors) = root.get("colors").and_then(toml::Value::as_table) {
+            collect_toml_strings(colors, "", &mut entries);
+        }
+        ColorScheme::from_alacritty_colors(&entries)
+    }
+
+    // From a color scheme of Konsole (.colorscheme), which has a section for each color like
+    // `[Color0Intense]`. The intense foreground is taken as the bold color, and the other intense
+    // and faint variants of the default colors are ignored
+    pub fn from_konsole(content: &str) -> Result<Self> {
+        let mut scheme = ColorScheme::default();
+        let mut section = "";
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name;
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::InvalidLineFormat(line.to_owned()))?;
+            if key.trim() != "Color" {
+                continue;
+            }
+            let color = Color::from_mintty_color(value.trim())?;
+            match section {
+                "Foreground" => scheme.foreground = color,
+                "Background" => scheme.background = color,
+                "ForegroundIntense" => scheme.set_extended_color("bold", color),
+                _ => {
+                    let (index, offset) = match section.strip_suffix("Intense") {
+                        Some(index) => (index, 8),
+                        None => (section, 0),
+                    };
+                    let index = index
+                        .strip_prefix("Color")
+                        .and_then(|i| i.parse::<usize>().ok());
+                    if let Some(slot) = index
+                        .filter(|&index| index < 8)
+                        .and_then(|index| scheme.ansi_color_mut(index + offset))
+                    {
+                        *slot = color;
+                    }
+                }
+            }
+        }
+        Ok(scheme)
+    }
+
+    // From the terminal colors of VS Code in `workbench.colorCustomizations` of settings.json or
+    // `colors` of a color theme. The editor colors are taken for the default colors that are not
+    // customized for the terminal, like VS Code does
+    pub fn from_vscode(content: &str) -> Result<Self> {
+        let root = json::parse(&strip_json_comments(content)).context(ParseError::JSONParse)?;
+        let colors = vscode_colors(&root).ok_or(ParseError::NoColorSchemes)?;
+        let mut scheme = ColorScheme::default();
+        if let Some(hex) = colors["editor.foreground"].as_str() {
+            scheme.foreground = Color::from_hex(hex)?;
+        }
+        if let Some(hex) = colors["editor.background"].as_str() {
+            scheme.background = Color::from_hex(hex)?;
+        }
+        for (key, value) in colors.entries() {
+            let hex = match value.as_str() {
+                Some(hex) => hex,
+                None => continue,
+            };
+            let slot = match key {
+                "terminal.foreground" => &mut scheme.foreground,
+                "terminal.background" => &mut scheme.background,
+                "terminalCursor.foreground" => scheme.cursor.get_or_insert_with(Color::default),
+                "terminalCursor.background" => {
+                    scheme.cursor_text.get_or_insert_with(Color::default)
+                }
+                "terminal.selectionForeground" | "terminal.selectionBackground" => {
+                    let name = if key.ends_with("Foreground") {
+                        "selection_foreground"
+                    } else {
+                        "selection_background"
+                    };
+                    scheme.set_extended_color(name, Color::from_hex(hex)?);
+                    continue;
+                }
+                _ => {
+                    let index = key
+                        .strip_prefix("terminal.ansi")
+                        .and_then(|name| PASCAL_CASE_COLOR_NAMES.iter().position(|&n| n == name));
+                    match index.and_then(|index| scheme.ansi_color_mut(index)) {
+                        Some(slot) => slot,
+                        None => continue,
+                    }
+                }
+            };
+            *slot = Color::from_hex(hex)?;
+        }
+        Ok(scheme)
+    }
+
+    // From a color scheme of WezTerm, which is a TOML file with the `colors` table like
+    // `ansi = ["#000000", ...]`
+    pub fn from_wezterm(content: &str) -> Result<Self> {
+        let root: toml::Table = content.parse().context(ParseError::TOMLParse)?;
+        let colors = root
+            .get("colors")
+            .and_then(toml::Value::as_table)
+            .ok_or(ParseError::NoColorSchemes)?;
+        let mut scheme = ColorScheme::default();
+        for (key, value) in colors {
+            if let toml::Value::Array(values) = value {
+                let offset = match key.as_str() {
+                    "ansi" => 0,
+                    "brights" => 8,
+                    _ => continue,
+                };
+                for (index, value) in values.iter().take(8).enumerate() {
+                    let color = wezterm_color(value)?;
+                    if let Some(slot) = scheme.ansi_color_mut(offset + index) {
+                        *slot = color;
+                    }
+                }
+                continue;
+            }
+            let color = match value {
+                // Not colored, like `selection_fg = "none"` to keep the color of text.
+                toml::Value::String(s) if s == "none" => continue,
+                toml::Value::String(_) => wezterm_color(value)?,
+                _ => continue,
+            };
+            match key.as_str() {
+                "foreground" => scheme.foreground = color,
+                "background" => scheme.background = color,
+                "cursor_bg" => scheme.cursor = Some(color),
+                "cursor_fg" => scheme.cursor_text = Some(color),
+                "selection_fg" => scheme.set_extended_color("selection_foreground", color),
+                "selection_bg" => scheme.set_extended_color("selection_background", color),
+                // Like `cursor_border` and `scrollbar_thumb`.
+                _ => scheme.set_extended_color(key, color),
+            }
+        }
+        Ok(scheme)
+    }
+
+    // From a Rio theme or config, which has colors like `light-blue` in the `colors` table. Dim
+    // colors are ignored
+    pub fn from_rio(content: &str) -> Result<Self> {
+        let root: toml::Table = content.parse().context(ParseError::TOMLParse)?;
+        let colors = root
+            .get("colors")
+            .and_then(toml::Value::as_table)
+            .ok_or(ParseError::NoColorSchemes)?;
+        let mut scheme = ColorScheme::default();
+        for (key, value) in colors {
+            let color = match value.as_str() {
+                Some(s) => Color::from_hex(s)?,
+                None => continue,
+            };
+            match key.as_str() {
+                "foreground" => scheme.foreground = color,
+                "background" => scheme.background = color,
+                "cursor" => scheme.cursor = Some(color),
+                "selection-foreground" => scheme.set_extended_color("selection_foreground", color),
+                "selection-background" => scheme.set_extended_color("selection_background", color),
+                _ => {
+                    let (name, offset) = match key.strip_prefix("light-") {
+                        Some(name) => (name, 8),
+                        None => (key.as_str(), 0),
+                    };
+                    let index = ANSI_COLOR_NAMES[..8].iter().position(|n| *n == name);
+                    if let Some(slot) =
+                        index.and_then(|index| scheme.ansi_color_mut(index + offset))
+                    {
+                        *slot = color;
+                    }
+                }
+            }
+        }
+        Ok(scheme)
+    }
+
+    // From a color scheme of xfce4-terminal (.theme), which has the ANSI colors in one line like
+    // `ColorPalette=#000000;#aa0000;...`
+    pub fn from_xfce4_terminal(content: &str) -> Result<Self> {
+        let mut scheme = ColorScheme::default();
+        for line in content.lines() {
+            let line = line.trim();
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+            if value.is_empty() {
+                continue;
+            }
+            match key {
+                "ColorForeground" => scheme.foreground = css_or_x11_color(value)?,
+                "ColorBackground" => scheme.background = css_or_x11_color(value)?,
+                "ColorCursor" => scheme.cursor = Some(css_or_x11_color(value)?),
+                "ColorCursorForeground" => scheme.cursor_text = Some(css_or_x11_color(value)?),
+                "ColorBold" => scheme.set_extended_color("bold", css_or_x11_color(value)?),
+                "ColorSelection" => {
+                    scheme.set_extended_color("selection_foreground", css_or_x11_color(value)?)
+                }
+                "ColorSelectionBackground" => {
+                    scheme.set_extended_color("selection_background", css_or_x11_color(value)?)
+                }
+                "ColorPalette" => {
+                    let colors = value.split(';').filter(|color| !color.trim().is_empty());
+                    for (index, color) in colors.enumerate() {
+                        match scheme.ansi_color_mut(index) {
+                            Some(slot) => *slot = css_or_x11_color(color.trim())?,
+                            None => {
+                                return Err(ParseError::InvalidLineFormat(line.to_owned()).into())
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(scheme)
+    }
+
+    // From the `colors` and `cursor` sections of a foot config (foot.ini) or theme, which have
+    // colors in hex without `#`
+    pub fn from_foot(content: &str) -> Result<Self> {
+        let foot_color = |value: &str| Color::from_hex(&format!("#{}", value));
+        let mut scheme = ColorScheme::default();
+        let mut section = "";
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name;
+                continue;
+            }
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+            match (section, key) {
+                ("cursor", "color") => {
+                    // The color of text under the cursor, and the cursor.
+                    let mut colors = value.split_whitespace();
+                    if let (Some(text), Some(cursor)) = (colors.next(), colors.next()) {
+                        scheme.cursor_text = Some(foot_color(text)?);
+                        scheme.cursor = Some(foot_color(cursor)?);
+                    }
+                }
+                ("colors", "foreground") => scheme.foreground = foot_color(value)?,
+                ("colors", "background") => scheme.background = foot_color(value)?,
+                ("colors", "selection-foreground") => {
+                    scheme.set_extended_color("selection_foreground", foot_color(value)?)
+                }
+                ("colors", "selection-background") => {
+                    scheme.set_extended_color("selection_background", foot_color(value)?)
+                }
+                ("colors", _) => {
+                    let (index, offset) = if let Some(index) = key.strip_prefix("regular") {
+                        (index, 0)
+                    } else if let Some(index) = key.strip_prefix("bright") {
+                        (index, 8)
+                    } else {
+                        continue;
+                    };
+                    let index = index.parse::<usize>().ok().filter(|&index| index < 8);
+                    if let Some(slot) =
+                        index.and_then(|index| scheme.ansi_color_mut(index + offset))
+                    {
+                        *slot = foot_color(value)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(scheme)
+    }
+
+    // From a Ghostty config or theme, which has the ANSI colors like `palette = 0=#21222c`.
+    // Colors of the palette beyond the ANSI colors are ignored
+    pub fn from_ghostty(content: &str) -> Result<Self> {
+        let mut scheme = ColorScheme::default();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            if let Some((index, value)) = parse_ghostty_palette(line) {
+                if let Some(slot) = scheme.ansi_color_mut(index) {
+                    *slot = ghostty_color(value)?;
+                }
+                continue;
+            }
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+            match key {
+                "foreground" => scheme.foreground = ghostty_color(value)?,
+                "background" => scheme.background = ghostty_color(value)?,
+                "cursor-color" => scheme.cursor = Some(ghostty_color(value)?),
+                "cursor-text" => scheme.cursor_text = Some(ghostty_color(value)?),
+                "selection-foreground" => {
+                    scheme.set_extended_color("selection_foreground", ghostty_color(value)?)
+                }
+                "selection-background" => {
+                    scheme.set_extended_color("selection_background", ghostty_color(value)?)
+                }
+                _ => {}
+            }
+        }
+        Ok(scheme)
+    }
+
+    // From `colors.properties` of Termux, like `color0=#21222c` and `foreground=#f8f8f2`
+    pub fn from_termux(content: &str) -> Result<Self> {
+        let mut scheme = ColorScheme::default();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('#') || line.starts_with('!') {
+                continue;
+            }
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+            match key {
+                "foreground" => scheme.foreground = Color::from_hex(value)?,
+                "background" => scheme.background = Color::from_hex(value)?,
+                "cursor" => scheme.cursor = Some(Color::from_hex(value)?),
+                _ => {
+                    let index = key.strip_prefix("color").and_then(|i| i.parse().ok());
+                    if let Some(slot) = index.and_then(|index| scheme.ansi_color_mut(index)) {
+                        *slot = Color::from_hex(value)?;
+                    }
+                }
+            }
+        }
+        Ok(scheme)
+    }
+
+    // From the `colorname[]` array in `config.h` of st. Entries are X11 color names or hex, and
+    // can have indices like `[256] = "#cccccc"`. `defaultfg`, `defaultbg` and `defaultcs` pick the
+    // foreground, the background and the cursor out of the array
+    pub fn from_st(content: &str) -> Result<Self> {
+        let content = strip_json_comments(content);
+        let array = content
+            .find("colorname[]")
+            .and_then(|start| {
+                let rest = &content[start..];
+                let body = &rest[rest.find('{')? + 1..];
+                Some(&body[..body.find('}')?])
+            })
+            .ok_or(ParseError::NoColorSchemes)?;
+
+        let mut colors: HashMap<usize, Color> = HashMap::new();
+        let mut index = 0;
+        for entry in array.split(',') {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let value = match entry.strip_prefix('[').and_then(|e| e.split_once(']')) {
+                Some((designated, value)) => {
+                    index = designated
+                        .trim()
+                        .parse()
+                        .map_err(|_| ParseError::ParseInt)?;
+                    value.trim_start().trim_start_matches('=').trim_start()
+                }
+                None => entry,
+            };
+            // Entries like `NULL` leave the slot empty.
+            if let Some(name) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                colors.insert(index, Color::from_x11(name)?);
+            }
+            index += 1;
+        }
+
+        let variable = |name: &str, default: usize| -> usize {
+            content
+                .split(';')
+                .find_map(|statement| {
+                    let (left, right) = statement.split_once('=')?;
+                    if left.split_whitespace().last()? == name {
+                        right.trim().parse().ok()
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(default)
+        };
+        let mut scheme = ColorScheme::default();
+        for (index, color) in &colors {
+            if let Some(slot) = scheme.ansi_color_mut(*index) {
+                *slot = color.clone();
+            }
+        }
+        // Defaults of st, and of themes for older versions of st without the extra entries.
+        let foreground = colors.get(&variable("defaultfg", 258)).or(colors.get(&7));
+        let background = colors.get(&variable("defaultbg", 259)).or(colors.get(&0));
+        scheme.foreground = foreground.cloned().unwrap_or_default();
+        scheme.background = background.cloned().unwrap_or_default();
+        scheme.cursor = colors.get(&variable("defaultcs", 256)).cloned();
+        Ok(scheme)
+    }
+
+    // From a Hyper theme or `.hyper.js`. Colors are taken from keys like `foregroundColor` and
+    // `lightBlack` wherever they are, and values can be names of variables with string literals
+    pub fn from_hyper(content: &str) -> Result<Self> {
+        let content = strip_json_comments(content);
+        let assignments = javascript_assignments(&content);
+        let string = |name: &str| {
+            assignments.iter().rev().find_map(|(n, value)| match value {
+                JavaScriptValue::String(s) if *n == name => Some(*s),
+                _ => None,
+            })
+        };
+        let color = |key: &str| -> Result<Option<Color>> {
+            let value = assignments
+                .iter()
+                .rev()
+                .find(|(name, _)| *name == key)
+                .and_then(|(_, value)| match value {
+                    JavaScriptValue::String(s) => Some(*s),
+                    JavaScriptValue::Name(name) => string(name),
+                });
+            value
+                .map(|value| css_or_x11_color(value.trim()))
+                .transpose()
+        };
+
+        let mut scheme = ColorScheme::default();
+        if let Some(color) = color("foregroundColor")? {
+            scheme.foreground = color;
+        }
+        if let Some(color) = color("backgroundColor")? {
+            scheme.background = color;
+        }
+        scheme.cursor = color("cursorColor")?;
+        scheme.cursor_text = color("cursorAccentColor")?;
+        if let Some(color) = color("selectionColor")? {
+            scheme.set_extended_color("selection_background", color);
+        }
+        for (index, name) in HYPER_COLOR_NAMES.iter().enumerate() {
+            if let (Some(color), Some(slot)) = (color(name)?, scheme.ansi_color_mut(index)) {
+                *slot = color;
+            }
+        }
+        Ok(scheme)
+    }
+
+    // From profiles of GNOME Terminal or Tilix in the output of `dconf dump`, with their visible
+    // names. Profiles without palettes are skipped
+    pub fn from_dconf_profiles(content: &str) -> Result<Vec<(String, Self)>> {
+        let mut profiles: Vec<(String, Option<String>, ColorScheme, bool)> = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                profiles.push((section.to_owned(), None, ColorScheme::default(), false));
+                continue;
+            }
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+            let (_, name, scheme, has_palette) = match profiles.last_mut() {
+                Some(profile) => profile,
+                None => continue,
+            };
+            let color = || css_or_x11_color(unquote_gvariant(value));
+            match key {
+                "visible-name" => *name = Some(unquote_gvariant(value).to_owned()),
+                "foreground-color" => scheme.foreground = color()?,
+                "background-color" => scheme.background = color()?,
+                "cursor-background-color" => scheme.cursor = Some(color()?),
+                "cursor-foreground-color" => scheme.cursor_text = Some(color()?),
+                "bold-color" => scheme.set_extended_color("bold", color()?),
+                "highlight-foreground-color" => {
+                    scheme.set_extended_color("selection_foreground", color()?)
+                }
+                "highlight-background-color" => {
+                    scheme.set_extended_color("selection_background", color()?)
+                }
+                "palette" => {
+                    // Every other part is quoted, like `['rgb(0,0,0)', '#ffffff']`.
+                    let colors = value.split('\'').skip(1).step_by(2);
+                    for (index, color) in colors.enumerate() {
+                        if let Some(slot) = scheme.ansi_color_mut(index) {
+                            *slot = css_or_x11_color(color)?;
+                        }
+                    }
+                    *has_palette = true;
+                }
+                _ => {}
+            }
+        }
+        let schemes: Vec<(String, ColorScheme)> = profiles
+            .into_iter()
+            .filter(|(_, _, _, has_palette)| *has_palette)
+            .map(|(section, name, scheme, _)| (name.unwrap_or(section), scheme))
+            .collect();
+        if schemes.is_empty() {
+            return Err(ParseError::NoColorSchemes.into());
+        }
+        Ok(schemes)
+    }
+
+    // From a PuTTY session exported from the registry (.reg). Use `from_putty_sessions()` for
+    // files with multiple sessions
+    pub fn from_putty(content: &str) -> Result<Self> {
+        let schemes = ColorScheme::from_putty_sessions(content)?;
+        single(
+            schemes
+                .into_iter()
+                .map(|(name, scheme)| (Some(name), scheme))
+                .collect(),
+        )
+    }
+
+    // From PuTTY sessions exported from the registry, with their names
+    //
+    // PuTTY has 22 colors: `Colour0` to `Colour5` are the default foreground, the bold foreground,
+    // the default background, the bold background, the cursor text and the cursor, and the rest
+    // are pairs of a normal and a bold color for each ANSI color.
+    pub fn from_putty_sessions(content: &str) -> Result<Vec<(String, Self)>> {
+        let mut schemes: Vec<(String, ColorScheme)> = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if let Some(key) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                if let Some((_, session)) = key.rsplit_once("\\Sessions\\") {
+                    schemes.push((decode_putty_session_name(session), ColorScheme::default()));
+                }
+                continue;
+            }
+            let (name, value) = match line.split_once('=') {
+                Some((name, value)) => (name.trim_matches('"'), value.trim_matches('"')),
+                None => continue,
+            };
+            let index = match name
+                .strip_prefix("Colour")
+                .and_then(|i| i.parse::<usize>().ok())
+            {
+                Some(index) => index,
+                None => continue,
+            };
+            let scheme = match schemes.last_mut() {
+                Some((_, scheme)) => scheme,
+                None => continue,
+            };
+            let color = Color::from_mintty_color(value)?;
+            match index {
+                0 => scheme.foreground = color,
+                1 => scheme.set_extended_color("bold", color),
+                2 => scheme.background = color,
+                3 => scheme.set_extended_color("bold_background", color),
+                4 => scheme.cursor_text = Some(color),
+                5 => scheme.cursor = Some(color),
+                6..=21 => {
+                    let (ansi_index, bold) = ((index - 6) / 2, (index - 6) % 2 == 1);
+                    let offset = if bold { 8 } else { 0 };
+                    if let Some(slot) = scheme.ansi_color_mut(ansi_index + offset) {
+                        *slot = color;
+                    }
+                }
+                _ => {}
+            }
+        }
+        if schemes.is_empty() {
+            return Err(ParseError::NoColorSchemes.into());
+        }
+        Ok(schemes)
+    }
+
+    // From a profile of Terminal.app (.terminal), which is a property list in XML or binary. Its
+    // colors are NSColor objects archived by NSKeyedArchiver
+    pub fn from_terminal_app(content: &[u8]) -> Result<Self> {
+        let root = if content.starts_with(b"bplist") {
+            plist::parse_binary(content)?
+        } else {
+            plist::parse(std::str::from_utf8(content).context("File is not in UTF-8")?)?
+        };
+        let entries = root.as_dict().ok_or(ParseError::NoRootDict)?;
+        let mut scheme = ColorScheme::default();
+        for (key, value) in entries {
+            let data = match value.as_data() {
+                Some(data) if key.ends_with("Color") => data,
+                _ => continue,
+            };
+            let color = unarchive_ns_color(data)?;
+            match key.as_str() {
+                "TextColor" => scheme.foreground = color,
+                "BackgroundColor" => scheme.background = color,
+                "CursorColor" => scheme.cursor = Some(color),
+                "TextBoldColor" => scheme.set_extended_color("bold", color),
+                "SelectionColor" => scheme.set_extended_color("selection_background", color),
+                _ => {
+                    let index = key
+                        .strip_prefix("ANSI")
+                        .and_then(|name| name.strip_suffix("Color"))
+                        .and_then(|name| PASCAL_CASE_COLOR_NAMES.iter().position(|&n| n == name));
+                    if let Some(slot) = index.and_then(|index| scheme.ansi_color_mut(index)) {
+                        *slot = color;
+                    }
+                }
+            }
+        }
+        Ok(scheme)
+    }
+
+    // Build a color scheme from keys under `colors` of an alacritty config, like
+    // `primary.background`
+    fn from_alacritty_colors(entries: &[(String, &str)]) -> Result<Self> {
+        if entries.is_empty() {
+            return Err(ParseError::NoColorSchemes.into());
+        }
+        let mut scheme = ColorScheme::default();
+        for (key, value) in entries {
+            if value.starts_with("Cell") {
+                continue;
+            }
+            let color: Color = value.parse()?;
+            if let Some(slot) = scheme.alacritty_color_mut(key) {
+                *slot = color;
+                continue;
+            }
+            let ui_color = ALACRITTY_UI_COLORS.iter().find_map(|(section, colors)| {
+                let name = key.strip_prefix(section)?.strip_prefix('.')?;
+                colors
+                    .iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, extended_name)| *extended_name)
+            });
+            match ui_color {
+                Some(name) => scheme.set_extended_color(name, color),
+                // Like `dim_black` and `vi_mode_cursor_text`.
+                None => scheme.set_extended_color(&key.replace('.', "_"), color),
+            }
+        }
+        Ok(scheme)
+    }
+
+    fn alacritty_color_mut(&mut self, key: &str) -> Option<&mut Color> {
+        match key {
+            "primary.foreground" => Some(&mut self.foreground),
+            "primary.background" => Some(&mut self.background),
+            "cursor.text" => Some(self.cursor_text.get_or_insert_with(Color::default)),
+            "cursor.cursor" => Some(self.cursor.get_or_insert_with(Color::default)),
+            _ => {
+                let (section, name) = key.split_once('.')?;
+                let offset = match section {
+                    "normal" => 0,
+                    "bright" => 8,
+                    _ => return None,
+                };
+                let index = ANSI_COLOR_NAMES[..8].iter().position(|n| *n == name)?;
+                self.ansi_color_mut(offset + index)
+            }
+        }
+    }
+
+    fn set_iterm_color(&mut self, key: &str, color: Color) {
+        match key {
+            "Ansi 0 Color" => self.black = color,
+            "Ansi 1 Color" => self.red = color,
+            "Ansi 2 Color" => self.green = color,
+            "Ansi 3 Color" => self.yellow = color,
+            "Ansi 4 Color" => self.blue = color,
+            "Ansi 5 Color" => self.magenta = color,
+            "Ansi 6 Color" => self.cyan = color,
+            "Ansi 7 Color" => self.white = color,
+            "Ansi 8 Color" => self.bright_black = color,
+            "Ansi 9 Color" => self.bright_red = color,
+            "Ansi 10 Color" => self.bright_green = color,
+            "Ansi 11 Color" => self.bright_yellow = color,
+            "Ansi 12 Color" => self.bright_blue = color,
+            "Ansi 13 Color" => self.bright_magenta = color,
+            "Ansi 14 Color" => self.bright_cyan = color,
+            "Ansi 15 Color" => self.bright_white = color,
+            "Background Color" => self.background = color,
+            "Foreground Color" => self.foreground = color,
+            "Cursor Color" => self.cursor = Some(color),
+            "Cursor Text Color" => self.cursor_text = Some(color),
+            _ => (),
+        }
+    }
+
+    fn xresources_color_mut(&mut self, name: &str) -> Option<&mut Color> {
+        match name {
+            "foreground" => Some(&mut self.foreground),
+            "background" => Some(&mut self.background),
+            "cursorColor" => Some(self.cursor.get_or_insert_with(Color::default)),
+            _ => {
+                let index = name.strip_prefix("color")?.parse::<usize>().ok()?;
+                self.ansi_color_mut(index)
+            }
+        }
+    }
+
+    fn ansi_color_mut(&mut self, index: usize) -> Option<&mut Color> {
+        match index {
+            0 => Some(&mut self.black),
+            1 => Some(&mut self.red),
+            2 => Some(&mut self.green),
+            3 => Some(&mut self.yellow),
+            4 => Some(&mut self.blue),
+            5 => Some(&mut self.magenta),
+            6 => Some(&mut self.cyan),
+            7 => Some(&mut self.white),
+            8 => Some(&mut self.bright_black),
+            9 => Some(&mut self.bright_red),
+            10 => Some(&mut self.bright_green),
+            11 => Some(&mut self.bright_yellow),
+            12 => Some(&mut self.bright_blue),
+            13 => Some(&mut self.bright_magenta),
+            14 => Some(&mut self.bright_cyan),
+            15 => Some(&mut self.bright_white),
+            _ => None,
+        }
+    }
+
+    // Output YAML that can be used as a color theme in .alacritty.yml
+    pub fn to_yaml(&self) -> String {
+        let cursor_colors = match (&self.cursor_text, &self.cursor) {
+            (Some(cursor_text), Some(cursor)) => format!(
+                "
+  # Cursor colors
   cursor:
     text:   '{}'
     cursor: '{}'
@@ -380,9 +2630,1140 @@ impl ColorScheme {
             self.bright_magenta.to_hex(),
             self.bright_cyan.to_hex(),
             self.bright_white.to_hex(),
+        ) + &self.alacritty_ui_colors_yaml()
+    }
+
+    // Output TOML that can be imported as a color theme in alacritty.toml (alacritty 0.13+)
+    pub fn to_toml(&self) -> String {
+        let cursor_colors = match (&self.cursor_text, &self.cursor) {
+            (Some(cursor_text), Some(cursor)) => format!(
+                "
+[colors.cursor]
+text = '{}'
+cursor = '{}'
+",
+                cursor_text.to_hex(),
+                cursor.to_hex()
+            ),
+            _ => String::new(),
+        };
+
+        format!(
+            "[colors.primary]
+background = '{}'
+foreground = '{}'
+{}
+[colors.normal]
+black = '{}'
+red = '{}'
+green = '{}'
+yellow = '{}'
+blue = '{}'
+magenta = '{}'
+cyan = '{}'
+white = '{}'
+
+[colors.bright]
+black = '{}'
+red = '{}'
+green = '{}'
+yellow = '{}'
+blue = '{}'
+magenta = '{}'
+cyan = '{}'
+white = '{}'
+",
+            self.background.to_hex(),
+            self.foreground.to_hex(),
+            cursor_colors,
+            self.black.to_hex(),
+            self.red.to_hex(),
+            self.green.to_hex(),
+            self.yellow.to_hex(),
+            self.blue.to_hex(),
+            self.magenta.to_hex(),
+            self.cyan.to_hex(),
+            self.white.to_hex(),
+            self.bright_black.to_hex(),
+            self.bright_red.to_hex(),
+            self.bright_green.to_hex(),
+            self.bright_yellow.to_hex(),
+            self.bright_blue.to_hex(),
+            self.bright_magenta.to_hex(),
+            self.bright_cyan.to_hex(),
+            self.bright_white.to_hex(),
+        ) + &self.alacritty_ui_colors_toml()
+    }
+
+    // Sections of alacritty's UI colors with both colors set, like `search.matches`
+    fn alacritty_ui_colors(&self) -> Vec<(&str, [(&str, &Color); 2])> {
+        ALACRITTY_UI_COLORS
+            .iter()
+            .filter_map(|(section, [(key1, name1), (key2, name2)])| {
+                let color1 = self.extended_color(name1)?;
+                let color2 = self.extended_color(name2)?;
+                Some((*section, [(*key1, color1), (*key2, color2)]))
+            })
+            .collect()
+    }
+
+    fn alacritty_ui_colors_yaml(&self) -> String {
+        let mut output = String::new();
+        let mut last_parent = "";
+        for (section, colors) in self.alacritty_ui_colors() {
+            let (parent, child) = section.split_once('.').unwrap_or((section, ""));
+            if parent != last_parent {
+                let title = match parent {
+                    "selection" => "Selection colors",
+                    "search" => "Search colors",
+                    _ => "Keyboard hint colors",
+                };
+                output.push_str(&format!("\n  # {}\n  {}:\n", title, parent));
+                last_parent = parent;
+            }
+            let indent = if child.is_empty() {
+                "    "
+            } else {
+                output.push_str(&format!("    {}:\n", child));
+                "      "
+            };
+            for (key, color) in colors.iter() {
+                output.push_str(&format!("{}{}: '{}'\n", indent, key, color.to_hex()));
+            }
+        }
+        output
+    }
+
+    fn alacritty_ui_colors_toml(&self) -> String {
+        let mut output = String::new();
+        for (section, colors) in self.alacritty_ui_colors() {
+            output.push_str(&format!("\n[colors.{}]\n", section));
+            for (key, color) in colors.iter() {
+                output.push_str(&format!("{} = '{}'\n", key, color.to_hex()));
+            }
+        }
+        output
+    }
+
+    // Derives colors of selections, search matches and hints from the palette for the ones that
+    // are not set. Selections invert the default colors, and the others are on accent colors.
+    pub fn derive_ui_colors(&mut self) {
+        let readable_on = |color: &Color| self.readable_on(color);
+        let derived = [
+            ("selection_foreground", self.background.clone()),
+            ("selection_background", self.foreground.clone()),
+            ("search_match_foreground", readable_on(&self.yellow)),
+            ("search_match_background", self.yellow.clone()),
+            ("search_focused_match_foreground", readable_on(&self.green)),
+            ("search_focused_match_background", self.green.clone()),
+            ("hint_start_foreground", readable_on(&self.bright_yellow)),
+            ("hint_start_background", self.bright_yellow.clone()),
+            ("hint_end_foreground", self.bright_yellow.clone()),
+            ("hint_end_background", self.background.clone()),
+        ];
+        for (name, color) in Vec::from(derived) {
+            if self.extended_color(name).is_none() {
+                self.set_extended_color(name, color);
+            }
+        }
+    }
+
+    // Output a color scheme of Windows Terminal, which can be pasted into `schemes` of
+    // settings.json
+    pub fn to_windows_terminal(&self, name: &str) -> String {
+        let mut output = self.windows_terminal_scheme(name).pretty(2);
+        output.push('\n');
+        output
+    }
+
+    // Output a color scheme of WezTerm, which can be put in `~/.config/wezterm/colors/`
+    pub fn to_wezterm(&self) -> String {
+        let quote = |color: &Color| format!("'{}'", color.to_css_hex());
+        let array = |colors: &[&Color]| {
+            let colors: Vec<String> = colors.iter().map(|color| quote(color)).collect();
+            format!("[{}]", colors.join(", "))
+        };
+        let ansi_colors = self.ansi_colors();
+        let mut output = format!(
+            "[colors]\nforeground = {}\nbackground = {}\n",
+            quote(&self.foreground),
+            quote(&self.background)
+        );
+        let mut colors = vec![];
+        if let Some(color) = &self.cursor {
+            colors.push(("cursor_bg", color));
+            colors.push(("cursor_border", color));
+        }
+        if let Some(color) = &self.cursor_text {
+            colors.push(("cursor_fg", color));
+        }
+        if let Some(color) = self.extended_color("selection_foreground") {
+            colors.push(("selection_fg", color));
+        }
+        if let Some(color) = self.extended_color("selection_background") {
+            colors.push(("selection_bg", color));
+        }
+        for (key, color) in colors {
+            output.push_str(&format!("{} = {}\n", key, quote(color)));
+        }
+        output.push_str(&format!(
+            "ansi = {}\nbrights = {}\n",
+            array(&ansi_colors[..8]),
+            array(&ansi_colors[8..])
+        ));
+        output
+    }
+
+    // Output the colors of foot.ini, which has hex without `#`. The cursor is written only when
+    // the color of text under it is also known because foot takes both at once
+    pub fn to_foot(&self) -> String {
+        let hex = |color: &Color| color.to_css_hex()[1..].to_owned();
+        let mut output = format!(
+            "[colors]\nforeground={}\nbackground={}\n",
+            hex(&self.foreground),
+            hex(&self.background)
+        );
+        for (index, color) in self.ansi_colors().iter().enumerate() {
+            let kind = if index < 8 { "regular" } else { "bright" };
+            output.push_str(&format!("{}{}={}\n", kind, index % 8, hex(color)));
+        }
+        for name in ["selection-foreground", "selection-background"] {
+            if let Some(color) = self.extended_color(&name.replace('-', "_")) {
+                output.push_str(&format!("{}={}\n", name, hex(color)));
+            }
+        }
+        if let (Some(text), Some(cursor)) = (&self.cursor_text, &self.cursor) {
+            output.push_str(&format!(
+                "\n[cursor]\ncolor={} {}\n",
+                hex(text),
+                hex(cursor)
+            ));
+        }
+        output
+    }
+
+    // Output a Ghostty theme, which can be put in `~/.config/ghostty/themes/`
+    pub fn to_ghostty(&self) -> String {
+        let mut output = String::new();
+        for (index, color) in self.ansi_colors().iter().enumerate() {
+            output.push_str(&format!("palette = {}={}\n", index, color.to_css_hex()));
+        }
+        let mut colors = vec![
+            ("background", &self.background),
+            ("foreground", &self.foreground),
+        ];
+        if let Some(color) = &self.cursor {
+            colors.push(("cursor-color", color));
+        }
+        if let Some(color) = &self.cursor_text {
+            colors.push(("cursor-text", color));
+        }
+        if let Some(color) = self.extended_color("selection_background") {
+            colors.push(("selection-background", color));
+        }
+        if let Some(color) = self.extended_color("selection_foreground") {
+            colors.push(("selection-foreground", color));
+        }
+        for (key, color) in colors {
+            output.push_str(&format!("{} = {}\n", key, color.to_css_hex()));
+        }
+        output
+    }
+
+    // Output a color scheme of Konsole with sections sorted like Konsole saves them. Faint
+    // variants are the same as the normal colors, and the intense foreground is the bold color
+    pub fn to_konsole(&self, name: &str) -> String {
+        let ansi_colors = self.ansi_colors();
+        let intense_foreground = self.extended_color("bold").unwrap_or(&self.foreground);
+        let mut sections = vec![
+            ("Background".to_owned(), &self.background),
+            ("BackgroundFaint".to_owned(), &self.background),
+            ("BackgroundIntense".to_owned(), &self.background),
+        ];
+        for index in 0..8 {
+            sections.push((format!("Color{}", index), ansi_colors[index]));
+            sections.push((format!("Color{}Faint", index), ansi_colors[index]));
+            sections.push((format!("Color{}Intense", index), ansi_colors[index + 8]));
+        }
+        sections.push(("Foreground".to_owned(), &self.foreground));
+        sections.push(("ForegroundFaint".to_owned(), &self.foreground));
+        sections.push(("ForegroundIntense".to_owned(), intense_foreground));
+
+        let mut output = String::new();
+        for (section, color) in sections {
+            output.push_str(&format!(
+                "[{}]\nColor={}\n\n",
+                section,
+                color.to_mintty_color()
+            ));
+        }
+        output.push_str(&format!(
+            "[General]\nDescription={}\nOpacity=1\n",
+            name.replace('\n', " ")
+        ));
+        output
+    }
+
+    // Output an iTerm 2 color preset (.itermcolors) with keys sorted like iTerm 2 exports them
+    pub fn to_iterm(&self) -> String {
+        let mut colors: Vec<(String, &Color)> = self
+            .ansi_colors()
+            .iter()
+            .enumerate()
+            .map(|(index, color)| (format!("Ansi {} Color", index), *color))
+            .collect();
+        colors.push(("Background Color".to_owned(), &self.background));
+        colors.push(("Foreground Color".to_owned(), &self.foreground));
+        if let Some(color) = &self.cursor {
+            colors.push(("Cursor Color".to_owned(), color));
+        }
+        if let Some(color) = &self.cursor_text {
+            colors.push(("Cursor Text Color".to_owned(), color));
+        }
+        let extended = [
+            ("Bold Color", "bold"),
+            ("Selection Color", "selection_background"),
+            ("Selected Text Color", "selection_foreground"),
+        ];
+        for (key, name) in extended {
+            if let Some(color) = self.extended_color(name) {
+                colors.push((key.to_owned(), color));
+            }
+        }
+        colors.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut output = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">
+<plist version=\"1.0\">
+<dict>
+",
+        );
+        let real = |component: u16| f64::from(component) / 65535.0;
+        for (key, color) in colors {
+            output.push_str(&format!(
+                "\t<key>{}</key>
+\t<dict>
+\t\t<key>Alpha Component</key>
+\t\t<real>1</real>
+\t\t<key>Blue Component</key>
+\t\t<real>{}</real>
+\t\t<key>Color Space</key>
+\t\t<string>sRGB</string>
+\t\t<key>Green Component</key>
+\t\t<real>{}</real>
+\t\t<key>Red Component</key>
+\t\t<real>{}</real>
+\t</dict>
+",
+                key,
+                real(color.blue),
+                real(color.green),
+                real(color.red)
+            ));
+        }
+        output.push_str("</dict>\n</plist>\n");
+        output
+    }
+
+    // Output colors of minttyrc, which mintty and wsltty read. Extended colors are written only
+    // if mintty has them
+    pub fn to_minttyrc(&self) -> String {
+        let mut colors = vec![
+            ("ForegroundColour".to_owned(), &self.foreground),
+            ("BackgroundColour".to_owned(), &self.background),
+        ];
+        if let Some(color) = &self.cursor {
+            colors.push(("CursorColour".to_owned(), color));
+        }
+        let ansi_colors = self.ansi_colors();
+        for (index, name) in PASCAL_CASE_COLOR_NAMES[..8].iter().enumerate() {
+            colors.push((name.to_string(), ansi_colors[index]));
+            colors.push((format!("Bold{}", name), ansi_colors[index + 8]));
+        }
+        for (name, color) in &self.extended_colors {
+            let key = match name.as_str() {
+                "ime_cursor" => "IMECursorColour",
+                "bold" => "BoldColour",
+                "underline" => "UnderlineColour",
+                "hover" => "HoverColour",
+                "selection_foreground" => "HighlightForegroundColour",
+                "selection_background" => "HighlightBackgroundColour",
+                // Unknown keys of minttyrc are kept as they are.
+                name if is_mintty_color_key(name) => name,
+                _ => continue,
+            };
+            colors.push((key.to_owned(), color));
+        }
+
+        let mut output = String::new();
+        for (key, color) in colors {
+            output.push_str(&format!("{}={}\n", key, color.to_mintty_color()));
+        }
+        output
+    }
+
+    // Output `workbench.colorCustomizations` of VS Code settings with the colors of the integrated
+    // terminal
+    pub fn to_vscode(&self) -> String {
+        let mut colors = json::object! {
+            "terminal.foreground" => self.foreground.to_css_hex(),
+            "terminal.background" => self.background.to_css_hex(),
+        };
+        for (name, color) in PASCAL_CASE_COLOR_NAMES
+            .iter()
+            .zip(self.ansi_colors().iter())
+        {
+            colors[format!("terminal.ansi{}", name)] = color.to_css_hex().into();
+        }
+        if let Some(color) = &self.cursor {
+            colors["terminalCursor.foreground"] = color.to_css_hex().into();
+        }
+        if let Some(color) = &self.cursor_text {
+            colors["terminalCursor.background"] = color.to_css_hex().into();
+        }
+        if let Some(color) = self.extended_color("selection_background") {
+            colors["terminal.selectionBackground"] = color.to_css_hex().into();
+        }
+        if let Some(color) = self.extended_color("selection_foreground") {
+            colors["terminal.selectionForeground"] = color.to_css_hex().into();
+        }
+        let mut output = json::object! { "workbench.colorCustomizations" => colors }.pretty(2);
+        output.push('\n');
+        output
+    }
+
+    // Output Lua for Neovim that sets the palette of `:terminal` and the default colors of text
+    pub fn to_neovim(&self) -> String {
+        let mut output = String::new();
+        for (index, color) in self.ansi_colors().iter().enumerate() {
+            output.push_str(&format!(
+                "vim.g.terminal_color_{} = '{}'\n",
+                index,
+                color.to_css_hex()
+            ));
+        }
+        output.push_str(&format!(
+            "vim.api.nvim_set_hl(0, 'Normal', {{ fg = '{}', bg = '{}' }})\n",
+            self.foreground.to_css_hex(),
+            self.background.to_css_hex()
+        ));
+        if let Some(cursor) = &self.cursor {
+            let text = self.cursor_text.as_ref().unwrap_or(&self.background);
+            output.push_str(&format!(
+                "vim.api.nvim_set_hl(0, 'Cursor', {{ fg = '{}', bg = '{}' }})\n",
+                text.to_css_hex(),
+                cursor.to_css_hex()
+            ));
+        }
+        if let Some(selection) = self.extended_color("selection_background") {
+            output.push_str(&format!(
+                "vim.api.nvim_set_hl(0, 'Visual', {{ bg = '{}' }})\n",
+                selection.to_css_hex()
+            ));
+        }
+        output
+    }
+
+    // Output a base16 scheme, mapping colors back like base16-shell maps them to the terminal.
+    // Shades of the background and the orange and the brown, which the ANSI colors don't have,
+    // are mixed from others
+    pub fn to_base16(&self, name: &str, author: &str) -> String {
+        let background = &self.background;
+        let foreground = &self.foreground;
+        let orange = self.red.mix(&self.yellow, 0.5);
+        let bases = [
+            background.clone(),
+            background.mix(foreground, 0.1),
+            background.mix(foreground, 0.2),
+            self.bright_black.clone(),
+            background.mix(foreground, 0.6),
+            foreground.clone(),
+            foreground.mix(&self.bright_white, 0.5),
+            self.bright_white.clone(),
+            self.red.clone(),
+            orange.clone(),
+            self.yellow.clone(),
+            self.green.clone(),
+            self.cyan.clone(),
+            self.blue.clone(),
+            self.magenta.clone(),
+            orange.mix(background, 0.4),
+        ];
+        // A double-quoted string of YAML.
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut output = format!("scheme: {}\nauthor: {}\n", quote(name), quote(author));
+        for (index, color) in bases.iter().enumerate() {
+            output.push_str(&format!(
+                "base{:02X}: \"{}\"\n",
+                index,
+                &color.to_css_hex()[1..]
+            ));
+        }
+        output
+    }
+
+    // Output OSC escape sequences that set the palette (OSC 4), the foreground (OSC 10), the
+    // background (OSC 11) and the cursor (OSC 12) of the terminal that prints them
+    pub fn to_osc(&self) -> String {
+        let mut output = String::new();
+        for (index, color) in self.ansi_colors().iter().enumerate() {
+            output.push_str(&format!("\x1b]4;{};{}\x1b\\", index, color.to_x11_rgb()));
+        }
+        output.push_str(&format!("\x1b]10;{}\x1b\\", self.foreground.to_x11_rgb()));
+        output.push_str(&format!("\x1b]11;{}\x1b\\", self.background.to_x11_rgb()));
+        if let Some(cursor) = &self.cursor {
+            output.push_str(&format!("\x1b]12;{}\x1b\\", cursor.to_x11_rgb()));
+        }
+        output
+    }
+
+    // Output a theme script of Gogh in the layout of the themes in the Gogh repository
+    pub fn to_gogh(&self, name: &str) -> String {
+        let mut output = String::from(
+            "#!/usr/bin/env bash
+
+# ====================CONFIG THIS =============================== #
+",
+        );
+        for (index, color) in self.ansi_colors().iter().enumerate() {
+            if index == 8 {
+                output.push('\n');
+            }
+            output.push_str(&format!(
+                "export COLOR_{:02}=\"{}\"\n",
+                index + 1,
+                color.to_css_hex()
+            ));
+        }
+        let cursor = match &self.cursor {
+            Some(cursor) => format!("\"{}\"", cursor.to_css_hex()),
+            None => "\"$FOREGROUND_COLOR\"".to_owned(),
+        };
+        // Characters that are special in double quotes of shell.
+        let name = name
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$")
+            .replace('`', "\\`");
+        output.push_str(&format!(
+            "
+export BACKGROUND_COLOR=\"{}\"
+export FOREGROUND_COLOR=\"{}\"
+export CURSOR_COLOR={}
+export PROFILE_NAME=\"{}\"
+# =============================================================== #
+
+# =============================================================== #
+# | Apply Colors
+# ===============================================================|#
+SCRIPT_PATH=\"${{SCRIPT_PATH:-$(cd \"$(dirname \"${{BASH_SOURCE[0]}}\")\" && pwd)}}\"
+PARENT_PATH=\"$(dirname \"${{SCRIPT_PATH}}\")\"
+
+# Allow developer to change url to forked url for easier testing
+# IMPORTANT: Make sure you export this variable if your main shell is not bash
+BASE_URL=${{BASE_URL:-\"https://raw.githubusercontent.com/Gogh-Co/Gogh/master\"}}
+
+if [[ -e \"${{PARENT_PATH}}/apply-colors.sh\" ]]; then
+  bash \"${{PARENT_PATH}}/apply-colors.sh\"
+else
+  if [[ \"$(uname)\" = \"Darwin\" ]]; then
+    # OSX ships with curl and ancient bash
+    bash -c \"$(curl -so- \"${{BASE_URL}}/apply-colors.sh\")\"
+  else
+    # Linux ships with wget
+    bash -c \"$(wget -qO- \"${{BASE_URL}}/apply-colors.sh\")\"
+  fi
+fi
+",
+            self.background.to_css_hex(),
+            self.foreground.to_css_hex(),
+            cursor,
+            name
+        ));
+        output
+    }
+
+    // Output a Windows Terminal fragment extension, which adds the color scheme without editing
+    // settings.json when it's put in a `Fragments` directory
+    pub fn to_windows_terminal_fragment(&self, name: &str) -> String {
+        let scheme = self.windows_terminal_scheme(name);
+        let mut output = json::object! { "schemes" => json::array![scheme] }.pretty(2);
+        output.push('\n');
+        output
+    }
+
+    fn windows_terminal_scheme(&self, name: &str) -> json::JsonValue {
+        let mut scheme = json::object! {
+            "name" => name,
+            "foreground" => self.foreground.to_css_hex(),
+            "background" => self.background.to_css_hex(),
+        };
+        if let Some(cursor) = &self.cursor {
+            scheme["cursorColor"] = cursor.to_css_hex().into();
+        }
+        if let Some(selection) = self.extended_color("selection_background") {
+            scheme["selectionBackground"] = selection.to_css_hex().into();
+        }
+        for (key, color) in WINDOWS_TERMINAL_COLOR_NAMES
+            .iter()
+            .zip(self.ansi_colors().iter())
+        {
+            scheme[*key] = color.to_css_hex().into();
+        }
+        scheme
+    }
+
+    // Output window colors and bar colors of i3, which sway also reads. Focused and urgent things
+    // are on the blue and red of the palette, and the rest on the default colors
+    pub fn to_i3(&self) -> String {
+        let background = self.background.to_css_hex();
+        let foreground = self.foreground.to_css_hex();
+        let inactive = self.bright_black.to_css_hex();
+        let focused = self.blue.to_css_hex();
+        let on_focused = self.readable_on(&self.blue).to_css_hex();
+        let on_inactive = self.readable_on(&self.bright_black).to_css_hex();
+        let urgent = self.red.to_css_hex();
+        let on_urgent = self.readable_on(&self.red).to_css_hex();
+        let binding_mode = self.yellow.to_css_hex();
+        let on_binding_mode = self.readable_on(&self.yellow).to_css_hex();
+        let indicator = self.cyan.to_css_hex();
+
+        let mut output = String::from(
+            "# class                 border  backgr. text    indicator child_border\n",
+        );
+        let clients = [
+            ("focused", &focused, &focused, &on_focused, &indicator),
+            (
+                "focused_inactive",
+                &inactive,
+                &inactive,
+                &on_inactive,
+                &inactive,
+            ),
+            (
+                "unfocused",
+                &background,
+                &background,
+                &foreground,
+                &background,
+            ),
+            ("urgent", &urgent, &urgent, &on_urgent, &urgent),
+            (
+                "placeholder",
+                &background,
+                &background,
+                &foreground,
+                &background,
+            ),
+        ];
+        for (class, border, background, text, indicator) in clients.iter() {
+            output.push_str(&format!(
+                "{:<23} {} {} {} {} {}\n",
+                format!("client.{}", class),
+                border,
+                background,
+                text,
+                indicator,
+                border
+            ));
+        }
+        output.push_str(&format!("client.background       {}\n", background));
+
+        output.push_str(&format!(
+            "
+bar {{
+  colors {{
+    background {background}
+    statusline {foreground}
+    separator  {inactive}
+
+    # class            border  backgr. text
+    focused_workspace  {focused} {focused} {on_focused}
+    active_workspace   {inactive} {inactive} {on_inactive}
+    inactive_workspace {background} {background} {foreground}
+    urgent_workspace   {urgent} {urgent} {on_urgent}
+    binding_mode       {binding_mode} {binding_mode} {on_binding_mode}
+  }}
+}}
+"
+        ));
+        output
+    }
+
+    // Output the palette as variables of a polybar config, which modules refer to like
+    // `${colors.background}`
+    pub fn to_polybar(&self) -> String {
+        let mut output = String::from("[colors]\n");
+        for (name, color) in self.palette_variables() {
+            output.push_str(&format!("{} = {}\n", name, color.to_css_hex()));
+        }
+        output
+    }
+
+    // Output the palette as named colors of a Waybar stylesheet, which rules refer to like
+    // `@background`. GTK CSS doesn't have custom properties but `@define-color` does the job
+    pub fn to_waybar_css(&self) -> String {
+        self.palette_variables()
+            .iter()
+            .map(|(name, color)| format!("@define-color {} {};\n", name, color.to_css_hex()))
+            .collect()
+    }
+
+    // The default colors, the cursor color if any and the ANSI colors in snake case, for
+    // configs that define colors as variables
+    fn palette_variables(&self) -> Vec<(String, &Color)> {
+        let mut variables = vec![
+            ("foreground".to_owned(), &self.foreground),
+            ("background".to_owned(), &self.background),
+        ];
+        if let Some(cursor) = &self.cursor {
+            variables.push(("cursor".to_owned(), cursor));
+        }
+        for (name, color) in ANSI_COLOR_NAMES.iter().zip(self.ansi_colors().iter()) {
+            variables.push((name.replace(' ', "_"), color));
+        }
+        variables
+    }
+
+    // Output a rofi theme that sets the colors of the default theme's layout. Selected entries
+    // are on the blue of the palette, and urgent and active ones on the red and the cyan
+    pub fn to_rofi(&self) -> String {
+        let mut output = String::from("* {\n");
+        let mut define = |name: &str, value: &str| {
+            output.push_str(&format!("    {}: {};\n", name, value));
+        };
+        define("foreground", &self.foreground.to_css_hex());
+        define("background", &self.background.to_css_hex());
+        define("lightfg", &self.bright_white.to_css_hex());
+        define("lightbg", &self.bright_black.to_css_hex());
+        define("red", &self.red.to_css_hex());
+        define("blue", &self.blue.to_css_hex());
+        define("cyan", &self.cyan.to_css_hex());
+        define("border-color", "@blue");
+        define("separatorcolor", "@lightbg");
+        define("background-color", "@background");
+        for prefix in ["", "alternate-"].iter() {
+            define(&format!("{}normal-foreground", prefix), "@foreground");
+            define(&format!("{}normal-background", prefix), "@background");
+            define(&format!("{}urgent-foreground", prefix), "@red");
+            define(&format!("{}urgent-background", prefix), "@background");
+            define(&format!("{}active-foreground", prefix), "@cyan");
+            define(&format!("{}active-background", prefix), "@background");
+        }
+        define(
+            "selected-normal-foreground",
+            &self.readable_on(&self.blue).to_css_hex(),
+        );
+        define("selected-normal-background", "@blue");
+        define(
+            "selected-urgent-foreground",
+            &self.readable_on(&self.red).to_css_hex(),
+        );
+        define("selected-urgent-background", "@red");
+        define(
+            "selected-active-foreground",
+            &self.readable_on(&self.cyan).to_css_hex(),
+        );
+        define("selected-active-background", "@cyan");
+        output.push_str("}\n");
+        output
+    }
+
+    // Output urgency sections of a dunst config. Notifications have the default colors, framed
+    // in the bright black, the blue and the red of the palette by urgency
+    pub fn to_dunst(&self) -> String {
+        let frames = [
+            ("low", &self.bright_black),
+            ("normal", &self.blue),
+            ("critical", &self.red),
+        ];
+        let sections: Vec<String> = frames
+            .iter()
+            .map(|(urgency, frame)| {
+                format!(
+                    "[urgency_{}]\n    background = \"{}\"\n    foreground = \"{}\"\n    frame_color = \"{}\"\n",
+                    urgency,
+                    self.background.to_css_hex(),
+                    self.foreground.to_css_hex(),
+                    frame.to_css_hex()
+                )
+            })
+            .collect();
+        sections.join("\n")
+    }
+
+    // Output style options of tmux. The status line has the default colors, and the current
+    // window, the active pane and messages stand out in the blue and the yellow like `to_i3()`
+    pub fn to_tmux(&self) -> String {
+        let background = self.background.to_css_hex();
+        let foreground = self.foreground.to_css_hex();
+        let inactive = self.bright_black.to_css_hex();
+        let focused = self.blue.to_css_hex();
+        let on_focused = self.readable_on(&self.blue).to_css_hex();
+        let message = self.yellow.to_css_hex();
+        let on_message = self.readable_on(&self.yellow).to_css_hex();
+        let urgent = self.red.to_css_hex();
+        let on_urgent = self.readable_on(&self.red).to_css_hex();
+        let (selection, on_selection) = match self.extended_color("selection_background") {
+            Some(selection) => (
+                selection.to_css_hex(),
+                self.extended_color("selection_foreground")
+                    .unwrap_or(&self.foreground)
+                    .to_css_hex(),
+            ),
+            None => (focused.clone(), on_focused.clone()),
+        };
+
+        let styles = [
+            (
+                "status-style",
+                format!("fg={},bg={}", foreground, background),
+            ),
+            (
+                "window-status-style",
+                format!("fg={},bg={}", inactive, background),
+            ),
+            (
+                "window-status-current-style",
+                format!("fg={},bg={},bold", on_focused, focused),
+            ),
+            (
+                "window-status-activity-style",
+                format!("fg={},bg={}", message, background),
+            ),
+            (
+                "window-status-bell-style",
+                format!("fg={},bg={}", on_urgent, urgent),
+            ),
+            ("pane-border-style", format!("fg={}", inactive)),
+            ("pane-active-border-style", format!("fg={}", focused)),
+            ("message-style", format!("fg={},bg={}", on_message, message)),
+            (
+                "message-command-style",
+                format!("fg={},bg={}", on_message, message),
+            ),
+            (
+                "mode-style",
+                format!("fg={},bg={}", on_selection, selection),
+            ),
+            ("display-panes-colour", inactive.clone()),
+            ("display-panes-active-colour", focused.clone()),
+            ("clock-mode-colour", focused.clone()),
+        ];
+        let mut output = String::new();
+        for (option, value) in styles {
+            output.push_str(&format!("set -g {} \"{}\"\n", option, value));
+        }
+        output
+    }
+
+    // Output `--color` of fzf in `FZF_DEFAULT_OPTS`. The current line is on the selection color,
+    // or a shade of the background, and matches are in the blue
+    pub fn to_fzf(&self) -> String {
+        let current = self
+            .extended_color("selection_background")
+            .cloned()
+            .unwrap_or_else(|| self.background.mix(&self.foreground, 0.15));
+        let colors = [
+            ("fg", &self.foreground),
+            ("bg", &self.background),
+            ("hl", &self.blue),
+            ("fg+", &self.foreground),
+            ("bg+", &current),
+            ("hl+", &self.bright_blue),
+            ("info", &self.yellow),
+            ("prompt", &self.green),
+            ("pointer", &self.magenta),
+            ("marker", &self.magenta),
+            ("spinner", &self.yellow),
+            ("header", &self.bright_black),
+        ];
+        let colors: Vec<String> = colors
+            .iter()
+            .map(|(name, color)| format!("{}:{}", name, color.to_css_hex()))
+            .collect();
+        format!(
+            "export FZF_DEFAULT_OPTS=\"$FZF_DEFAULT_OPTS --color={}\"\n",
+            colors.join(",")
+        )
+    }
+
+    // Output colors of a mako config with urgency sections, framed like `to_dunst()`
+    pub fn to_mako(&self) -> String {
+        format!(
+            "background-color={background}
+text-color={foreground}
+border-color={normal}
+progress-color=over {low}
+
+[urgency=low]
+border-color={low}
+
+[urgency=critical]
+border-color={critical}
+",
+            background = self.background.to_css_hex(),
+            foreground = self.foreground.to_css_hex(),
+            normal = self.blue.to_css_hex(),
+            low = self.bright_black.to_css_hex(),
+            critical = self.red.to_css_hex(),
         )
     }
 
+    // Output an editor color scheme of JetBrains IDEs with the colors of the console and the
+    // terminal, based on the bundled dark or light scheme
+    pub fn to_jetbrains(&self, name: &str) -> String {
+        let parent = if self.is_dark() { "Darcula" } else { "Default" };
+        let mut output = format!(
+            "<scheme name=\"{}\" version=\"142\" parent_scheme=\"{}\">\n  <colors>\n",
+            quick_xml::escape::escape(name),
+            parent
+        );
+        let mut colors = vec![("CONSOLE_BACKGROUND_KEY", &self.background)];
+        if let Some(selection) = self.extended_color("selection_background") {
+            colors.push(("CONSOLE_SELECTION_BACKGROUND", selection));
+        }
+        for (key, color) in colors {
+            output.push_str(&format!(
+                "    <option name=\"{}\" value=\"{}\" />\n",
+                key,
+                &color.to_css_hex()[1..]
+            ));
+        }
+        output.push_str("  </colors>\n  <attributes>\n");
+        let ansi_colors = self.ansi_colors();
+        let attributes = std::iter::once(("CONSOLE_NORMAL_OUTPUT", &self.foreground)).chain(
+            JETBRAINS_CONSOLE_COLOR_NAMES
+                .iter()
+                .copied()
+                .zip(ansi_colors.iter().copied()),
+        );
+        for (key, color) in attributes {
+            output.push_str(&format!(
+                "    <option name=\"{}\">\n      <value>\n        <option name=\"FOREGROUND\" value=\"{}\" />\n      </value>\n    </option>\n",
+                key,
+                &color.to_css_hex()[1..]
+            ));
+        }
+        output.push_str("  </attributes>\n</scheme>\n");
+        output
+    }
+
+    // Output a registry file that sets the default color table of the Windows console host. The
+    // console can only use colors of the table, so the background and the foreground take the
+    // places of the black and the white, which are the default screen colors
+    pub fn to_cmd_reg(&self) -> String {
+        let mut output = String::from(
+            "Windows Registry Editor Version 5.00\r\n\r\n[HKEY_CURRENT_USER\\Console]\r\n",
+        );
+        let ansi_colors = self.ansi_colors();
+        for (index, &ansi_index) in CONSOLE_COLOR_ORDER.iter().enumerate() {
+            let color = match index {
+                0 => &self.background,
+                7 => &self.foreground,
+                _ => ansi_colors[ansi_index],
+            };
+            let (red, green, blue) = color.to_rgb8();
+            output.push_str(&format!(
+                "\"ColorTable{:02}\"=dword:00{:02x}{:02x}{:02x}\r\n",
+                index, blue, green, red
+            ));
+        }
+        // The background in the high nibble and the foreground in the low nibble.
+        output.push_str("\"ScreenColors\"=dword:00000007\r\n");
+        output.push_str("\"PopupColors\"=dword:00000070\r\n");
+        output
+    }
+
+    // Output a kitty theme, which can be included from kitty.conf
+    pub fn to_kitty(&self) -> String {
+        let mut colors = vec![
+            ("foreground".to_owned(), &self.foreground),
+            ("background".to_owned(), &self.background),
+        ];
+        if let Some(color) = &self.cursor {
+            colors.push(("cursor".to_owned(), color));
+        }
+        if let Some(color) = &self.cursor_text {
+            colors.push(("cursor_text_color".to_owned(), color));
+        }
+        for name in ["selection_foreground", "selection_background"] {
+            if let Some(color) = self.extended_color(name) {
+                colors.push((name.to_owned(), color));
+            }
+        }
+        for (index, color) in self.ansi_colors().iter().enumerate() {
+            colors.push((format!("color{}", index), color));
+        }
+
+        let mut output = String::new();
+        for (name, color) in colors {
+            output.push_str(&format!("{:<20} {}\n", name, color.to_css_hex()));
+        }
+        output
+    }
+
+    // Output in the given format. Formats that name color schemes inside use the name
+    pub fn serialize(&self, format: &OutputFormat, name: &str) -> String {
+        match format {
+            OutputFormat::Alacritty(format) => self.to_alacritty(format),
+            OutputFormat::I3 => self.to_i3(),
+            OutputFormat::Polybar => self.to_polybar(),
+            OutputFormat::Waybar => self.to_waybar_css(),
+            OutputFormat::Rofi => self.to_rofi(),
+            OutputFormat::Dunst => self.to_dunst(),
+            OutputFormat::Mako => self.to_mako(),
+            OutputFormat::JetBrains => self.to_jetbrains(name),
+            OutputFormat::CmdReg => self.to_cmd_reg(),
+            OutputFormat::Kitty => self.to_kitty(),
+            OutputFormat::WindowsTerminal => self.to_windows_terminal(name),
+            OutputFormat::WindowsTerminalFragment => self.to_windows_terminal_fragment(name),
+            OutputFormat::WezTerm => self.to_wezterm(),
+            OutputFormat::Foot => self.to_foot(),
+            OutputFormat::Ghostty => self.to_ghostty(),
+            OutputFormat::Konsole => self.to_konsole(name),
+            OutputFormat::ITerm => self.to_iterm(),
+            OutputFormat::Mintty => self.to_minttyrc(),
+            OutputFormat::Tmux => self.to_tmux(),
+            OutputFormat::VSCode => self.to_vscode(),
+            OutputFormat::Neovim => self.to_neovim(),
+            // The author is unknown.
+            OutputFormat::Base16 => self.to_base16(name, ""),
+            OutputFormat::Osc => self.to_osc(),
+            OutputFormat::Fzf => self.to_fzf(),
+            OutputFormat::Gogh => self.to_gogh(name),
+        }
+    }
+
+    // Output in the given alacritty config format
+    pub fn to_alacritty(&self, format: &AlacrittyConfigFormat) -> String {
+        match format {
+            AlacrittyConfigFormat::Yaml => self.to_yaml(),
+            AlacrittyConfigFormat::Toml => self.to_toml(),
+        }
+    }
+
+    pub fn foreground(&self) -> &Color {
+        &self.foreground
+    }
+
+    pub fn background(&self) -> &Color {
+        &self.background
+    }
+
+    pub fn cursor(&self) -> Option<&Color> {
+        self.cursor.as_ref()
+    }
+
+    pub fn cursor_text(&self) -> Option<&Color> {
+        self.cursor_text.as_ref()
+    }
+
+    // Colors of other things than text, like `bold`, by names in snake case
+    pub fn extended_colors(&self) -> &[(String, Color)] {
+        &self.extended_colors
+    }
+
+    pub fn extended_color(&self, name: &str) -> Option<&Color> {
+        self.extended_colors
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, color)| color)
+    }
+
+    // The foreground or the background, whichever is more readable on the color
+    fn readable_on(&self, color: &Color) -> Color {
+        if self.foreground.contrast_ratio(color) > self.background.contrast_ratio(color) {
+            self.foreground.clone()
+        } else {
+            self.background.clone()
+        }
+    }
+
+    fn set_extended_color(&mut self, name: &str, color: Color) {
+        self.extended_colors.retain(|(n, _)| n != name);
+        self.extended_colors.push((name.to_owned(), color));
+    }
+
+    // Normal colors followed by bright colors, in the ANSI order
+    pub fn ansi_colors(&self) -> [&Color; 16] {
+        [
+            &self.black,
+            &self.red,
+            &self.green,
+            &self.yellow,
+            &self.blue,
+            &self.magenta,
+            &self.cyan,
+            &self.white,
+            &self.bright_black,
+            &self.bright_red,
+            &self.bright_green,
+            &self.bright_yellow,
+            &self.bright_blue,
+            &self.bright_magenta,
+            &self.bright_cyan,
+            &self.bright_white,
+        ]
+    }
+
+    // Whether the background is darker than the middle gray in CIE L*
+    pub fn is_dark(&self) -> bool {
+        self.background.to_lab().0 < 50.0
+    }
+
+    // Hue in degrees that the background and the foreground are tinted with, weighted by their
+    // chroma, or None if both are gray
+    pub fn dominant_hue(&self) -> Option<f64> {
+        let (mut x, mut y) = (0.0, 0.0);
+        for color in [&self.background, &self.foreground].iter() {
+            let (hue, saturation, lightness) = color.to_hsl();
+            let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+            x += chroma * hue.to_radians().cos();
+            y += chroma * hue.to_radians().sin();
+        }
+        // Less than a step of 8-bit channels
+        if x.hypot(y) < 1.0 / 255.0 {
+            return None;
+        }
+        Some(y.atan2(x).to_degrees().rem_euclid(360.0))
+    }
+
+    // Average color difference between the default and ANSI colors of two schemes
+    pub fn distance(&self, other: &ColorScheme) -> f64 {
+        let palette = self.palette();
+        let other_palette = other.palette();
+        let total: f64 = palette
+            .iter()
+            .zip(other_palette.iter())
+            .map(|(color, other_color)| color.delta_e(other_color))
+            .sum();
+        total / palette.len() as f64
+    }
+
+    // Default and ANSI colors. Cursor colors are left out because they are optional.
+    fn palette(&self) -> Vec<&Color> {
+        let mut palette = vec![&self.foreground, &self.background];
+        palette.extend(self.ansi_colors());
+        palette
+    }
+
+    // A stable hash of the default and ANSI colors reduced to 8 bits, which is the same for the
+    // same color scheme regardless of its name, format and precision
+    pub fn fingerprint(&self) -> String {
+        // 64-bit FNV-1a, which doesn't change across Rust versions unlike `DefaultHasher`.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for color in self.palette() {
+            let (red, green, blue) = color.to_rgb8();
+            for byte in [red, green, blue].iter() {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        }
+        format!("{:016x}", hash)
+    }
+
     // Show all colors in one line
     pub fn to_preview(&self) -> String {
         let colors = vec![
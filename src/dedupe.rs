@@ -0,0 +1,26 @@
+use crate::color::ColorScheme;
+
+/// The average color difference under which two color schemes are treated as duplicates.
+///
+/// A CIE76 ΔE of around 2.3 is a just noticeable difference, so schemes below this look the
+/// same in practice.
+pub const DEFAULT_THRESHOLD: f64 = 2.0;
+
+/// Groups color schemes that look identical or nearly identical.
+///
+/// Returns groups of indices into `schemes`, in the order of their first members. Every scheme
+/// belongs to exactly one group, which is the first group whose first member is within
+/// `threshold` of it.
+pub fn group_duplicates(schemes: &[&ColorScheme], threshold: f64) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (index, scheme) in schemes.iter().enumerate() {
+        let group = groups
+            .iter_mut()
+            .find(|group| schemes[group[0]].distance(scheme) <= threshold);
+        match group {
+            Some(group) => group.push(index),
+            None => groups.push(vec![index]),
+        }
+    }
+    groups
+}
@@ -0,0 +1,273 @@
+use crate::a11y::{MIN_ANSI_CONTRAST, MIN_TEXT_CONTRAST};
+use crate::color::{Color, ColorScheme, ANSI_COLOR_NAMES};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Colors closer than this (CIE76 ΔE) are merged into one cluster.
+const CLUSTER_DELTA_E: f64 = 12.0;
+
+/// Clusters with a smaller share of the pixels than this are treated as noise.
+const MIN_CLUSTER_SHARE: f64 = 0.0005;
+
+/// Colors with a lower CIE chroma than this are treated as gray. HSL saturation doesn't work well
+/// for this because it's high for any tint of nearly white colors.
+const GRAY_CHROMA: f64 = 12.0;
+
+/// The minimum contrast ratio of a cluster on the background to be taken as an ANSI color. Less
+/// contrasting ones are mostly anti-aliased edges of text.
+const MIN_COLOR_CONTRAST: f64 = 1.5;
+
+/// Conventional hues of ANSI colors 1 to 6: red, green, yellow, blue, magenta and cyan.
+const ANSI_HUES: [f64; 6] = [0.0, 120.0, 60.0, 240.0, 300.0, 180.0];
+
+/// A group of similar pixels.
+#[derive(Debug, PartialEq)]
+pub struct Cluster {
+    /// The average color of the pixels.
+    pub color: Color,
+    /// The number of pixels.
+    pub count: usize,
+}
+
+/// Groups similar pixels, most common first. Clusters of too few pixels are dropped.
+pub fn clusters(pixels: &[Color]) -> Vec<Cluster> {
+    // Reduce to 5 bits per channel first so that clustering doesn't look at every distinct color.
+    let mut buckets: HashMap<(u16, u16, u16), ([u64; 3], usize)> = HashMap::new();
+    for pixel in pixels {
+        let key = (pixel.red >> 11, pixel.green >> 11, pixel.blue >> 11);
+        let (sums, count) = buckets.entry(key).or_insert(([0; 3], 0));
+        sums[0] += u64::from(pixel.red);
+        sums[1] += u64::from(pixel.green);
+        sums[2] += u64::from(pixel.blue);
+        *count += 1;
+    }
+    let mut buckets: Vec<_> = buckets.into_values().collect();
+    // Break ties by color so that the result doesn't depend on the order of the hash map.
+    buckets.sort_by(|(a_sums, a_count), (b_sums, b_count)| {
+        b_count.cmp(a_count).then(a_sums.cmp(b_sums))
+    });
+
+    // Each cluster is seeded by its most common bucket, and takes in the buckets close to it.
+    let mut seeds: Vec<Color> = Vec::new();
+    let mut totals: Vec<([u64; 3], usize)> = Vec::new();
+    for (sums, count) in buckets {
+        let color = average(&sums, count);
+        match seeds
+            .iter()
+            .position(|seed| seed.delta_e(&color) < CLUSTER_DELTA_E)
+        {
+            Some(index) => {
+                let total = &mut totals[index];
+                for (total_sum, sum) in total.0.iter_mut().zip(sums.iter()) {
+                    *total_sum += sum;
+                }
+                total.1 += count;
+            }
+            None => {
+                seeds.push(color);
+                totals.push((sums, count));
+            }
+        }
+    }
+
+    let min_count = (pixels.len() as f64 * MIN_CLUSTER_SHARE).ceil() as usize;
+    let mut clusters: Vec<Cluster> = totals
+        .iter()
+        .filter(|(_, count)| *count >= min_count.max(1))
+        .map(|(sums, count)| Cluster {
+            color: average(sums, *count),
+            count: *count,
+        })
+        .collect();
+    clusters.sort_by_key(|cluster| Reverse(cluster.count));
+    clusters
+}
+
+/// Builds a color scheme from a screenshot of a terminal.
+///
+/// Terminals are mostly empty, so the most common color is taken as the background and the most
+/// common gray that is readable on it as the foreground. Returns `None` if there are no pixels.
+pub fn from_terminal_screenshot(pixels: &[Color]) -> Option<ColorScheme> {
+    let clusters = clusters(pixels);
+    let background = clusters.first()?.color.clone();
+    let readable =
+        |cluster: &&Cluster| cluster.color.contrast_ratio(&background) >= MIN_ANSI_CONTRAST;
+    let foreground = clusters[1..]
+        .iter()
+        .filter(readable)
+        .find(|cluster| is_gray(&cluster.color))
+        .or_else(|| clusters[1..].iter().find(readable))
+        .map(|cluster| cluster.color.clone())
+        .unwrap_or_else(|| readable_gray(&background));
+    Some(build(&clusters, foreground, background))
+}
+
+/// Builds a color scheme from the dominant colors of any picture.
+///
+/// The darkest color is taken as the background and the lightest as the foreground. Returns
+/// `None` if there are no pixels.
+pub fn from_picture(pixels: &[Color]) -> Option<ColorScheme> {
+    let clusters = clusters(pixels);
+    let (foreground, background) = lightest_and_darkest(&clusters)?;
+    Some(build(&clusters, foreground, background))
+}
+
+/// Builds a color scheme from the colors of a palette, like a brand palette from designers.
+///
+/// Colors named after a slot such as "Background" or "Bright Red" go to the slot. The others are
+/// assigned like `from_picture()`, with earlier colors preferred. Returns `None` if the palette is
+/// empty.
+pub fn from_palette(colors: &[(String, Color)]) -> Option<ColorScheme> {
+    if colors.is_empty() {
+        return None;
+    }
+    let slots: Vec<String> = ["foreground", "background"]
+        .iter()
+        .chain(ANSI_COLOR_NAMES.iter())
+        .map(|name| normalize_name(name))
+        .collect();
+    let clusters: Vec<Cluster> = colors
+        .iter()
+        .filter(|(name, _)| !slots.contains(&normalize_name(name)))
+        .map(|(_, color)| Cluster {
+            color: color.clone(),
+            count: 1,
+        })
+        .collect();
+    let (mut foreground, mut background) = lightest_and_darkest(&clusters)
+        .unwrap_or_else(|| (Color::from_rgb8(255, 255, 255), Color::default()));
+
+    let named = |slot: &str| {
+        colors
+            .iter()
+            .find(|(name, _)| normalize_name(name) == slot)
+            .map(|(_, color)| color.clone())
+    };
+    if let Some(color) = named("background") {
+        background = color;
+    }
+    if let Some(color) = named("foreground") {
+        foreground = color;
+    }
+    let mut ansi_colors = assign(&clusters, &foreground, &background);
+    for (index, name) in ANSI_COLOR_NAMES.iter().enumerate() {
+        if let Some(color) = named(&normalize_name(name)) {
+            ansi_colors[index] = color;
+        }
+    }
+    Some(ColorScheme::from_colors(
+        foreground,
+        background,
+        ansi_colors,
+    ))
+}
+
+fn build(clusters: &[Cluster], foreground: Color, background: Color) -> ColorScheme {
+    let ansi_colors = assign(clusters, &foreground, &background);
+    ColorScheme::from_colors(foreground, background, ansi_colors)
+}
+
+/// Assigns clusters to the ANSI colors by hue and lightness.
+fn assign(clusters: &[Cluster], foreground: &Color, background: &Color) -> [Color; 16] {
+    let mut ansi_colors: [Color; 16] = Default::default();
+
+    for (offset, &hue) in ANSI_HUES.iter().enumerate() {
+        // Up to two most common clusters of the hue: the darker for normal, the lighter for bright.
+        let mut candidates: Vec<&Color> = clusters
+            .iter()
+            .map(|cluster| &cluster.color)
+            .filter(|color| !is_gray(color) && hue_distance(color.to_hsl().0, hue) < 30.0)
+            .filter(|color| color.contrast_ratio(background) >= MIN_COLOR_CONTRAST)
+            .take(2)
+            .collect();
+        candidates.sort_by(|a, b| a.to_lab().0.total_cmp(&b.to_lab().0));
+        let (normal, bright) = match candidates.as_slice() {
+            [] => (
+                Color::from_hsl(hue, 0.6, 0.45),
+                Color::from_hsl(hue, 0.7, 0.6),
+            ),
+            [only] => ((*only).clone(), (*only).clone()),
+            [darker, lighter, ..] => ((*darker).clone(), (*lighter).clone()),
+        };
+        ansi_colors[offset + 1] = normal;
+        ansi_colors[offset + 9] = bright;
+    }
+
+    let mut grays: Vec<&Color> = clusters
+        .iter()
+        .map(|cluster| &cluster.color)
+        .filter(|color| is_gray(color))
+        .chain(vec![foreground, background])
+        .collect();
+    grays.sort_by(|a, b| a.to_lab().0.total_cmp(&b.to_lab().0));
+    let closest = |lightness: f64| {
+        grays
+            .iter()
+            .min_by(|a, b| {
+                (a.to_lab().0 - lightness)
+                    .abs()
+                    .total_cmp(&(b.to_lab().0 - lightness).abs())
+            })
+            .map(|color| (*color).clone())
+            .unwrap_or_default()
+    };
+    ansi_colors[0] = grays[0].clone();
+    ansi_colors[8] = closest(50.0);
+    ansi_colors[7] = closest(80.0);
+    ansi_colors[15] = grays[grays.len() - 1].clone();
+
+    ansi_colors
+}
+
+/// The lightest color as the foreground and the darkest as the background. Black or white is taken
+/// as the foreground instead if the lightest is not readable.
+fn lightest_and_darkest(clusters: &[Cluster]) -> Option<(Color, Color)> {
+    let lightness = |color: &Color| color.to_lab().0;
+    let background = clusters
+        .iter()
+        .map(|cluster| &cluster.color)
+        .min_by(|a, b| lightness(a).total_cmp(&lightness(b)))?
+        .clone();
+    let foreground = clusters
+        .iter()
+        .map(|cluster| &cluster.color)
+        .max_by(|a, b| lightness(a).total_cmp(&lightness(b)))
+        .filter(|color| color.contrast_ratio(&background) >= MIN_TEXT_CONTRAST)
+        .cloned()
+        .unwrap_or_else(|| readable_gray(&background));
+    Some((foreground, background))
+}
+
+/// Lowercase letters and digits of a color name, so that "Bright_Red" matches "bright red".
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn average(sums: &[u64; 3], count: usize) -> Color {
+    let [red, green, blue] = sums.map(|sum| (sum / count as u64) as u16);
+    Color { red, green, blue }
+}
+
+fn is_gray(color: &Color) -> bool {
+    let (_, a, b) = color.to_lab();
+    a.hypot(b) < GRAY_CHROMA
+}
+
+/// Black or white, whichever is more readable on the background.
+fn readable_gray(background: &Color) -> Color {
+    let black = Color::default();
+    let white = Color::from_rgb8(255, 255, 255);
+    if black.contrast_ratio(background) > white.contrast_ratio(background) {
+        black
+    } else {
+        white
+    }
+}
+
+fn hue_distance(a: f64, b: f64) -> f64 {
+    let distance = (a - b).rem_euclid(360.0);
+    distance.min(360.0 - distance)
+}
@@ -0,0 +1,93 @@
+use crate::color::ColorScheme;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; background: #f4f4f4; }
+.cards { display: flex; flex-wrap: wrap; gap: 1em; }
+.card { width: 18em; border-radius: 6px; overflow: hidden; box-shadow: 0 1px 3px #0004; }
+.card h2 { margin: 0; padding: 0.5em 0.75em; font-size: 1em; background: #fff; }
+.screen { padding: 0.75em; font-family: monospace; white-space: pre; }
+.swatches { display: flex; }
+.swatches span { flex: 1; height: 1.25em; }";
+
+/// Renders color schemes as cards of a static HTML page.
+///
+/// Each card has the name of the color scheme, a sample of text on the background and swatches of
+/// the normal and bright ANSI colors.
+pub fn to_html(title: &str, schemes: &[(String, ColorScheme)]) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{title}</title>
+<style>
+{style}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<p>{count} color schemes</p>
+<div class=\"cards\">
+",
+        title = escape(title),
+        style = STYLE,
+        count = schemes.len()
+    );
+    for (name, scheme) in schemes {
+        html.push_str(&card(name, scheme));
+    }
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
+
+fn card(name: &str, scheme: &ColorScheme) -> String {
+    let colors = scheme.ansi_colors();
+    let text = |index: usize, text: &str| {
+        format!(
+            "<span style=\"color: {}\">{}</span>",
+            colors[index].to_css_hex(),
+            escape(text)
+        )
+    };
+    let swatches = |range: std::ops::Range<usize>| -> String {
+        range
+            .map(|index| {
+                format!(
+                    "<span style=\"background: {}\"></span>",
+                    colors[index].to_css_hex()
+                )
+            })
+            .collect()
+    };
+    format!(
+        "<div class=\"card\">
+<h2>{name}</h2>
+<div class=\"screen\" style=\"color: {foreground}; background: {background}\">{prompt}:{path}$ ls
+{directory}  {link}  {executable}  README.md
+{error} {warning} {comment}</div>
+<div class=\"swatches\">{normal}</div>
+<div class=\"swatches\">{bright}</div>
+</div>
+",
+        name = escape(name),
+        foreground = scheme.foreground().to_css_hex(),
+        background = scheme.background().to_css_hex(),
+        prompt = text(2, "user@host"),
+        path = text(4, "~"),
+        directory = text(12, "src"),
+        link = text(14, "link"),
+        executable = text(10, "run.sh"),
+        error = text(1, "error"),
+        warning = text(3, "warning"),
+        comment = text(8, "// note"),
+        normal = swatches(0..8),
+        bright = swatches(8..16),
+    )
+}
+
+/// Escapes text for HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,89 @@
+use anyhow::{anyhow, bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The number of parallel jobs when nothing is specified.
+pub const DEFAULT_JOBS: usize = 8;
+
+/// The environment variable to set the number of parallel jobs with.
+pub const JOBS_ENV: &str = "COLORTTY_JOBS";
+
+static JOBS: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of parallel jobs for bulk operations: downloads, reading the cache and batch
+/// conversion.
+///
+/// Set with `set()` or `load()`, or `DEFAULT_JOBS` otherwise.
+pub fn get() -> usize {
+    match JOBS.load(Ordering::Relaxed) {
+        0 => DEFAULT_JOBS,
+        jobs => jobs,
+    }
+}
+
+/// Sets the number of parallel jobs for the process. Values under 1 are taken as 1.
+pub fn set(jobs: usize) {
+    JOBS.store(jobs.max(1), Ordering::Relaxed);
+}
+
+/// Sets the number of parallel jobs from the `COLORTTY_JOBS` environment variable, or from `jobs`
+/// in the config file if the variable is not set. Both must be positive integers.
+pub fn load() -> Result<()> {
+    match std::env::var(JOBS_ENV) {
+        Ok(value) => {
+            set(parse_env(&value)?);
+            Ok(())
+        }
+        Err(std::env::VarError::NotPresent) => match config_path() {
+            Ok(path) => load_config(&path),
+            // Without a config directory, there is no config file either.
+            Err(_) => Ok(()),
+        },
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", JOBS_ENV)),
+    }
+}
+
+/// Parses the value of the `COLORTTY_JOBS` environment variable, which must be a positive integer.
+pub fn parse_env(value: &str) -> Result<usize> {
+    match value.parse() {
+        Ok(jobs) if jobs > 0 => Ok(jobs),
+        _ => bail!("{} must be a positive integer: {}", JOBS_ENV, value),
+    }
+}
+
+/// The config file of colortty in the config directory, like `~/.config/colortty/config.toml`.
+pub fn config_path() -> Result<PathBuf> {
+    let mut path = dirs::config_dir().ok_or(anyhow!("There is no config directory"))?;
+    path.push("colortty");
+    path.push("config.toml");
+    Ok(path)
+}
+
+/// Reads the number of parallel jobs from a config file. Does nothing if the file doesn't exist.
+pub fn load_config(path: &Path) -> Result<()> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read: {}", path.display())),
+    };
+    let jobs = parse_config(&content).map_err(|e| anyhow!("{:#} in {}", e, path.display()))?;
+    if let Some(jobs) = jobs {
+        set(jobs);
+    }
+    Ok(())
+}
+
+/// Parses `jobs = N` in the content of a config file, which must be a positive integer.
+pub fn parse_config(content: &str) -> Result<Option<usize>> {
+    let table: toml::Table = content
+        .parse()
+        .context("Failed to parse the config as TOML")?;
+    match table.get("jobs") {
+        None => Ok(None),
+        Some(value) => match value.as_integer().filter(|&jobs| jobs > 0) {
+            Some(jobs) => Ok(Some(jobs as usize)),
+            None => bail!("jobs must be a positive integer"),
+        },
+    }
+}
@@ -1,5 +1,25 @@
+pub mod a11y;
+pub mod alacritty;
+pub mod appearance;
 pub mod color;
+pub mod dedupe;
+pub mod extract;
+pub mod gallery;
+pub mod jobs;
+pub mod lint;
+pub mod log;
+pub mod manpage;
+pub mod picker;
+pub mod plist;
+pub mod preview;
 pub mod provider;
+pub mod state;
+pub mod stats;
+pub mod suggest;
+pub mod tags;
+pub mod terminal;
 
-pub use crate::color::{AlacrittyConfigFormat, Color, ColorScheme, ColorSchemeFormat};
+pub use crate::color::{
+    AlacrittyConfigFormat, Color, ColorScheme, ColorSchemeFormat, OutputFormat,
+};
 pub use crate::provider::Provider;
@@ -0,0 +1,69 @@
+use crate::a11y::MIN_DELTA_E;
+use crate::color::{Color, ColorScheme, ANSI_COLOR_NAMES};
+
+/// A likely mistake in a color scheme.
+#[derive(Debug, PartialEq)]
+pub struct Problem {
+    /// A short identifier of the rule that found the problem, such as `identical-bright`.
+    pub rule: &'static str,
+    pub message: String,
+}
+
+/// Looks for common authoring mistakes in a color scheme.
+pub fn lint(scheme: &ColorScheme) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let colors = scheme.ansi_colors();
+    let black = Color::default();
+
+    for index in 0..8 {
+        if colors[index] == colors[index + 8] {
+            problems.push(Problem {
+                rule: "identical-bright",
+                message: format!(
+                    "{} is the same as {} ({})",
+                    ANSI_COLOR_NAMES[index + 8],
+                    ANSI_COLOR_NAMES[index],
+                    colors[index].to_hex()
+                ),
+            });
+        }
+    }
+
+    // Among the ANSI colors, pure black is expected only in the black slot. Elsewhere, it usually
+    // means that the source didn't define the color.
+    for (name, color) in ANSI_COLOR_NAMES.iter().zip(colors.iter()).skip(1) {
+        if **color == black {
+            problems.push(Problem {
+                rule: "default-black",
+                message: format!(
+                    "{} is {}, which may be a missing color",
+                    name,
+                    black.to_hex()
+                ),
+            });
+        }
+    }
+
+    let delta_e = scheme.foreground().delta_e(scheme.background());
+    if delta_e < MIN_DELTA_E {
+        problems.push(Problem {
+            rule: "foreground-background",
+            message: format!(
+                "foreground is too close to background (ΔE {:.1}, below {})",
+                delta_e, MIN_DELTA_E
+            ),
+        });
+    }
+
+    if scheme.cursor() == Some(scheme.background()) {
+        problems.push(Problem {
+            rule: "cursor-background",
+            message: format!(
+                "cursor is the same as background ({})",
+                scheme.background().to_hex()
+            ),
+        });
+    }
+
+    problems
+}
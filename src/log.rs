@@ -0,0 +1,48 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much colortty tells on stderr about what it's doing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only warnings and errors.
+    Quiet,
+    /// Progress like downloads and written files too.
+    Normal,
+    /// HTTP requests, cache hits and misses and parse timing too.
+    Verbose,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// The verbosity of the process, `Verbosity::Normal` unless set with `set()`.
+pub fn get() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        _ => Verbosity::Verbose,
+    }
+}
+
+/// Sets the verbosity of the process.
+pub fn set(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// Prints a message about progress on stderr unless the verbosity is quiet.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::log::get() >= $crate::log::Verbosity::Normal {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Prints a detailed message on stderr only if the verbosity is verbose.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::get() >= $crate::log::Verbosity::Verbose {
+            eprintln!($($arg)*);
+        }
+    };
+}
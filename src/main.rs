@@ -1,154 +1,2443 @@
 use anyhow::{anyhow, bail, Context, Result};
-use colortty::{AlacrittyConfigFormat, ColorScheme, ColorSchemeFormat, Provider};
-use getopts::Options;
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap::error::{ContextKind, ContextValue};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use colortty::a11y::{self, Category, Severity};
+use colortty::alacritty;
+use colortty::appearance::{self, Appearance};
+use colortty::color::{ParseError, ANSI_COLOR_NAMES};
+use colortty::extract;
+use colortty::gallery;
+use colortty::info;
+use colortty::jobs;
+use colortty::lint;
+use colortty::log::{self, Verbosity};
+use colortty::manpage::ManPage;
+use colortty::picker;
+use colortty::preview;
+use colortty::provider::{self, FetchError};
+use colortty::state::{Applied, State};
+use colortty::stats::{PaletteStats, HUE_NAMES};
+use colortty::suggest;
+use colortty::tags::{self, Tags};
+#[cfg(unix)]
+use colortty::terminal;
+use colortty::{
+    dedupe, AlacrittyConfigFormat, Color, ColorScheme, ColorSchemeFormat, OutputFormat, Provider,
+};
+use futures::{stream, StreamExt, TryStreamExt};
 use std::env;
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::{self, IsTerminal, Read, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime};
 
 #[async_std::main]
 async fn main() {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => exit_with_usage_error(e),
+    };
+    let error_format = cli.error_format;
+    // --jobs takes precedence over the environment variable and the config file.
+    match cli.jobs {
+        Some(jobs) => jobs::set(jobs.get()),
+        None => {
+            if let Err(e) = jobs::load() {
+                exit_with_error(&e, error_format);
+            }
+        }
+    }
+    if cli.verbose {
+        log::set(Verbosity::Verbose);
+    } else if cli.quiet {
+        log::set(Verbosity::Quiet);
+    }
+    if let Some(token) = cli.github_token {
+        provider::set_github_token(token);
+    }
+
+    let command = match cli.command {
+        Some(command) => command,
+        None => return help(),
+    };
+    let result = match command {
+        Command::Convert(args) => convert(args).await,
+        Command::List(args) => list(args).await,
+        Command::Search(args) => search(args).await,
+        Command::Get(args) => get(args).await,
+        Command::Apply(args) => apply(args).await,
+        Command::Install(args) => install(args).await,
+        Command::Watch(args) => watch(args).await,
+        Command::Random(args) => random(args).await,
+        Command::ExportAll(args) => export_all(args).await,
+        Command::Update => update().await,
+        Command::Cache(args) => cache(args).await,
+        Command::Prune(args) => prune(args).await,
+        Command::Show(args) => show(args).await,
+        Command::Info(args) => info(args).await,
+        Command::Status(args) => status(args),
+        Command::Tag(args) => tag(args).await,
+        Command::Dedupe(args) => dedupe(args).await,
+        Command::Stats(args) => stats(args).await,
+        Command::A11y(args) => accessibility(args).await,
+        Command::Check(args) => check(args).await,
+        Command::Lint(args) => lint(args).await,
+        Command::Preview(args) => preview(args).await,
+        Command::Live(args) => live(args).await,
+        Command::Gallery(args) => gallery(args).await,
+        Command::Pick(args) => pick(args).await,
+        Command::FromImage(args) => from_image(args),
+        Command::Manpage(args) => manpage(args),
+        Command::Help(args) => command_help(&args.command),
+    };
+    if let Err(e) = result {
+        exit_with_error(&e, error_format);
+    }
+}
+
+// -- errors
+
+/// An error in how a command is invoked.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+struct UsageError(String);
+
+/// An error of a color scheme or a file that doesn't exist.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+struct NotFoundError(String);
+
+/// Kinds of errors that are told apart by exit codes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ErrorKind {
+    // Also used when a check like `lint` finds problems.
+    Failure,
+    Usage,
+    NotFound,
+    Network,
+    Parse,
+}
+
+impl ErrorKind {
+    /// Classifies an error by the errors and the contexts in it.
+    fn of(error: &anyhow::Error) -> Self {
+        if error.is::<UsageError>() {
+            return ErrorKind::Usage;
+        }
+        if error.is::<NotFoundError>() {
+            return ErrorKind::NotFound;
+        }
+        if let Some(fetch_error) = error.downcast_ref::<FetchError>() {
+            return match fetch_error {
+                FetchError::Status(404) => ErrorKind::NotFound,
+                _ => ErrorKind::Network,
+            };
+        }
+        if let Some(io_error) = error.downcast_ref::<io::Error>() {
+            if io_error.kind() == io::ErrorKind::NotFound {
+                return ErrorKind::NotFound;
+            }
+        }
+        if let Some(image_error) = error.downcast_ref::<image::ImageError>() {
+            return match image_error {
+                image::ImageError::IoError(e) if e.kind() == io::ErrorKind::NotFound => {
+                    ErrorKind::NotFound
+                }
+                image::ImageError::IoError(_) => ErrorKind::Failure,
+                _ => ErrorKind::Parse,
+            };
+        }
+        if error.is::<ParseError>() || error.is::<json::Error>() {
+            return ErrorKind::Parse;
+        }
+        ErrorKind::Failure
+    }
+
+    fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Failure => 1,
+            ErrorKind::Usage => 2,
+            ErrorKind::NotFound => 3,
+            ErrorKind::Network => 4,
+            ErrorKind::Parse => 5,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Failure => "failure",
+            ErrorKind::Usage => "usage",
+            ErrorKind::NotFound => "not-found",
+            ErrorKind::Network => "network",
+            ErrorKind::Parse => "parse",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum ErrorFormat {
+    Text,
+    Json,
+}
+
+/// Prints an error in the arguments like `exit_with_error` and exits with the code for usage
+/// errors, or prints the help that was asked for and exits successfully.
+fn exit_with_usage_error(error: clap::Error) -> ! {
+    if !error.use_stderr() {
+        error.exit();
+    }
+    // The arguments didn't parse, so look for `--error-format json` by hand.
     let args: Vec<String> = env::args().collect();
+    let json = args
+        .windows(2)
+        .any(|pair| pair[0] == "--error-format" && pair[1] == "json")
+        || args.iter().any(|arg| arg == "--error-format=json");
+    let format = if json {
+        ErrorFormat::Json
+    } else {
+        ErrorFormat::Text
+    };
+
+    if let Some(ContextValue::String(name)) = error.get(ContextKind::InvalidSubcommand) {
+        // Find the command that the unknown subcommand was given to, like `cache` of `cache foo`.
+        let mut parent = Cli::command();
+        for arg in args.iter().skip(1).take_while(|arg| *arg != name) {
+            if let Some(command) = parent.find_subcommand(arg) {
+                parent = command.clone();
+            }
+        }
+        exit_with_error(&unknown_command(name, &parent), format);
+    }
+    if format == ErrorFormat::Text {
+        error.exit();
+    }
+    let rendered = error.render().to_string();
+    let message = rendered.lines().next().unwrap_or_default();
+    let message = message.strip_prefix("error: ").unwrap_or(message);
+    exit_with_error(&UsageError(message.to_owned()).into(), format)
+}
+
+/// An error of an unknown command, suggesting commands with similar names.
+fn unknown_command(name: &str, parent: &clap::Command) -> anyhow::Error {
+    let names: Vec<String> = parent
+        .get_subcommands()
+        .map(|command| command.get_name().to_owned())
+        .collect();
+    let similar = suggest::similar_names(name, &names, 3);
+    let message = if similar.is_empty() {
+        format!("no such subcommand: `{}`", name)
+    } else {
+        format!(
+            "no such subcommand: `{}`. Did you mean: {}?",
+            name,
+            similar.join(", ")
+        )
+    };
+    UsageError(message).into()
+}
+
+/// Prints usage examples, or the summary, the usage and the options of a command like
+/// `colortty get --help`.
+fn command_help(names: &[String]) -> Result<()> {
+    if names.is_empty() {
+        help();
+        return Ok(());
+    }
+    let mut command = Cli::command();
+    command.build();
+    for name in names {
+        let subcommand = command
+            .find_subcommand(name)
+            .ok_or_else(|| unknown_command(name, &command))?
+            .clone();
+        command = subcommand;
+    }
+    command.print_help().context("Failed to write to stdout")
+}
+
+/// Prints an error on stderr and exits with the code for its kind.
+fn exit_with_error(error: &anyhow::Error, format: ErrorFormat) -> ! {
+    let kind = ErrorKind::of(error);
+    match format {
+        ErrorFormat::Text => eprintln!("error: {}", error),
+        ErrorFormat::Json => {
+            let causes: Vec<String> = error.chain().skip(1).map(|e| e.to_string()).collect();
+            let output = json::object! {
+                "error" => json::object! {
+                    "kind" => kind.name(),
+                    "exit_code" => kind.exit_code(),
+                    "message" => error.to_string(),
+                    "causes" => causes,
+                },
+            };
+            eprintln!("{}", output.dump());
+        }
+    }
+    process::exit(kind.exit_code());
+}
+
+// -- commands
+
+/// The heading of options that all commands take, which are listed after their own options.
+const GLOBAL_OPTIONS: &str = "Global options";
+
+/// color scheme converter for alacritty
+#[derive(Parser)]
+#[command(
+    name = "colortty",
+    disable_help_subcommand = true,
+    after_help = "Run `colortty help` for usage examples."
+)]
+struct Cli {
+    /// print errors as text or as JSON on stderr
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t = ErrorFormat::Text,
+        help_heading = GLOBAL_OPTIONS
+    )]
+    error_format: ErrorFormat,
+    /// number of parallel downloads, cache reads and conversions (default: $COLORTTY_JOBS, jobs
+    /// in ~/.config/colortty/config.toml or 8)
+    #[arg(long, global = true, value_name = "N", help_heading = GLOBAL_OPTIONS)]
+    jobs: Option<NonZeroUsize>,
+    /// token to send to GitHub to avoid its rate limit of 60 requests per hour (default:
+    /// $GITHUB_TOKEN)
+    #[arg(long, global = true, value_name = "TOKEN", help_heading = GLOBAL_OPTIONS)]
+    github_token: Option<String>,
+    /// also print HTTP requests, cache hits and misses and parse timing
+    #[arg(
+        short,
+        long,
+        global = true,
+        conflicts_with = "quiet",
+        help_heading = GLOBAL_OPTIONS
+    )]
+    verbose: bool,
+    /// don't print progress like downloads and written files
+    #[arg(short, long, global = true, help_heading = GLOBAL_OPTIONS)]
+    quiet: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// convert a color scheme file into alacritty configuration
+    Convert(ConvertArgs),
+    /// list color schemes of a provider
+    List(ListArgs),
+    /// search color schemes of all providers by name
+    Search(SearchArgs),
+    /// get a color scheme of a provider as alacritty configuration
+    Get(GetArgs),
+    /// get a color scheme of a provider and import it in the alacritty config
+    Apply(ApplyArgs),
+    /// write a color scheme into alacritty's themes directory
+    Install(InstallArgs),
+    /// switch color schemes when the system switches between dark and light
+    Watch(WatchArgs),
+    /// show a random color scheme of a provider
+    Random(RandomArgs),
+    /// convert all color schemes of a provider into files in a directory
+    ExportAll(ExportAllArgs),
+    /// update cached color schemes of all providers
+    Update,
+    /// show or clear cached color schemes
+    #[command(subcommand)]
+    Cache(CacheAction),
+    /// find cached color scheme files that fail to parse
+    Prune(PruneArgs),
+    /// show the fingerprint and contrast of a color scheme
+    Show(SourceArgs),
+    /// print the colors of a color scheme in multiple notations
+    Info(SourceArgs),
+    /// show the color schemes that were last applied to terminals
+    Status(StatusArgs),
+    /// attach tags to color schemes to filter them with list --tag
+    #[command(subcommand)]
+    Tag(TagAction),
+    /// find color schemes that look the same across providers
+    Dedupe(DedupeArgs),
+    /// show palette statistics of color schemes
+    Stats(StatsArgs),
+    /// check accessibility of a color scheme
+    A11y(SourceArgs),
+    /// check contrast ratios of the colors of a color scheme on their backgrounds
+    Check(CheckArgs),
+    /// find common mistakes in color schemes
+    Lint(LintArgs),
+    /// render sample screens of color schemes side by side
+    Preview(SourcesArgs),
+    /// try a color scheme in the current terminal until a key is pressed
+    Live(SourceArgs),
+    /// render cached color schemes as a static HTML page
+    Gallery(GalleryArgs),
+    /// pick a color scheme with fuzzy filtering and a live preview
+    Pick(PickArgs),
+    /// create a color scheme from the colors of an image
+    FromImage(FromImageArgs),
+    /// print man pages of colortty
+    Manpage(ManpageArgs),
+    /// print usage examples, or the options of a command
+    Help(HelpArgs),
+}
+
+#[derive(Args)]
+struct ConvertArgs {
+    /// files, URLs or directories to convert, or `-` for stdin
+    #[arg(required = true, value_name = "SOURCE")]
+    sources: Vec<String>,
+    #[command(flatten)]
+    input_format: InputFormatOption,
+    #[command(flatten)]
+    output_format: OutputFormatOption,
+    /// color scheme to convert from a file with multiple color schemes
+    #[arg(short, long)]
+    name: Option<String>,
+    /// convert all color schemes in the file
+    #[arg(short, long)]
+    all: bool,
+    /// list color schemes in the file
+    #[arg(short, long)]
+    list: bool,
+    /// convert all files in directories into --out-dir, which is also done without this
+    #[arg(short, long)]
+    recursive: bool,
+    /// directory to write a file per color scheme into instead of stdout
+    #[arg(long, value_name = "DIR")]
+    out_dir: Option<String>,
+    #[command(flatten)]
+    output: OutputOptions,
+    #[command(flatten)]
+    full_config: FullConfigOption,
+}
+
+async fn convert(args: ConvertArgs) -> Result<()> {
+    let output_format = &args.output_format.output_format;
+
+    // A directory for --output works like --out-dir if there are multiple sources.
+    let output_dir =
+        args.output.output.clone().filter(|path| {
+            args.sources.len() > 1 && (path.ends_with('/') || Path::new(path).is_dir())
+        });
+    if let Some(out_dir) = args
+        .out_dir
+        .clone()
+        .or_else(|| args.output.output_dir.clone())
+        .or(output_dir)
+    {
+        let mut files = Vec::new();
+        for source in &args.sources {
+            if source == "-" {
+                bail!(UsageError(
+                    "stdin can't be converted into --out-dir".to_owned()
+                ));
+            }
+            if provider::is_url(source) {
+                bail!(UsageError(
+                    "URLs can't be converted into --out-dir".to_owned()
+                ));
+            }
+            let source = Path::new(source);
+            if source.is_dir() {
+                let mut found = Vec::new();
+                collect_files(source, &mut found)
+                    .with_context(|| format!("Failed to read: {}", source.display()))?;
+                found.sort();
+                for file in found {
+                    let relative_path = file.strip_prefix(source).unwrap_or(&file).to_owned();
+                    files.push((file, relative_path));
+                }
+            } else {
+                let file_name = PathBuf::from(source.file_name().unwrap_or_default());
+                files.push((source.to_owned(), file_name));
+            }
+        }
+        return convert_files(
+            &files,
+            Path::new(&out_dir),
+            args.input_format.input_format.as_ref(),
+            output_format,
+            args.full_config.enabled,
+        );
+    }
+    if args.recursive {
+        bail!(UsageError(
+            "--out-dir is required with --recursive".to_owned()
+        ));
+    }
+
+    // Outputs of multiple sources are delimited by comments with the sources, and by document
+    // separators in YAML.
+    let multiple_sources = args.sources.len() > 1;
+    let mut output = String::new();
+    for source in &args.sources {
+        let schemes = read_color_schemes(source, &args).await?;
+        if args.list {
+            for (name, _) in &schemes {
+                let name = name.as_deref().unwrap_or("(unnamed)");
+                if multiple_sources {
+                    println!("{}: {}", source, name);
+                } else {
+                    println!("{}", name);
+                }
+            }
+            continue;
+        }
+
+        let with_names = schemes.len() > 1;
+        for (name, scheme) in &schemes {
+            if multiple_sources {
+                if let OutputFormat::Alacritty(AlacrittyConfigFormat::Yaml) = output_format {
+                    output.push_str("---\n");
+                }
+                push_comment(&mut output, output_format, &format!("Source: {}", source));
+            }
+            if let Some(name) = name.as_ref().filter(|_| with_names) {
+                push_comment(&mut output, output_format, name);
+            }
+            push_comment(
+                &mut output,
+                output_format,
+                &format!("Fingerprint: {}", scheme.fingerprint()),
+            );
+            output.push_str(&scheme.serialize(
+                output_format,
+                &name.clone().unwrap_or_else(|| source_name(source)),
+            ));
+            output.push('\n');
+        }
+    }
+
+    match &args.output.output {
+        Some(path) => {
+            // The file is named after the source if a directory is given.
+            let path = output_path(path, &source_name(&args.sources[0]), output_format);
+            write_output(&path, &output)?;
+        }
+        None => print!("{}", output),
+    }
+
+    Ok(())
+}
+
+/// Appends a line of comment if the format allows it.
+fn push_comment(output: &mut String, format: &OutputFormat, text: &str) {
+    if let Some(comment) = format.comment(text) {
+        output.push_str(&comment);
+        output.push('\n');
+    }
+}
+
+/// The file to write into for `--output`, which is named after the color scheme with the extension
+/// of the format if the path is a directory.
+fn output_path(path: &str, name: &str, format: &OutputFormat) -> PathBuf {
+    let is_dir = path.ends_with('/') || path.ends_with(std::path::MAIN_SEPARATOR);
+    let path = PathBuf::from(path);
+    if is_dir || path.is_dir() {
+        path.join(format!(
+            "{}.{}",
+            sanitize_filename(name),
+            format.extension()
+        ))
+    } else {
+        path
+    }
+}
+
+/// Writes an output into a file, creating its directory if necessary.
+fn write_output(path: &Path, output: &str) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create: {}", dir.display()))?;
+    }
+    std::fs::write(path, output).with_context(|| format!("Failed to write: {}", path.display()))?;
+    info!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// The name of a color scheme from a source file without names inside, like `Dracula` of
+/// `Dracula.itermcolors`.
+fn source_name(source: &str) -> String {
+    let path = if provider::is_url(source) {
+        provider::url_path(source)
+    } else {
+        source.to_owned()
+    };
+    match source {
+        "-" => "Untitled".to_owned(),
+        _ => Path::new(&path)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(source)
+            .to_owned(),
+    }
+}
+
+/// Reads the color schemes to convert from a source file, a URL or stdin (`-`).
+///
+/// Fails if the source has multiple color schemes unless one is chosen with `--name` or all with
+/// `--all` or `--list`.
+async fn read_color_schemes(
+    source: &str,
+    args: &ConvertArgs,
+) -> Result<Vec<(Option<String>, ColorScheme)>> {
+    let mut buffer = Vec::new();
+    // The format is guessed from the path of a URL.
+    let mut filename = source.to_owned();
+    if source == "-" {
+        io::stdin()
+            .read_to_end(&mut buffer)
+            .context("Failed to read stdin")?;
+    } else if provider::is_url(source) {
+        buffer = provider::download(source)
+            .await
+            .with_context(|| format!("Failed to download: {}", source))?;
+        filename = provider::url_path(source);
+    } else {
+        File::open(source)
+            .and_then(|mut file| file.read_to_end(&mut buffer))
+            .with_context(|| format!("Failed to read: {}", source))?;
+    }
+
+    let input_format = args
+        .input_format
+        .input_format
+        .clone()
+        .or_else(|| ColorSchemeFormat::from_filename(&filename))
+        .or_else(|| ColorSchemeFormat::from_content(&String::from_utf8_lossy(&buffer)))
+        .ok_or_else(|| {
+            UsageError(format!(
+                "Input format is not specified and failed to guess from the source file name: {}",
+                source
+            ))
+        })?;
+
+    let mut schemes = ColorScheme::parse_all_bytes(&buffer, &input_format)
+        .with_context(|| format!("Failed to parse: {}", source))?;
+    if args.full_config.enabled {
+        for (_, scheme) in &mut schemes {
+            scheme.derive_ui_colors();
+        }
+    }
+    if args.list {
+        return Ok(schemes);
+    }
+
+    if let Some(name) = &args.name {
+        schemes.retain(|(scheme_name, _)| scheme_name.as_ref() == Some(name));
+        if schemes.is_empty() {
+            bail!(NotFoundError(format!(
+                "Color scheme is not found in {}: {}",
+                source, name
+            )));
+        }
+    } else if schemes.len() > 1 && !args.all {
+        for (name, _) in &schemes {
+            info!("{}", name.as_deref().unwrap_or("(unnamed)"));
+        }
+        bail!(
+            "{} contains {} color schemes. Choose one with --name or convert all with --all",
+            source,
+            schemes.len()
+        );
+    }
+    Ok(schemes)
+}
+
+/// Converts files into `out_dir`, each into the path relative to `out_dir` that comes with it.
+///
+/// Files are converted by parallel workers. Files that can't be converted are skipped with
+/// warnings.
+fn convert_files(
+    files: &[(PathBuf, PathBuf)],
+    out_dir: &Path,
+    input_format: Option<&ColorSchemeFormat>,
+    output_format: &OutputFormat,
+    full_config: bool,
+) -> Result<()> {
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, Result<Option<String>>)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs::get().min(files.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let (file, relative_path) = match files.get(index) {
+                            Some(file) => file,
+                            None => return results,
+                        };
+                        let result = convert_file(
+                            file,
+                            &out_dir.join(relative_path),
+                            input_format,
+                            output_format,
+                            full_config,
+                        );
+                        results.push((index, result));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+    results.sort_by_key(|(index, _)| *index);
+
+    let (mut converted, mut skipped) = (0, 0);
+    for (index, result) in results {
+        let relative_path = &files[index].1;
+        match result? {
+            None => converted += 1,
+            Some(reason) => {
+                info!("warning: {}: {}", relative_path.display(), reason);
+                skipped += 1;
+            }
+        }
+    }
+
+    info!(
+        "Converted {} files into {} ({} skipped)",
+        converted,
+        out_dir.display(),
+        skipped
+    );
+    Ok(())
+}
+
+/// Converts a file into files next to `target`, which is the path of the file in the output
+/// directory. Returns why the file is skipped if it's not a color scheme that can be converted.
+fn convert_file(
+    file: &Path,
+    target: &Path,
+    input_format: Option<&ColorSchemeFormat>,
+    output_format: &OutputFormat,
+    full_config: bool,
+) -> Result<Option<String>> {
+    let content = match std::fs::read(file) {
+        Ok(content) => content,
+        Err(e) => return Ok(Some(e.to_string())),
+    };
+    let format = input_format
+        .cloned()
+        .or_else(|| ColorSchemeFormat::from_filename(&file.to_string_lossy()))
+        .or_else(|| ColorSchemeFormat::from_content(&String::from_utf8_lossy(&content)));
+    let format = match format {
+        Some(format) => format,
+        None => return Ok(Some("unknown format".to_owned())),
+    };
+    let mut schemes = match ColorScheme::parse_all_bytes(&content, &format) {
+        Ok(schemes) => schemes,
+        Err(e) => return Ok(Some(e.to_string())),
+    };
+    if full_config {
+        for (_, scheme) in &mut schemes {
+            scheme.derive_ui_colors();
+        }
+    }
+
+    let target_dir = target.parent().unwrap_or_else(|| Path::new(""));
+    std::fs::create_dir_all(target_dir)
+        .with_context(|| format!("Failed to create: {}", target_dir.display()))?;
+    let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+    for (name, scheme) in &schemes {
+        let filename = match name {
+            Some(name) if schemes.len() > 1 => format!("{}-{}", stem, sanitize_filename(name)),
+            _ => stem.to_string(),
+        };
+        let path = target_dir.join(format!("{}.{}", filename, output_format.extension()));
+        let output = scheme.serialize(output_format, name.as_deref().unwrap_or(&stem));
+        std::fs::write(&path, output)
+            .with_context(|| format!("Failed to write: {}", path.display()))?;
+    }
+    Ok(None)
+}
+
+/// Collects paths of all files under a directory recursively.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Args)]
+struct ListArgs {
+    #[command(flatten)]
+    provider: ProviderOption,
+    /// update color scheme cache
+    #[arg(short, long)]
+    update_cache: bool,
+    /// hide color schemes that look the same as another one
+    #[arg(long)]
+    unique: bool,
+    /// hide color schemes whose foreground has a lower contrast ratio on the background
+    #[arg(long, value_name = "RATIO")]
+    min_contrast: Option<f64>,
+    /// show only color schemes with a dark background
+    #[arg(long, conflicts_with = "light")]
+    dark: bool,
+    /// show only color schemes with a light background
+    #[arg(long)]
+    light: bool,
+    /// sort order
+    #[arg(long, value_enum, value_name = "ORDER")]
+    sort: Option<SortOrder>,
+    /// show only color schemes with the tag, which can be given multiple times
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// print only names, one per line, without previews and the summary
+    #[arg(long)]
+    names_only: bool,
+    /// pick one of the listed color schemes and print it like `pick`
+    #[arg(short, long)]
+    interactive: bool,
+    /// print only names like --names-only, which is the default when stdout is not a terminal
+    #[arg(long, conflicts_with = "force_color")]
+    no_color: bool,
+    /// print previews even when stdout is not a terminal
+    #[arg(long)]
+    force_color: bool,
+    /// show color schemes in a grid of N columns, or as many as fit in the terminal with 'auto'
+    #[arg(long, value_name = "N|auto", value_parser = parse_columns)]
+    columns: Option<Columns>,
+}
 
-    if args.len() < 2 {
-        return help();
+/// The number of columns of a grid.
+#[derive(Clone, Copy)]
+enum Columns {
+    /// As many as fit in the terminal.
+    Auto,
+    Count(usize),
+}
+
+fn parse_columns(s: &str) -> std::result::Result<Columns, String> {
+    match s {
+        "auto" => Ok(Columns::Auto),
+        _ => match s.parse::<usize>() {
+            Ok(n) if n > 0 => Ok(Columns::Count(n)),
+            _ => Err("expected a positive number or 'auto'".to_owned()),
+        },
+    }
+}
+
+async fn list(args: ListArgs) -> Result<()> {
+    let provider = args.provider.get();
+
+    if args.update_cache {
+        provider.download_all().await?;
+    }
+
+    // Previews are escape sequences that would be garbage in pipes and files.
+    let color = args.force_color
+        || (!args.no_color
+            && env::var_os("NO_COLOR").unwrap_or_default().is_empty()
+            && io::stdout().is_terminal());
+
+    let mut color_schemes = provider.list().await?;
+    if args.dark {
+        color_schemes.retain(|(_, color_scheme)| color_scheme.is_dark());
+    } else if args.light {
+        color_schemes.retain(|(_, color_scheme)| !color_scheme.is_dark());
+    }
+    if args.unique {
+        color_schemes = remove_duplicates(color_schemes);
+    }
+    if let Some(min_contrast) = args.min_contrast {
+        color_schemes.retain(|(_, color_scheme)| contrast_ratio(color_scheme) >= min_contrast);
+    }
+    if !args.tags.is_empty() {
+        let tags = Tags::load(&Tags::default_path()?)?;
+        color_schemes.retain(|(name, color_scheme)| {
+            let scheme_tags = tags.all_of(name, color_scheme);
+            args.tags
+                .iter()
+                .all(|tag| scheme_tags.contains(&tag.to_lowercase()))
+        });
+    }
+    if let Some(order) = args.sort {
+        sort_color_schemes(&mut color_schemes, order);
+    }
+
+    if args.interactive {
+        if color_schemes.is_empty() {
+            bail!("There are no color schemes to pick from");
+        }
+        if let Some((name, color_scheme)) = pick_interactively(&color_schemes, true)? {
+            print!(
+                "{}",
+                get_output(&provider, name, color_scheme, &OutputFormat::default())
+            );
+        }
+        return Ok(());
+    }
+
+    if args.names_only || !color {
+        for (name, _) in &color_schemes {
+            println!("{}", name);
+        }
+        return Ok(());
+    }
+
+    if let Some(columns) = args.columns {
+        let schemes: Vec<_> = color_schemes
+            .iter()
+            .map(|(name, color_scheme)| (name.as_str(), color_scheme))
+            .collect();
+        let columns = match columns {
+            Columns::Auto => preview::grid_columns(&schemes, terminal_width()),
+            Columns::Count(columns) => columns,
+        };
+        if !schemes.is_empty() {
+            println!("{}", preview::grid(&schemes, columns));
+        }
+    } else {
+        let mut max_name_length = 0;
+        for (name, _) in &color_schemes {
+            max_name_length = max_name_length.max(name.len());
+        }
+
+        for (name, color_scheme) in &color_schemes {
+            println!(
+                "{:width$} {}",
+                name,
+                color_scheme.to_preview(),
+                width = max_name_length
+            );
+        }
+    }
+
+    let noun = if color_schemes.len() == 1 {
+        "scheme"
+    } else {
+        "schemes"
+    };
+    let mut summary = format!("{} {} ({})", color_schemes.len(), noun, provider.name());
+    if let Some(updated_at) = provider.cache_updated_at().await {
+        let age = SystemTime::now()
+            .duration_since(updated_at)
+            .unwrap_or_default();
+        summary.push_str(&format!(", cache updated {}", format_age(age)));
+    }
+    println!("{}", summary);
+
+    Ok(())
+}
+
+#[derive(Args)]
+struct SearchArgs {
+    /// words to look for in names
+    #[arg(required = true, value_name = "QUERY")]
+    query: Vec<String>,
+    #[command(flatten)]
+    providers: ProvidersOption,
+    /// match names that contain the characters of the query in order, best matches first
+    #[arg(short, long)]
+    fuzzy: bool,
+    /// print only providers and names without previews and the summary
+    #[arg(long)]
+    names_only: bool,
+}
+
+async fn search(args: SearchArgs) -> Result<()> {
+    let query = args.query.join(" ");
+    let lower_query = query.to_lowercase();
+    let fuzzy = args.fuzzy;
+    let providers = args.providers.get_all();
+
+    // (score, provider, name, color scheme)
+    let mut results = Vec::new();
+    for provider in &providers {
+        let mut color_schemes = match provider.list().await {
+            Ok(color_schemes) => color_schemes,
+            // Other providers may still have matches.
+            Err(e) if providers.len() > 1 => {
+                eprintln!("warning: {}: {:#}", provider.name(), e);
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        color_schemes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (name, color_scheme) in color_schemes {
+            let score = if fuzzy {
+                picker::fuzzy_score(&query, &name)
+            } else if name.to_lowercase().contains(&lower_query) {
+                Some(0)
+            } else {
+                None
+            };
+            if let Some(score) = score {
+                results.push((score, provider.name().to_owned(), name, color_scheme));
+            }
+        }
+    }
+    // Stable, so results with the same score stay in the order of providers and names.
+    results.sort_by(|(a, ..), (b, ..)| b.cmp(a));
+
+    if results.is_empty() {
+        bail!(NotFoundError(format!("No color schemes match: {}", query)));
+    }
+
+    let provider_width = results.iter().map(|(_, p, ..)| p.len()).max().unwrap_or(0);
+    let name_width = results
+        .iter()
+        .map(|(_, _, n, _)| n.len())
+        .max()
+        .unwrap_or(0);
+    for (_, provider, name, color_scheme) in &results {
+        if args.names_only {
+            println!("{:width$} {}", provider, name, width = provider_width);
+        } else {
+            println!(
+                "{:provider_width$} {:name_width$} {}",
+                provider,
+                name,
+                color_scheme.to_preview(),
+                provider_width = provider_width,
+                name_width = name_width
+            );
+        }
+    }
+    if !args.names_only {
+        let noun = if results.len() == 1 {
+            "match"
+        } else {
+            "matches"
+        };
+        println!("{} {}", results.len(), noun);
+    }
+
+    Ok(())
+}
+
+#[derive(Args)]
+struct GetArgs {
+    /// names of color schemes, or parts of them
+    #[arg(required = true, value_name = "NAME")]
+    names: Vec<String>,
+    #[command(flatten)]
+    provider: ProviderOption,
+    #[command(flatten)]
+    output_format: OutputFormatOption,
+    /// write into a file named after the color scheme instead of stdout
+    #[arg(short, long)]
+    write: bool,
+    /// directory to write into (default: current directory)
+    #[arg(long, value_name = "DIR", requires = "write")]
+    dir: Option<String>,
+    #[command(flatten)]
+    output: OutputOptions,
+    /// directory to write a file per color scheme into instead of stdout
+    #[arg(long, value_name = "DIR")]
+    out_dir: Option<String>,
+    /// don't look for cached color schemes whose names contain the given name
+    #[arg(long)]
+    exact: bool,
+    #[command(flatten)]
+    full_config: FullConfigOption,
+}
+
+async fn get(args: GetArgs) -> Result<()> {
+    let output_format = &args.output_format.output_format;
+
+    let provider = args.provider.get();
+    let mut names = Vec::new();
+    for name in &args.names {
+        names.push(if args.exact {
+            name.to_owned()
+        } else {
+            resolve_name(&provider, name).await?
+        });
+    }
+    // Fetch color schemes in parallel, keeping the order.
+    let mut color_schemes: Vec<ColorScheme> =
+        stream::iter(names.iter().map(|name| get_color_scheme(&provider, name)))
+            .buffered(jobs::get())
+            .try_collect()
+            .await?;
+    if args.full_config.enabled {
+        for color_scheme in &mut color_schemes {
+            color_scheme.derive_ui_colors();
+        }
+    }
+
+    let multiple = names.len() > 1;
+    let output_dir = args
+        .out_dir
+        .clone()
+        .or_else(|| args.output.output_dir.clone())
+        .or_else(|| {
+            args.write
+                .then(|| args.dir.clone().unwrap_or_else(|| ".".to_owned()))
+        })
+        .map(|dir| format!("{}/", dir));
+    // A file per color scheme with a directory, or all of them in one output otherwise.
+    let paths: Vec<Option<PathBuf>> = names
+        .iter()
+        .map(|name| match (&output_dir, &args.output.output) {
+            (Some(dir), _) => Some(output_path(dir, name, output_format)),
+            (None, Some(path)) => Some(output_path(path, name, output_format)),
+            (None, None) => None,
+        })
+        .collect();
+    let one_output = paths.windows(2).all(|pair| pair[0] == pair[1]);
+
+    if one_output {
+        // Outputs of multiple color schemes are delimited by comments with the names, and by
+        // document separators in YAML.
+        let mut output = String::new();
+        for (name, color_scheme) in names.iter().zip(&color_schemes) {
+            if multiple {
+                if let OutputFormat::Alacritty(AlacrittyConfigFormat::Yaml) = output_format {
+                    output.push_str("---\n");
+                }
+                push_comment(&mut output, output_format, name);
+            }
+            output.push_str(&get_output(&provider, name, color_scheme, output_format));
+        }
+        match &paths[0] {
+            Some(path) => {
+                write_output(path, &output)?;
+                if !multiple {
+                    record_output(&provider, &names[0], output_format, path.clone())?;
+                }
+            }
+            None => print!("{}", output),
+        }
+    } else {
+        for ((name, color_scheme), path) in names.iter().zip(&color_schemes).zip(paths) {
+            let path = path.unwrap_or_default();
+            write_output(
+                &path,
+                &get_output(&provider, name, color_scheme, output_format),
+            )?;
+            record_output(&provider, name, output_format, path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Records a color scheme written for a terminal as applied.
+fn record_output(
+    provider: &Provider,
+    name: &str,
+    format: &OutputFormat,
+    path: PathBuf,
+) -> Result<()> {
+    if let Some(target) = terminal_of(format) {
+        let state_path = State::default_path()?;
+        let mut state = State::load(&state_path)?;
+        state.record(Applied {
+            target: target.to_owned(),
+            name: name.to_owned(),
+            provider: Some(provider.name().to_owned()),
+            path: Some(path.canonicalize().unwrap_or(path)),
+            applied_at: SystemTime::now(),
+        });
+        state.save(&state_path)?;
+    }
+    Ok(())
+}
+
+/// Writes a color scheme into `themes` next to the alacritty config, imports it in the config and
+/// records it as applied.
+fn write_alacritty_theme(
+    provider: &Provider,
+    name: &str,
+    color_scheme: &ColorScheme,
+    config_path: &Path,
+    backup: bool,
+) -> Result<()> {
+    let config_dir = config_path.parent().unwrap_or_else(|| Path::new(""));
+    let theme_path = write_theme_file(provider, name, color_scheme, &config_dir.join("themes"))?;
+    import_in_config(&theme_path, config_path, backup)?;
+    record_alacritty_theme(provider, name, theme_path)
+}
+
+/// Writes a color scheme as `NAME.toml` into a directory and returns its absolute path.
+fn write_theme_file(
+    provider: &Provider,
+    name: &str,
+    color_scheme: &ColorScheme,
+    theme_dir: &Path,
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(theme_dir)
+        .with_context(|| format!("Failed to create: {}", theme_dir.display()))?;
+    let theme_path = theme_dir.join(format!("{}.toml", sanitize_filename(name)));
+    let theme_path = if theme_path.is_relative() {
+        env::current_dir()
+            .context("Failed to get the current directory")?
+            .join(theme_path)
+    } else {
+        theme_path
+    };
+    let theme = get_output(provider, name, color_scheme, &OutputFormat::default());
+    std::fs::write(&theme_path, theme)
+        .with_context(|| format!("Failed to write: {}", theme_path.display()))?;
+    info!("Wrote {}", theme_path.display());
+    Ok(theme_path)
+}
+
+/// Imports a theme file in the alacritty config, copying the config into `.bak` first with `backup`.
+fn import_in_config(theme_path: &Path, config_path: &Path, backup: bool) -> Result<()> {
+    let config = match std::fs::read_to_string(config_path) {
+        Ok(config) => config,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read: {}", config_path.display()))
+        }
+    };
+    let updated = alacritty::import_theme(&config, theme_path)
+        .with_context(|| format!("Failed to parse: {}", config_path.display()))?;
+    if backup && !config.is_empty() {
+        let mut backup = config_path.as_os_str().to_owned();
+        backup.push(".bak");
+        std::fs::write(&backup, &config)
+            .with_context(|| format!("Failed to write: {}", Path::new(&backup).display()))?;
+        info!("Backed up {}", Path::new(&backup).display());
+    }
+    std::fs::write(config_path, updated)
+        .with_context(|| format!("Failed to write: {}", config_path.display()))?;
+    info!("Imported it in {}", config_path.display());
+    Ok(())
+}
+
+fn record_alacritty_theme(provider: &Provider, name: &str, theme_path: PathBuf) -> Result<()> {
+    let state_path = State::default_path()?;
+    let mut state = State::load(&state_path)?;
+    state.record(Applied {
+        target: "alacritty".to_owned(),
+        name: name.to_owned(),
+        provider: Some(provider.name().to_owned()),
+        path: Some(theme_path),
+        applied_at: SystemTime::now(),
+    });
+    state.save(&state_path)
+}
+
+#[derive(Args)]
+struct ApplyArgs {
+    /// name of the color scheme, or a part of it
+    name: String,
+    #[command(flatten)]
+    provider: ProviderOption,
+    /// alacritty config to edit (default: the one that alacritty reads)
+    #[arg(short, long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// copy the config into a file with `.bak` appended before editing it
+    #[arg(short, long)]
+    backup: bool,
+    /// don't look for cached color schemes whose names contain the given name
+    #[arg(long)]
+    exact: bool,
+    #[command(flatten)]
+    full_config: FullConfigOption,
+}
+
+/// Writes a color scheme into `themes` next to the alacritty config and imports it in the config.
+async fn apply(args: ApplyArgs) -> Result<()> {
+    let config_path = match args.config {
+        Some(path) => path,
+        None => alacritty::config_path().ok_or(anyhow!("There is no home directory"))?,
+    };
+
+    let provider = args.provider.get();
+    let name = if args.exact {
+        args.name
+    } else {
+        resolve_name(&provider, &args.name).await?
+    };
+    let mut color_scheme = get_color_scheme(&provider, &name).await?;
+    if args.full_config.enabled {
+        color_scheme.derive_ui_colors();
+    }
+
+    write_alacritty_theme(&provider, &name, &color_scheme, &config_path, args.backup)
+}
+
+#[derive(Args)]
+struct InstallArgs {
+    /// name of the color scheme, or a part of it
+    name: String,
+    #[command(flatten)]
+    provider: ProviderOption,
+    /// directory to write the theme into (default: `themes` next to the alacritty config)
+    #[arg(short, long, value_name = "DIR")]
+    dir: Option<PathBuf>,
+    /// import the theme in the alacritty config instead of printing the import line
+    #[arg(long)]
+    import: bool,
+    /// alacritty config to import the theme in (default: the one that alacritty reads)
+    #[arg(short, long, value_name = "FILE", requires = "import")]
+    config: Option<PathBuf>,
+    /// copy the config into a file with `.bak` appended before importing the theme
+    #[arg(short, long, requires = "import")]
+    backup: bool,
+    /// don't look for cached color schemes whose names contain the given name
+    #[arg(long)]
+    exact: bool,
+    #[command(flatten)]
+    full_config: FullConfigOption,
+}
+
+/// Writes a color scheme into alacritty's themes directory and prints the line to import it, or
+/// imports it in the config with `--import`.
+async fn install(args: InstallArgs) -> Result<()> {
+    let config_path = args.config.or_else(alacritty::config_path);
+    let theme_dir = match (args.dir, &config_path) {
+        (Some(dir), _) => dir,
+        (None, Some(config_path)) => config_path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join("themes"),
+        (None, None) => bail!("There is no home directory"),
+    };
+
+    let provider = args.provider.get();
+    let name = if args.exact {
+        args.name
+    } else {
+        resolve_name(&provider, &args.name).await?
+    };
+    let mut color_scheme = get_color_scheme(&provider, &name).await?;
+    if args.full_config.enabled {
+        color_scheme.derive_ui_colors();
+    }
+
+    let theme_path = write_theme_file(&provider, &name, &color_scheme, &theme_dir)?;
+    if args.import {
+        let config_path = config_path.ok_or(anyhow!("There is no home directory"))?;
+        import_in_config(&theme_path, &config_path, args.backup)?;
+        record_alacritty_theme(&provider, &name, theme_path)
+    } else {
+        print!("{}", alacritty::import_theme("", &theme_path)?);
+        Ok(())
+    }
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    #[command(flatten)]
+    provider: ProviderOption,
+    /// color scheme for the dark appearance
+    #[arg(long, value_name = "NAME")]
+    dark: String,
+    /// color scheme for the light appearance
+    #[arg(long, value_name = "NAME")]
+    light: String,
+    /// print OSC escape sequences to recolor the terminal instead of editing the alacritty config
+    #[arg(long, conflicts_with = "config")]
+    osc: bool,
+    /// alacritty config to edit (default: the one that alacritty reads)
+    #[arg(short, long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// seconds between checks of the system appearance
+    #[arg(long, value_name = "SECONDS", default_value = "5", value_parser = parse_seconds)]
+    interval: Duration,
+    #[command(flatten)]
+    full_config: FullConfigOption,
+}
+
+fn parse_seconds(s: &str) -> std::result::Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .filter(|seconds| *seconds > 0.0)
+        .map(Duration::from_secs_f64)
+        .ok_or_else(|| "expected a positive number of seconds".to_owned())
+}
+
+/// Applies the color scheme for the appearance of macOS or GNOME, and again whenever it changes.
+async fn watch(args: WatchArgs) -> Result<()> {
+    let osc = args.osc;
+    let config_path = match args.config {
+        Some(path) => path,
+        None => alacritty::config_path().ok_or(anyhow!("There is no home directory"))?,
+    };
+
+    // Get both color schemes first so that switching doesn't fail on the network.
+    let provider = args.provider.get();
+    let mut color_schemes = Vec::new();
+    for name in [&args.dark, &args.light].iter() {
+        let name = resolve_name(&provider, name).await?;
+        let mut color_scheme = get_color_scheme(&provider, &name).await?;
+        if args.full_config.enabled {
+            color_scheme.derive_ui_colors();
+        }
+        color_schemes.push((name, color_scheme));
+    }
+
+    let mut current = None;
+    loop {
+        let detected = appearance::detect();
+        if detected.is_none() && current.is_none() {
+            bail!("Failed to detect the system appearance. watch supports macOS and GNOME");
+        }
+        if let Some(detected) = detected.filter(|detected| current != Some(*detected)) {
+            let (name, color_scheme) = match detected {
+                Appearance::Dark => &color_schemes[0],
+                Appearance::Light => &color_schemes[1],
+            };
+            if osc {
+                print!("{}", color_scheme.to_osc());
+                io::stdout().flush().context("Failed to write to stdout")?;
+            } else {
+                write_alacritty_theme(&provider, name, color_scheme, &config_path, false)?;
+            }
+            info!(
+                "Switched to {} for the {} appearance",
+                name,
+                detected.name()
+            );
+            current = Some(detected);
+        }
+        async_std::task::sleep(args.interval).await;
+    }
+}
+
+#[derive(Args)]
+struct RandomArgs {
+    #[command(flatten)]
+    provider: ProviderOption,
+    #[command(flatten)]
+    output_format: OutputFormatOption,
+    /// pick only color schemes with a dark background
+    #[arg(long, conflicts_with = "light")]
+    dark: bool,
+    /// pick only color schemes with a light background
+    #[arg(long)]
+    light: bool,
+    /// print the color scheme in the output format, showing the preview on stderr
+    #[arg(long)]
+    print: bool,
+    /// import the color scheme in the alacritty config, showing the preview on stderr
+    #[arg(long)]
+    apply: bool,
+}
+
+async fn random(args: RandomArgs) -> Result<()> {
+    let format = &args.output_format.output_format;
+
+    let provider = args.provider.get();
+    let mut color_schemes = provider.list().await?;
+    if args.dark {
+        color_schemes.retain(|(_, color_scheme)| color_scheme.is_dark());
+    } else if args.light {
+        color_schemes.retain(|(_, color_scheme)| !color_scheme.is_dark());
+    }
+    if color_schemes.is_empty() {
+        bail!(NotFoundError(
+            "There are no color schemes to pick from".to_owned()
+        ));
+    }
+    let (name, color_scheme) = &color_schemes[random_index(color_schemes.len())];
+
+    let screen = preview::side_by_side(&[(name.as_str(), color_scheme)], terminal_width());
+    if args.print {
+        eprintln!("{}", screen);
+        print!("{}", get_output(&provider, name, color_scheme, format));
+    } else if args.apply {
+        eprintln!("{}", screen);
+        let config_path = alacritty::config_path().ok_or(anyhow!("There is no home directory"))?;
+        write_alacritty_theme(&provider, name, color_scheme, &config_path, false)?;
+    } else {
+        println!("{}", screen);
+    }
+
+    Ok(())
+}
+
+/// A random index below `len`, which is seeded differently every time by the standard library.
+fn random_index(len: usize) -> usize {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let random = RandomState::new().build_hasher().finish();
+    (random % len as u64) as usize
+}
+
+#[derive(Args)]
+struct ExportAllArgs {
+    #[command(flatten)]
+    provider: ProviderOption,
+    #[command(flatten)]
+    output_format: OutputFormatOption,
+    /// directory to write <name>.<extension> of each color scheme into
+    #[arg(long, value_name = "DIR")]
+    out_dir: PathBuf,
+    #[command(flatten)]
+    full_config: FullConfigOption,
+}
+
+async fn export_all(args: ExportAllArgs) -> Result<()> {
+    let out_dir = args.out_dir.as_path();
+    let format = &args.output_format.output_format;
+
+    let provider = args.provider.get();
+    let mut color_schemes = provider.list().await?;
+    color_schemes.sort_by(|(a, _), (b, _)| a.cmp(b));
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create: {}", out_dir.display()))?;
+    for (name, color_scheme) in &mut color_schemes {
+        if args.full_config.enabled {
+            color_scheme.derive_ui_colors();
+        }
+        let path = out_dir.join(format!(
+            "{}.{}",
+            sanitize_filename(name),
+            format.extension()
+        ));
+        std::fs::write(&path, get_output(&provider, name, color_scheme, format))
+            .with_context(|| format!("Failed to write: {}", path.display()))?;
+    }
+
+    info!(
+        "Exported {} color schemes ({}) into {}",
+        color_schemes.len(),
+        provider.name(),
+        out_dir.display()
+    );
+    Ok(())
+}
+
+async fn update() -> Result<()> {
+    for provider in Provider::all() {
+        let count = provider.download_all().await?;
+        if count == 0 {
+            info!("{}: up to date", provider.name());
+        } else {
+            info!("{}: {} color schemes downloaded", provider.name(), count);
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// print the cache directory, or the one of a provider
+    Path(ProvidersOption),
+    /// show the number, size and age of cached color schemes
+    Status(ProvidersOption),
+    /// delete cached color schemes
+    Clear(ClearArgs),
+    /// same as `colortty prune`
+    Prune(PruneArgs),
+}
+
+#[derive(Args)]
+struct ClearArgs {
+    #[command(flatten)]
+    providers: ProvidersOption,
+    /// clear the caches of all providers, which is required without --provider
+    #[arg(long, required_unless_present = "provider")]
+    all: bool,
+}
+
+async fn cache(action: CacheAction) -> Result<()> {
+    match action {
+        CacheAction::Path(providers) => match providers.get() {
+            Some(provider) => println!("{}", provider.repo_dir()?.display()),
+            None => println!("{}", colortty::provider::cache_dir()?.display()),
+        },
+        CacheAction::Status(providers) => {
+            let providers = providers.get_all();
+            let (mut total_count, mut total_size) = (0, 0);
+            for provider in &providers {
+                let (count, size) = provider.cache_size().await;
+                let updated = match provider.cache_updated_at().await {
+                    Some(updated_at) => format!(
+                        "updated {}",
+                        format_age(
+                            SystemTime::now()
+                                .duration_since(updated_at)
+                                .unwrap_or_default()
+                        )
+                    ),
+                    None => "not cached".to_owned(),
+                };
+                println!(
+                    "{}: {} color schemes, {}, {}",
+                    provider.name(),
+                    count,
+                    format_size(size),
+                    updated
+                );
+                total_count += count;
+                total_size += size;
+            }
+            if providers.len() > 1 {
+                println!(
+                    "total: {} color schemes, {}",
+                    total_count,
+                    format_size(total_size)
+                );
+            }
+        }
+        CacheAction::Clear(args) => {
+            for provider in &args.providers.get_all() {
+                if provider.clear_cache().await? {
+                    println!("{}: cleared", provider.name());
+                } else {
+                    println!("{}: not cached", provider.name());
+                }
+            }
+        }
+        CacheAction::Prune(args) => prune(args).await?,
+    }
+
+    Ok(())
+}
+
+#[derive(Args)]
+struct PruneArgs {
+    #[command(flatten)]
+    providers: ProvidersOption,
+    /// delete unparsable color scheme files
+    #[arg(short, long, conflicts_with = "redownload")]
+    delete: bool,
+    /// download unparsable color scheme files again
+    #[arg(short, long)]
+    redownload: bool,
+}
+
+async fn prune(args: PruneArgs) -> Result<()> {
+    let providers = args.providers.get_all();
+
+    let mut remaining = 0;
+    for provider in &providers {
+        let broken = match provider.broken().await {
+            Ok(broken) => broken,
+            // Nothing is cached yet.
+            Err(_) => continue,
+        };
+        for (name, error) in &broken {
+            println!("{}: {}: {:#}", provider.name(), name, error);
+            if args.delete {
+                provider.remove_cached(name).await?;
+                println!("  deleted");
+            } else if args.redownload {
+                if let Some(error) = provider.redownload(name).await? {
+                    println!("  downloaded again, but still unparsable: {:#}", error);
+                    remaining += 1;
+                } else {
+                    println!("  downloaded again");
+                }
+            } else {
+                remaining += 1;
+            }
+        }
+    }
+
+    if remaining > 0 {
+        bail!("Found {} unparsable color scheme files", remaining);
+    }
+
+    Ok(())
+}
+
+#[derive(Args)]
+struct SourceArgs {
+    /// name of a color scheme of the provider, or a color scheme file
+    #[arg(value_name = "NAME|FILE")]
+    source: String,
+    #[command(flatten)]
+    options: SourceOptions,
+}
+
+async fn show(args: SourceArgs) -> Result<()> {
+    let source = &args.source;
+    let color_scheme = load_color_scheme(source, &args.options).await?;
+    println!("Name:        {}", source);
+    println!("Fingerprint: {}", color_scheme.fingerprint());
+    println!(
+        "Contrast:    {:.2}:1 (foreground on background)",
+        contrast_ratio(&color_scheme)
+    );
+    println!("Preview:     {}", color_scheme.to_preview());
+
+    Ok(())
+}
+
+async fn info(args: SourceArgs) -> Result<()> {
+    let color_scheme = load_color_scheme(&args.source, &args.options).await?;
+
+    let mut slots: Vec<(String, &Color)> = vec![
+        ("foreground".to_owned(), color_scheme.foreground()),
+        ("background".to_owned(), color_scheme.background()),
+    ];
+    if let Some(cursor) = color_scheme.cursor() {
+        slots.push(("cursor".to_owned(), cursor));
+    }
+    if let Some(cursor_text) = color_scheme.cursor_text() {
+        slots.push(("cursor text".to_owned(), cursor_text));
+    }
+    for (index, color) in color_scheme.ansi_colors().iter().enumerate() {
+        slots.push((ANSI_COLOR_NAMES[index].to_owned(), color));
+    }
+    for (name, color) in color_scheme.extended_colors() {
+        slots.push((name.replace('_', " "), color));
+    }
+
+    let slot_width = slots.iter().map(|(slot, _)| slot.len()).max().unwrap_or(0);
+    println!(
+        "{:slot_width$}        {:7}  {:18}  {:20}  XTERM",
+        "SLOT",
+        "HEX",
+        "RGB",
+        "HSL",
+        slot_width = slot_width
+    );
+    for (slot, color) in &slots {
+        let (red, green, blue) = color.to_rgb8();
+        let (hue, saturation, lightness) = color.to_hsl();
+        println!(
+            "{:slot_width$}  {}    \x1b[0m  {}  {:18}  {:20}  {}",
+            slot,
+            color.to_24bit_be(),
+            color.to_css_hex(),
+            format!("rgb({}, {}, {})", red, green, blue),
+            format!(
+                "hsl({:.0}, {:.0}%, {:.0}%)",
+                hue,
+                saturation * 100.0,
+                lightness * 100.0
+            ),
+            color.to_xterm_256(),
+            slot_width = slot_width
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Args)]
+struct StatusArgs {
+    /// terminal to show the color scheme of, like alacritty
+    target: Option<String>,
+    /// output format
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+}
+
+/// Formats of reports like `status` and `lint`.
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum ReportFormat {
+    Text,
+    Json,
+}
+
+fn status(args: StatusArgs) -> Result<()> {
+    let mut state = State::load(&State::default_path()?)?;
+    if let Some(target) = &args.target {
+        let applied: Vec<Applied> = state
+            .applied()
+            .iter()
+            .filter(|applied| &applied.target == target)
+            .cloned()
+            .collect();
+        if applied.is_empty() {
+            bail!(NotFoundError(format!(
+                "No color scheme has been applied to {}",
+                target
+            )));
+        }
+        state = State::default();
+        for applied in applied {
+            state.record(applied);
+        }
+    }
+
+    if args.format == ReportFormat::Json {
+        println!("{}", state.to_json().dump());
+        return Ok(());
+    }
+    if state.applied().is_empty() {
+        println!("No color scheme has been applied yet");
+    }
+    for (i, applied) in state.applied().iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let age = SystemTime::now()
+            .duration_since(applied.applied_at)
+            .unwrap_or_default();
+        println!("Target:   {}", applied.target);
+        println!("Name:     {}", applied.name);
+        if let Some(provider) = &applied.provider {
+            println!("Provider: {}", provider);
+        }
+        if let Some(path) = &applied.path {
+            println!("Path:     {}", path.display());
+        }
+        println!("Applied:  {}", format_age(age));
+    }
+
+    Ok(())
+}
+
+#[derive(Subcommand)]
+enum TagAction {
+    /// add tags to a color scheme
+    Add(TagChange),
+    /// remove tags from a color scheme
+    Remove(TagChange),
+    /// list all tags with the numbers of color schemes, or the tags of a color scheme
+    List {
+        /// color scheme to list the tags of, including automatic ones like dark
+        name: Option<String>,
+        #[command(flatten)]
+        provider: ProviderOption,
+    },
+}
+
+#[derive(Args)]
+struct TagChange {
+    /// name of the color scheme
+    name: String,
+    /// tags to add or remove
+    #[arg(required = true, value_name = "TAG")]
+    tags: Vec<String>,
+}
+
+async fn tag(action: TagAction) -> Result<()> {
+    let path = Tags::default_path()?;
+    let mut tags = Tags::load(&path)?;
+
+    let (add, change) = match action {
+        TagAction::Add(change) => (true, change),
+        TagAction::Remove(change) => (false, change),
+        TagAction::List { name: None, .. } => {
+            for (tag, count) in tags.counts() {
+                println!("{} ({})", tag, count);
+            }
+            return Ok(());
+        }
+        TagAction::List {
+            name: Some(name),
+            provider,
+        } => {
+            let cached = provider.get().cached().await;
+            let tag_names = match cached.iter().find(|(cached_name, _)| *cached_name == name) {
+                Some((_, color_scheme)) => tags.all_of(&name, color_scheme),
+                None => tags.of(&name).into_iter().map(str::to_owned).collect(),
+            };
+            println!("{}", tag_names.join(" "));
+            return Ok(());
+        }
+    };
+
+    let names: Vec<&str> = change.tags.iter().map(String::as_str).collect();
+    if let Some(tag) = names.iter().find(|tag| tags::AUTO_TAGS.contains(tag)) {
+        bail!(UsageError(format!(
+            "'{}' is given automatically and can't be changed",
+            tag
+        )));
+    }
+    if add {
+        tags.add(&change.name, &names);
+    } else {
+        tags.remove(&change.name, &names);
+    }
+    tags.save(&path)?;
+    println!("{}: {}", change.name, tags.of(&change.name).join(" "));
+
+    Ok(())
+}
+
+#[derive(Args)]
+struct DedupeArgs {
+    /// maximum average color difference (CIE76 ΔE) between duplicates
+    #[arg(short, long, default_value_t = dedupe::DEFAULT_THRESHOLD)]
+    threshold: f64,
+}
+
+async fn dedupe(args: DedupeArgs) -> Result<()> {
+    let mut entries = Vec::new();
+    for provider in Provider::all() {
+        let provider_name = provider.name().to_owned();
+        let mut color_schemes = provider.list().await?;
+        color_schemes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (name, color_scheme) in color_schemes {
+            entries.push((provider_name.clone(), name, color_scheme));
+        }
+    }
+
+    let schemes: Vec<_> = entries.iter().map(|(_, _, scheme)| scheme).collect();
+    let groups = dedupe::group_duplicates(&schemes, args.threshold);
+    for group in groups.iter().filter(|group| group.len() > 1) {
+        let (_, name, color_scheme) = &entries[group[0]];
+        println!("{} {}", name, color_scheme.to_preview());
+        for &index in group {
+            let (provider_name, name, _) = &entries[index];
+            println!("    {:5} {}", provider_name, name);
+        }
     }
 
-    match args[1].as_ref() {
-        "convert" => handle_error(convert(args)),
-        "list" => handle_error(list(args).await),
-        "get" => handle_error(get(args).await),
-        "help" => help(),
-        _ => {
-            eprintln!("error: no such subcommand: `{}`", args[1]);
-            process::exit(1);
-        }
-    };
+    Ok(())
 }
 
-fn handle_error(result: Result<()>) {
-    if let Err(e) = result {
-        eprintln!("error: {}", e);
-        process::exit(1);
-    }
+#[derive(Args)]
+struct StatsArgs {
+    /// name of a color scheme of the provider, or a color scheme file
+    #[arg(value_name = "NAME|FILE", required_unless_present = "all")]
+    source: Option<String>,
+    #[command(flatten)]
+    options: SourceOptions,
+    /// summarize all cached color schemes of the provider
+    #[arg(short, long)]
+    all: bool,
 }
 
-// -- commands
+async fn stats(args: StatsArgs) -> Result<()> {
+    let stats = if args.all {
+        let provider = args.options.provider.get();
+        let all_stats: Vec<_> = provider
+            .list()
+            .await?
+            .iter()
+            .map(|(_, color_scheme)| PaletteStats::new(color_scheme))
+            .collect();
+        PaletteStats::aggregate(&all_stats).ok_or(anyhow!("There are no color schemes"))?
+    } else {
+        let source = args.source.unwrap_or_default();
+        let color_scheme = load_color_scheme(&source, &args.options).await?;
+        PaletteStats::new(&color_scheme)
+    };
 
-fn convert(args: Vec<String>) -> Result<()> {
-    let mut opts = Options::new();
-    opts.optopt(
-        "i",
-        "input-format",
-        "input format: 'iterm'|'mintty'|'gogh'",
-        "INPUT_FORMAT",
+    if stats.count > 1 {
+        println!("Schemes:    {}", stats.count);
+    }
+    println!(
+        "Brightness: {:.1} (average L* of ANSI colors)",
+        stats.brightness
     );
-    opts.optopt(
-        "o",
-        "output-format",
-        "output format: 'yaml'|'toml'",
-        "OUTPUT_FORMAT",
+    println!(
+        "Saturation: {:.2} - {:.2}",
+        stats.saturation.0, stats.saturation.1
     );
-    let matches = opts
-        .parse(&args[2..])
-        .context("Failed to parse arguments")?;
-
-    if matches.free.is_empty() {
-        bail!("Source is not specified");
-    }
-
-    let source = &matches.free[0];
-    let input_format = matches
-        .opt_str("i")
-        .and_then(|s| ColorSchemeFormat::from_string(&s))
-        .or_else(|| ColorSchemeFormat::from_filename(&source))
-        .ok_or(anyhow!(
-            "Input format is not specified and failed to guess from the source file name"
-        ))?;
-    let output_format = matches
-        .opt_str("o")
-        .and_then(|s| AlacrittyConfigFormat::from_string(&s))
-        .unwrap_or(AlacrittyConfigFormat::Yaml);
-
-    let mut buffer = String::new();
-    if source == "-" {
-        io::stdin()
-            .read_to_string(&mut buffer)
-            .context("Failed to read stdin")?;
+    if stats.count > 1 {
+        println!(
+            "Contrast:   {:.2}:1 - {:.2}:1, {:.2}:1 on average (foreground on background)",
+            stats.contrast.0, stats.contrast.2, stats.contrast.1
+        );
     } else {
-        File::open(source)
-            .unwrap()
-            .read_to_string(&mut buffer)
-            .with_context(|| format!("Failed to read: {}", source))?;
+        println!(
+            "Contrast:   {:.2}:1 (foreground on background)",
+            stats.contrast.1
+        );
+    }
+    println!("Hues:");
+    for (name, count) in HUE_NAMES.iter().zip(stats.hues.iter()) {
+        println!("    {:7} {}", name, count);
     }
 
-    let scheme = match input_format {
-        ColorSchemeFormat::ITerm => ColorScheme::from_iterm(&buffer),
-        ColorSchemeFormat::Mintty => ColorScheme::from_minttyrc(&buffer),
-        ColorSchemeFormat::Gogh => ColorScheme::from_gogh(&buffer),
-    }?;
-    let output = match output_format {
-        AlacrittyConfigFormat::Yaml => scheme.to_yaml(),
-        // TODO: Output in toml.
-        AlacrittyConfigFormat::Toml => scheme.to_yaml(),
-    };
-    println!("{}", output);
+    Ok(())
+}
+
+async fn accessibility(args: SourceArgs) -> Result<()> {
+    let color_scheme = load_color_scheme(&args.source, &args.options).await?;
+    let report = a11y::Report::new(&color_scheme);
+
+    for category in [
+        Category::Contrast,
+        Category::ColorBlindness,
+        Category::ColorDifference,
+    ]
+    .iter()
+    {
+        println!("{}", category.title());
+        for check in report.checks.iter().filter(|c| c.category == *category) {
+            let label = match check.severity {
+                Severity::Pass => "PASS",
+                Severity::Warning => "WARN",
+                Severity::Failure => "FAIL",
+            };
+            println!("  {}  {}", label, check.message);
+        }
+    }
+
+    let failures = report.count(Severity::Failure);
+    println!(
+        "\nSummary: {} ({} failures, {} warnings)",
+        if report.passed() { "PASS" } else { "FAIL" },
+        failures,
+        report.count(Severity::Warning)
+    );
+    if !report.passed() {
+        bail!("{} accessibility checks failed", failures);
+    }
 
     Ok(())
 }
 
-async fn list(args: Vec<String>) -> Result<()> {
-    let mut opts = Options::new();
-    set_provider_option(&mut opts);
-    opts.optflag("u", "update-cache", "update color scheme cache");
+#[derive(Args)]
+struct CheckArgs {
+    /// name of a color scheme of the provider, or a color scheme file
+    #[arg(value_name = "NAME|FILE")]
+    source: String,
+    #[command(flatten)]
+    options: SourceOptions,
+    /// minimum contrast ratio of the foreground and the cursor text
+    #[arg(long, value_name = "RATIO", default_value_t = a11y::MIN_TEXT_CONTRAST)]
+    min_text: f64,
+    /// minimum contrast ratio of ANSI colors
+    #[arg(long, value_name = "RATIO", default_value_t = a11y::MIN_ANSI_CONTRAST)]
+    min_ansi: f64,
+}
 
-    let matches = opts
-        .parse(&args[2..])
-        .context("Failed to parse arguments")?;
-    let provider = get_provider(&matches)?;
+async fn check(args: CheckArgs) -> Result<()> {
+    let color_scheme = load_color_scheme(&args.source, &args.options).await?;
+    let mut low = 0;
+    for contrast in a11y::contrast_ratios(&color_scheme) {
+        let min = if contrast.is_text {
+            args.min_text
+        } else {
+            args.min_ansi
+        };
+        let status = if contrast.is_exempt {
+            "-"
+        } else if contrast.ratio < min {
+            low += 1;
+            "LOW"
+        } else {
+            "OK"
+        };
+        println!("{:14} {:>6.2}:1  {}", contrast.slot, contrast.ratio, status);
+    }
 
-    if matches.opt_present("u") {
-        provider.download_all().await?;
+    if low > 0 {
+        bail!("Found {} colors below the minimum contrast ratio", low);
     }
 
-    let color_schemes = provider.list().await?;
+    Ok(())
+}
+
+#[derive(Args)]
+struct LintArgs {
+    /// names of color schemes of the provider, or color scheme files
+    #[arg(required = true, value_name = "NAME|FILE")]
+    sources: Vec<String>,
+    #[command(flatten)]
+    options: SourceOptions,
+    /// output format
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+}
+
+async fn lint(args: LintArgs) -> Result<()> {
+    let json_output = args.format == ReportFormat::Json;
+
+    let mut results = json::JsonValue::new_array();
+    let mut problem_count = 0;
+    for source in &args.sources {
+        let color_scheme = load_color_scheme(source, &args.options).await?;
+        let problems = lint::lint(&color_scheme);
+        problem_count += problems.len();
 
-    let mut max_name_length = 0;
-    for (name, _) in &color_schemes {
-        max_name_length = max_name_length.max(name.len());
+        if json_output {
+            let mut json_problems = json::JsonValue::new_array();
+            for problem in &problems {
+                json_problems.push(json::object! {
+                    "rule" => problem.rule,
+                    "message" => problem.message.as_str(),
+                })?;
+            }
+            results.push(json::object! {
+                "source" => source.as_str(),
+                "problems" => json_problems,
+            })?;
+        } else {
+            for problem in &problems {
+                println!("{}: {}: {}", source, problem.rule, problem.message);
+            }
+        }
     }
 
-    for (name, color_scheme) in &color_schemes {
-        println!(
-            "{:width$} {}",
-            name,
-            color_scheme.to_preview(),
-            width = max_name_length
-        );
+    if json_output {
+        println!("{}", results.dump());
+    }
+    if problem_count > 0 {
+        bail!("Found {} problems", problem_count);
+    }
+
+    Ok(())
+}
+
+#[derive(Args)]
+struct SourcesArgs {
+    /// names of color schemes of the provider, or color scheme files
+    #[arg(required = true, value_name = "NAME|FILE")]
+    sources: Vec<String>,
+    #[command(flatten)]
+    options: SourceOptions,
+}
+
+async fn preview(args: SourcesArgs) -> Result<()> {
+    let mut color_schemes = Vec::new();
+    for source in &args.sources {
+        color_schemes.push((
+            source.as_str(),
+            load_color_scheme(source, &args.options).await?,
+        ));
+    }
+
+    let schemes: Vec<_> = color_schemes
+        .iter()
+        .map(|(name, color_scheme)| (*name, color_scheme))
+        .collect();
+    println!("{}", preview::side_by_side(&schemes, terminal_width()));
+
+    Ok(())
+}
+
+async fn live(args: SourceArgs) -> Result<()> {
+    let color_scheme = load_color_scheme(&args.source, &args.options).await?;
+    try_on_terminal(&color_scheme, &args.source)
+}
+
+#[cfg(unix)]
+fn try_on_terminal(color_scheme: &ColorScheme, name: &str) -> Result<()> {
+    if !io::stdin().is_terminal() {
+        bail!("live needs an interactive terminal");
     }
+    terminal::try_on(color_scheme, name)
+}
+
+#[cfg(not(unix))]
+fn try_on_terminal(_color_scheme: &ColorScheme, _name: &str) -> Result<()> {
+    bail!("live is not supported on this platform");
+}
+
+#[derive(Args)]
+struct GalleryArgs {
+    #[command(flatten)]
+    provider: ProviderOption,
+    /// HTML file to write into instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<String>,
+}
+
+async fn gallery(args: GalleryArgs) -> Result<()> {
+    let provider = args.provider.get();
+
+    let mut color_schemes = provider.list().await?;
+    color_schemes.sort_by_key(|(name, _)| name.to_lowercase());
+    let title = format!("colortty gallery: {}", provider.repository_url());
+    let html = gallery::to_html(&title, &color_schemes);
+
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, html).with_context(|| format!("Failed to write: {}", path))?;
+            info!("Wrote {} color schemes into {}", color_schemes.len(), path);
+        }
+        None => print!("{}", html),
+    }
+
+    Ok(())
+}
+
+#[derive(Args)]
+struct PickArgs {
+    /// show only color schemes whose names contain the query
+    query: Option<String>,
+    #[command(flatten)]
+    provider: ProviderOption,
+    #[command(flatten)]
+    output_format: OutputFormatOption,
+    /// print only the name of the picked color scheme
+    #[arg(long)]
+    print: bool,
+    /// import the picked color scheme in the alacritty config instead of printing it
+    #[arg(long)]
+    apply: bool,
+    /// don't recolor the terminal with the highlighted color scheme while picking
+    #[arg(long)]
+    no_live: bool,
+}
+
+async fn pick(args: PickArgs) -> Result<()> {
+    let format = &args.output_format.output_format;
+
+    let provider = args.provider.get();
+    let mut color_schemes = provider.list().await?;
+    color_schemes.sort_by(|(a, _), (b, _)| a.cmp(b));
+    if let Some(query) = &args.query {
+        let query = query.to_lowercase();
+        color_schemes.retain(|(name, _)| name.to_lowercase().contains(&query));
+    }
+    if color_schemes.is_empty() {
+        bail!("There are no color schemes to pick from");
+    }
+
+    let (name, color_scheme) = match pick_interactively(&color_schemes, !args.no_live)? {
+        Some(picked) => picked,
+        None => return Ok(()),
+    };
+
+    if args.print {
+        println!("{}", name);
+    } else if args.apply {
+        let config_path = alacritty::config_path().ok_or(anyhow!("There is no home directory"))?;
+        write_alacritty_theme(&provider, name, color_scheme, &config_path, false)?;
+    } else {
+        print!("{}", get_output(&provider, name, color_scheme, format));
+    }
+
+    Ok(())
+}
+
+/// Picks a color scheme with the full-screen picker if the terminal is interactive, or with a
+/// numbered list otherwise. Returns `None` if the picker was cancelled.
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn pick_interactively(
+    color_schemes: &[(String, ColorScheme)],
+    live: bool,
+) -> Result<Option<&(String, ColorScheme)>> {
+    #[cfg(unix)]
+    {
+        if io::stdin().is_terminal() && io::stderr().is_terminal() {
+            let picked = picker::run(color_schemes, live)?;
+            return Ok(picked.map(|index| &color_schemes[index]));
+        }
+    }
+    choose(color_schemes).map(Some)
+}
+
+#[derive(Args)]
+struct FromImageArgs {
+    /// image file to extract colors from
+    #[arg(required_unless_present = "screenshot")]
+    file: Option<String>,
+    /// screenshot of a terminal to extract colors from, implying --as-terminal
+    #[arg(long, value_name = "FILE", conflicts_with = "file")]
+    screenshot: Option<String>,
+    /// treat the image as a screenshot of a terminal
+    #[arg(long)]
+    as_terminal: bool,
+    #[command(flatten)]
+    output_format: OutputFormatOption,
+}
+
+fn from_image(args: FromImageArgs) -> Result<()> {
+    let as_terminal = args.as_terminal || args.screenshot.is_some();
+    let source = args.screenshot.or(args.file).unwrap_or_default();
+    let image = image::open(&source)
+        .with_context(|| format!("Failed to read image: {}", source))?
+        .to_rgb8();
+    let pixels: Vec<Color> = image
+        .pixels()
+        .map(|pixel| Color::from_rgb8(pixel[0], pixel[1], pixel[2]))
+        .collect();
+
+    let color_scheme = if as_terminal {
+        extract::from_terminal_screenshot(&pixels)
+    } else {
+        extract::from_picture(&pixels)
+    }
+    .ok_or(anyhow!("Image has no pixels: {}", source))?;
+    print!(
+        "{}",
+        color_scheme.serialize(&args.output_format.output_format, &source_name(&source))
+    );
 
     Ok(())
 }
 
-async fn get(args: Vec<String>) -> Result<()> {
-    let mut opts = Options::new();
-    set_provider_option(&mut opts);
-    let matches = opts
-        .parse(&args[2..])
-        .context("Failed to parse arguments")?;
+#[derive(Args)]
+struct ManpageArgs {
+    /// command to print the page of instead of colortty
+    command: Option<String>,
+    /// write pages of colortty and all commands into the directory
+    #[arg(long, value_name = "DIR")]
+    out_dir: Option<String>,
+}
+
+fn manpage(args: ManpageArgs) -> Result<()> {
+    let version = format!("colortty {}", env!("CARGO_PKG_VERSION"));
+    let mut cli = Cli::command();
+    cli.build();
 
-    if matches.free.is_empty() {
-        bail!("Color scheme name is missing");
+    if let Some(out_dir) = args.out_dir {
+        std::fs::create_dir_all(&out_dir)
+            .with_context(|| format!("Failed to create: {}", out_dir))?;
+        let pages = std::iter::once(main_page(&cli)).chain(cli.get_subcommands().map(command_page));
+        for page in pages {
+            let path = Path::new(&out_dir).join(format!("{}.1", page.name));
+            std::fs::write(&path, page.to_troff(&version))
+                .with_context(|| format!("Failed to write: {}", path.display()))?;
+            info!("Wrote {}", path.display());
+        }
+        return Ok(());
     }
-    let name = &matches.free[0].to_string();
 
-    let provider = get_provider(&matches)?;
-    let color_scheme = provider.get(name).await?;
-    print!("# {}\n{}", name, color_scheme.to_yaml());
+    let page = match args.command {
+        None => main_page(&cli),
+        Some(name) => command_page(
+            cli.find_subcommand(&name)
+                .ok_or_else(|| unknown_command(&name, &cli))?,
+        ),
+    };
+    print!("{}", page.to_troff(&version));
 
     Ok(())
 }
 
+fn main_page(cli: &clap::Command) -> ManPage {
+    ManPage {
+        name: "colortty".to_owned(),
+        summary: about(cli),
+        command: "colortty".to_owned(),
+        synopsis: vec![
+            "[--error-format text|json] [--jobs N] [--github-token TOKEN] [-v|-q] COMMAND [OPTIONS] [ARGS]".to_owned(),
+        ],
+        options: option_rows(cli, |arg| arg.is_global_set()),
+        sections: vec![
+            (
+                "Commands".to_owned(),
+                cli.get_subcommands()
+                    .map(|command| format!("{}  {}", command.get_name(), about(command)))
+                    .collect(),
+            ),
+            (
+                "Exit status".to_owned(),
+                vec![
+                    "0  success".to_owned(),
+                    "1  failure, including problems found by checks like lint and a11y".to_owned(),
+                    "2  invalid usage".to_owned(),
+                    "3  color scheme or file not found".to_owned(),
+                    "4  network error".to_owned(),
+                    "5  parse error".to_owned(),
+                ],
+            ),
+        ],
+        see_also: cli
+            .get_subcommands()
+            .map(|command| format!("colortty-{}", command.get_name()))
+            .collect(),
+    }
+}
+
+fn command_page(command: &clap::Command) -> ManPage {
+    let name = command.get_name();
+    // Commands with actions like `cache clear` have a usage per action, and the options of all of
+    // them.
+    let mut commands: Vec<&clap::Command> = command.get_subcommands().collect();
+    if commands.is_empty() {
+        commands.push(command);
+    }
+    let prefix = format!("Usage: colortty {} ", name);
+    let synopsis = commands
+        .iter()
+        .map(|command| {
+            let usage = (*command).clone().render_usage().to_string();
+            usage.strip_prefix(&prefix).unwrap_or(&usage).to_owned()
+        })
+        .collect();
+    let mut options: Vec<(String, String)> = Vec::new();
+    for command in &commands {
+        for row in option_rows(command, |arg| !arg.is_global_set()) {
+            if !options.contains(&row) {
+                options.push(row);
+            }
+        }
+    }
+
+    ManPage {
+        name: format!("colortty-{}", name),
+        summary: about(command),
+        command: format!("colortty {}", name),
+        synopsis,
+        options,
+        sections: vec![],
+        see_also: vec!["colortty".to_owned()],
+    }
+}
+
+fn about(command: &clap::Command) -> String {
+    command
+        .get_about()
+        .map(|about| about.to_string())
+        .unwrap_or_default()
+}
+
+/// Options of a command as headings like `-i, --input-format INPUT_FORMAT` and descriptions with
+/// the same details as `--help`.
+fn option_rows(
+    command: &clap::Command,
+    filter: impl Fn(&clap::Arg) -> bool,
+) -> Vec<(String, String)> {
+    command
+        .get_arguments()
+        .filter(|arg| !arg.is_positional() && !arg.is_hide_set() && arg.get_id() != "help")
+        .filter(|arg| filter(arg))
+        .map(|arg| {
+            let mut flags: Vec<String> = Vec::new();
+            flags.extend(arg.get_short().map(|short| format!("-{}", short)));
+            flags.extend(arg.get_long().map(|long| format!("--{}", long)));
+            for short in arg.get_visible_short_aliases().unwrap_or_default() {
+                flags.push(format!("-{}", short));
+            }
+            for long in arg.get_visible_aliases().unwrap_or_default() {
+                flags.push(format!("--{}", long));
+            }
+            let mut heading = flags.join(", ");
+            if arg.get_action().takes_values() {
+                for value_name in arg.get_value_names().unwrap_or_default() {
+                    heading.push_str(&format!(" {}", value_name));
+                }
+            }
+
+            let mut description = arg
+                .get_help()
+                .map(|help| help.to_string())
+                .unwrap_or_default();
+            let defaults: Vec<String> = arg
+                .get_default_values()
+                .iter()
+                .map(|value| value.to_string_lossy().into_owned())
+                .collect();
+            if !defaults.is_empty() && arg.get_action().takes_values() {
+                description.push_str(&format!(" [default: {}]", defaults.join(", ")));
+            }
+            let possible_values: Vec<String> = arg
+                .get_possible_values()
+                .iter()
+                .filter(|value| !value.is_hide_set())
+                .map(|value| value.get_name().to_owned())
+                .collect();
+            if !possible_values.is_empty() && arg.get_action().takes_values() {
+                description.push_str(&format!(
+                    " [possible values: {}]",
+                    possible_values.join(", ")
+                ));
+            }
+            (heading, description)
+        })
+        .collect()
+}
+
+#[derive(Args)]
+struct HelpArgs {
+    /// command to show the options of, like `get` or `cache clear`
+    command: Vec<String>,
+}
+
 fn help() {
     println!(
         "colortty - color scheme converter for alacritty
@@ -163,47 +2452,714 @@ USAGE:
     colortty list -p gogh
     colortty list -p gogh -u # update cached color schemes
 
+    # List color schemes without near-duplicates
+    colortty list --unique
+
+    # List only names of color schemes, for scripts
+    colortty list --names-only
+    colortty list | grep -i solarized # names only when stdout is not a terminal
+    colortty list --force-color | less -R # previews even in a pipe
+
+    # List color schemes from light to dark backgrounds, or grouped by the hue of their tint
+    colortty list --sort background-luminance
+    colortty list --sort hue
+
+    # List color schemes in a compact grid that fits the terminal, or in a given number of columns
+    colortty list --columns auto
+    colortty list --columns 3
+
+    # List color schemes with a dark or light background
+    colortty list --dark
+    colortty list --light
+
+    # List high-contrast color schemes
+    colortty list --min-contrast 7
+    colortty list --sort contrast
+
+    # Tag color schemes and list them by tags
+    colortty tag add Dracula purple work
+    colortty tag remove Dracula work
+    colortty tag list # all tags
+    colortty tag list Dracula # including dark, light and high-contrast from analysis
+    colortty list --tag dark --tag high-contrast
+
+    # Convert all color schemes of a provider into a directory, like a theme pack
+    colortty export-all -p iterm -o kitty --out-dir ./kitty-themes
+
+    # Update cached color schemes of all providers, downloading only changed files
+    colortty update
+
+    # Show or clear cached color schemes
+    colortty cache path
+    colortty cache status
+    colortty cache clear -p gogh
+    colortty cache clear --all
+
+    # Find cached color scheme files that fail to parse
+    colortty prune
+    colortty prune --delete # delete them
+    colortty prune -p gogh --redownload # download them again
+
+    # Find color schemes that look the same across providers
+    colortty dedupe
+    colortty dedupe -t 5 # allow larger color differences
+
     # Get color scheme from https://github.com/mbadolato/iTerm2-Color-Schemes
     colortty get <color scheme name>
     colortty get -p iterm <color scheme name>
 
+    # Get color scheme by a part of its name, choosing from cached color schemes
+    colortty get solarized
+    colortty get --exact 'Solarized Dark' # don't match partially
+    colortty get dracula # 'Dracula' rather than 'Dracula+' for a name that only differs in case
+    colortty get dracla # suggests cached color schemes with similar names if not found
+
+    # Output in other formats (default: alacritty TOML)
+    colortty get -o yaml Dracula # alacritty before 0.13
+    colortty get -o kitty Dracula
+
+    # Save color scheme into a file named after it, like Dracula.toml
+    colortty get -t toml --write Dracula
+    colortty get -t toml --write --dir ~/.config/alacritty/themes Dracula
+    colortty get -O ~/.config/alacritty/themes/ Dracula # writes Dracula.toml there
+    colortty get -t kitty -O ~/.config/kitty/current-theme.conf Dracula
+
+    # Get several color schemes at once, in one output or a file each
+    colortty get Dracula Nord \"Gruvbox Dark\"
+    colortty get --out-dir ~/.config/alacritty/themes Dracula Nord \"Gruvbox Dark\"
+    colortty convert -O themes/ some-color.itermcolors # writes themes/some-color.toml
+
+    # Get color scheme for kitty
+    colortty get -t kitty Dracula > ~/.config/kitty/current-theme.conf
+
+    # Recolor the running terminal without editing its config
+    colortty get -t osc Dracula > /dev/tty
+
+    # Write color scheme into themes next to the alacritty config and import it there
+    colortty apply Dracula
+    colortty apply --backup --config ~/dotfiles/alacritty.toml Dracula
+
+    # Write color scheme into alacritty's themes directory and print the line to import it
+    colortty install Dracula
+    colortty install --dir ~/dotfiles/alacritty/themes Dracula
+    colortty install --import Dracula # import it in the alacritty config too
+
+    # Switch color schemes when macOS or GNOME switches between dark and light
+    colortty watch --dark Dracula --light \"Solarized Light\"
+    colortty watch --dark Dracula --light \"Solarized Light\" --osc # recolor the terminal instead
+
+    # Also output selection, search and hint colors derived from the palette
+    colortty get --full-config Dracula
+    colortty convert --full-config some-color.itermcolors
+
     # Get color scheme from https://github.com/Mayccoll/Gogh
     colortty get -p gogh <color scheme name>
 
+    # Show a random color scheme, and print or apply it
+    colortty random
+    colortty random -p gogh --dark
+    colortty random --light --print > random.toml
+    colortty random --apply
+
+    # Search color schemes of all providers by name
+    colortty search drac
+    colortty search --fuzzy gbdark # 'gruvbox-dark' and the like, best matches first
+    colortty search -p gogh --names-only nord
+
+    # Pick a color scheme in a full-screen list: type to filter, arrow keys to move, enter to print it
+    colortty pick
+    colortty pick dracula # only color schemes whose names contain 'dracula'
+    colortty pick --print # print only the name for scripts
+    colortty pick --apply # import the picked color scheme in the alacritty config
+    colortty pick --no-live # don't recolor the terminal while moving through the list
+    colortty list -i --tag dark # pick one of the listed color schemes
+
+    # Show the fingerprint of a color scheme to tell whether two files are the same theme
+    colortty show <color scheme name>
+    colortty show some-color.itermcolors
+
+    # Show the color scheme that was last applied to each terminal by get --write, apply, install --import and so on
+    colortty status
+    colortty status -f json
+
+    # Show palette statistics of a color scheme
+    colortty stats <color scheme name>
+    colortty stats some-color.itermcolors
+    colortty stats -a # summarize all cached color schemes
+    colortty stats -p gogh -a
+
+    # Print every color of a color scheme in hex, rgb(), hsl() and the nearest xterm 256 color
+    colortty info <color scheme name>
+    colortty info some-color.itermcolors
+
+    # Check accessibility of a color scheme
+    colortty a11y <color scheme name>
+    colortty a11y some-color.itermcolors
+
+    # Check contrast ratios of the foreground, ANSI colors and cursor text (WCAG)
+    colortty check <color scheme name>
+    colortty check --min-text 7 --min-ansi 4.5 some-color.itermcolors
+
+    # Find common mistakes in color schemes
+    colortty lint some-color.itermcolors other-color.itermcolors
+    colortty lint -f json some-color.itermcolors # machine-readable output
+
+    # Render a sample screen with a shell session, code, text attributes and all 16 colors
+    colortty preview Dracula
+
+    # Compare color schemes side by side
+    colortty preview <color scheme name> <color scheme name> ...
+    colortty preview some-color.itermcolors other-color.itermcolors
+
+    # Try a color scheme in the current terminal and restore the colors on a keypress
+    colortty live Dracula
+
+    # Browse color schemes of a provider in a web browser
+    colortty gallery -o gallery.html
+    colortty gallery -p gogh -o gogh.html
+
+    # Create a color scheme from the colors of an image
+    colortty from-image wallpaper.png
+    colortty from-image --screenshot shot.png # recreate a terminal's colors
+    colortty from-image -o kitty wallpaper.png
+
     # Convert with implicit input type
     colortty convert some-color.itermcolors
     colortty convert some-color.minttyrc
     colortty convert some-color.sh
+    colortty convert some-color.yml # Gogh
+    colortty convert some-color.Xresources # URxvt, XTerm and so on
+    colortty convert some-color.colorscheme # Konsole
+    colortty convert some-color.toml # alacritty or WezTerm
+    colortty convert putty-sessions.reg -a # PuTTY sessions exported with regedit
+    colortty convert Basic.terminal # Terminal.app
+    colortty convert some-color.theme # xfce4-terminal
+    colortty convert ~/.config/foot/foot.ini
+    colortty convert -i ghostty ~/.config/ghostty/themes/some-theme
+    colortty convert ~/.termux/colors.properties
+    colortty convert st/config.h
+    colortty convert ~/.hyper.js
+    colortty convert -i hyper node_modules/hyper-dracula/index.js
+    colortty convert ~/.config/rio/themes/dracula.toml
+    dconf dump /org/gnome/terminal/ | colortty convert -i dconf -a - # GNOME Terminal
+
+    # Convert a color scheme file on the web, guessing the format from the URL
+    colortty convert https://raw.githubusercontent.com/mbadolato/iTerm2-Color-Schemes/master/schemes/Dracula.itermcolors
+    dconf dump /com/gexperts/Tilix/ | colortty convert -i dconf -a - # Tilix
 
     # Convert with explicit input type
     colortty convert -i iterm some-color-theme
     colortty convert -i mintty some-color-theme
     colortty convert -i gogh some-color-theme
+    colortty convert -i windows-terminal some-scheme.json
+    colortty convert -i vscode settings.json # terminal colors in workbench.colorCustomizations
+    colortty convert -i vscode some-theme-color-theme.json
+
+    # Migrate colors of an alacritty YAML config to TOML for alacritty 0.13+
+    colortty convert -t toml ~/.alacritty.yml
+    colortty convert -i alacritty-yaml -t toml old-config.yml
+
+    # Convert alacritty themes into other formats
+    colortty convert -t rofi dracula.toml
+    colortty convert -i alacritty-toml -t i3 ~/.config/alacritty/alacritty.toml
+
+    # Convert multiple files at once, separating outputs by comments with their sources
+    colortty convert -t toml some-color.itermcolors other-color.minttyrc
+
+    # Convert palettes, assigning colors to the slots by their names or hues
+    colortty convert brand.gpl
+    colortty convert brand.ase
+
+    # Convert many files into a file each, keeping the directory structure of directories
+    colortty convert --to toml --out-dir dist/ themes/
+    colortty convert --to kitty --out-dir kitty/ *.itermcolors
+
+    # Convert files with multiple color schemes
+    colortty convert -l settings.json # list color schemes
+    colortty convert -n 'One Half Dark' settings.json
+    colortty convert -a settings.json
+    colortty convert -a -i iterm-dynamic-profiles profiles.json
+    colortty convert -a .Xresources
+
+    # Convert into configs of desktop apps to match the terminal
+    colortty convert -t i3 some-color.itermcolors # also for sway
+    colortty convert -t polybar some-color.itermcolors
+    colortty convert -t waybar some-color.itermcolors
+    colortty convert -t rofi some-color.itermcolors > colors.rasi
+    colortty convert -t dunst some-color.itermcolors
+    colortty convert -t mako some-color.itermcolors
+    colortty convert -t tmux some-color.itermcolors > ~/.config/tmux/colors.conf
+    colortty convert -t fzf some-color.itermcolors >> ~/.bashrc
+    colortty convert -t jetbrains some-color.itermcolors > some-color.icls
+    colortty convert -t vscode some-color.itermcolors # merge into settings.json of VS Code
+    colortty convert -t neovim some-color.itermcolors > ~/.config/nvim/lua/terminal_colors.lua
+    colortty get -t base16 --write Dracula # for base16 templates
+    colortty convert -t cmd some-color.itermcolors > some-color.reg # for cmd.exe
+
+    # Convert into themes of other terminals
+    colortty convert -t kitty some-color.itermcolors > some-color.conf
+    colortty convert -t windows-terminal some-color.itermcolors # paste into settings.json
+    colortty convert -t wezterm some-color.itermcolors > ~/.config/wezterm/colors/some-color.toml
+    colortty convert -t foot some-color.itermcolors # paste into foot.ini
+    colortty convert -o ghostty some-color.itermcolors > ~/.config/ghostty/themes/some-color
+    colortty get -t konsole --write --dir ~/.local/share/konsole Dracula
+    colortty convert -t iterm some-color.minttyrc > some-color.itermcolors
+    colortty convert -t gogh some-color.itermcolors > some-color.sh # to contribute to Gogh
+    colortty get -t mintty Dracula >> ~/.minttyrc # also for wsltty
 
     # Convert stdin (explicit input type is necessary)
     cat some-color-theme | colortty convert -i iterm -
     cat some-color-theme | colortty convert -i mintty -
-    cat some-color-theme | colortty convert -i gogh -"
+    cat some-color-theme | colortty convert -i gogh -
+
+    # Show the options of a command
+    colortty get --help
+    colortty help get
+    colortty cache clear --help # actions of cache and tag have their own options
+
+    # Print man pages generated from the options of the commands
+    colortty manpage > colortty.1
+    colortty manpage convert > colortty-convert.1
+    colortty manpage --out-dir man/
+
+    # Report errors as JSON with the kind and the exit code, for scripts and editor plugins
+    colortty --error-format json get NoSuchScheme
+    # {{\"error\":{{\"kind\":\"not-found\",\"exit_code\":3,\"message\":\"...\",\"causes\":[...]}}}}
+
+OPTIONS:
+    --error-format text|json   print errors as text (default) or as JSON on stderr
+    --jobs N                   number of parallel downloads, cache reads and conversions
+                               (default: $COLORTTY_JOBS, jobs = N in
+                               ~/.config/colortty/config.toml or 8)
+    --github-token TOKEN       token to send to GitHub to avoid its rate limit of 60 requests
+                               per hour (default: $GITHUB_TOKEN)
+    -v, --verbose              also print HTTP requests, cache hits and misses and parse timing
+    -q, --quiet                don't print progress like downloads and written files
+
+EXIT CODES:
+    0   success
+    1   failure, including problems found by checks like lint and a11y
+    2   invalid usage
+    3   color scheme or file not found
+    4   network error
+    5   parse error"
     );
 }
 
 // -- Utility functions
 
-fn set_provider_option(opts: &mut getopts::Options) {
-    opts.optopt(
-        "p",
-        "provider",
-        "color scheme provider: 'iterm'|'gogh'",
-        "PROVIDER",
-    );
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum ProviderName {
+    Iterm,
+    Gogh,
+}
+
+impl ProviderName {
+    fn provider(self) -> Provider {
+        match self {
+            ProviderName::Iterm => Provider::iterm(),
+            ProviderName::Gogh => Provider::gogh(),
+        }
+    }
+}
+
+#[derive(Args)]
+struct ProviderOption {
+    /// color scheme provider
+    #[arg(short, long, value_enum, value_name = "PROVIDER", default_value_t = ProviderName::Iterm)]
+    provider: ProviderName,
+}
+
+impl ProviderOption {
+    fn get(&self) -> Provider {
+        self.provider.provider()
+    }
+}
+
+/// A provider for commands that work on all providers without `--provider`.
+#[derive(Args)]
+struct ProvidersOption {
+    /// color scheme provider (default: all of them)
+    #[arg(short, long, value_enum, value_name = "PROVIDER")]
+    provider: Option<ProviderName>,
+}
+
+impl ProvidersOption {
+    fn get(&self) -> Option<Provider> {
+        self.provider.map(ProviderName::provider)
+    }
+
+    fn get_all(&self) -> Vec<Provider> {
+        match self.get() {
+            Some(provider) => vec![provider],
+            None => Provider::all(),
+        }
+    }
 }
 
-fn get_provider(matches: &getopts::Matches) -> Result<Provider> {
-    let provider_name = matches.opt_str("p").unwrap_or_else(|| "iterm".to_owned());
-    let provider = match provider_name.as_ref() {
-        "iterm" => Provider::iterm(),
-        "gogh" => Provider::gogh(),
-        _ => bail!("Unknown color scheme provider: {}", provider_name),
+#[derive(Args)]
+struct FullConfigOption {
+    /// also output selection, search and hint colors, deriving missing ones from the palette
+    #[arg(long = "full-config")]
+    enabled: bool,
+}
+
+/// Fetches a color scheme from a provider, suggesting cached color schemes with similar names if
+/// it doesn't exist.
+async fn get_color_scheme(provider: &Provider, name: &str) -> Result<ColorScheme> {
+    let error = match provider.get(name).await {
+        Ok(color_scheme) => return Ok(color_scheme),
+        Err(error) => error,
+    };
+    if ErrorKind::of(&error) != ErrorKind::NotFound {
+        return Err(error);
+    }
+    let names: Vec<String> = provider
+        .cached()
+        .await
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    let similar = suggest::similar_names(name, &names, 5);
+    if similar.is_empty() {
+        return Err(error);
+    }
+    Err(error.context(NotFoundError(format!(
+        "Color scheme is not found: {}. Did you mean: {}?",
+        name,
+        similar.join(", ")
+    ))))
+}
+
+/// Finds the name of a cached color scheme that matches a partial name case-insensitively.
+///
+/// Returns the name as is if there is an exact match or no matches. When there are multiple
+/// matches, picks the one that only differs in case if any, or asks which one to use on a terminal,
+/// and fails otherwise.
+async fn resolve_name(provider: &Provider, name: &str) -> Result<String> {
+    // Only the names are needed to find matches, so parse just the ones to choose from.
+    let mut candidates = provider.cached_names().await.unwrap_or_default();
+    if candidates.iter().any(|candidate| candidate == name) {
+        return Ok(name.to_owned());
+    }
+    let query = name.to_lowercase();
+    candidates.retain(|candidate| candidate.to_lowercase().contains(&query));
+    candidates.sort();
+    // Prefer a name that only differs in case, like `Dracula` for `dracula` over `Dracula+`.
+    let same_names: Vec<&String> = candidates
+        .iter()
+        .filter(|candidate| candidate.to_lowercase() == query)
+        .collect();
+    if let [same_name] = same_names.as_slice() {
+        return Ok(same_name.to_string());
+    }
+
+    match candidates.len() {
+        0 => Ok(name.to_owned()),
+        1 => Ok(candidates.remove(0)),
+        _ => {
+            let results: Vec<Result<(String, ColorScheme)>> = stream::iter(
+                candidates
+                    .iter()
+                    .map(|candidate| provider.read_color_scheme(candidate.clone())),
+            )
+            .buffered(jobs::get())
+            .collect()
+            .await;
+            let mut readable = Vec::new();
+            let mut errors = Vec::new();
+            for (candidate, result) in candidates.into_iter().zip(results) {
+                match result {
+                    Ok(color_scheme) => readable.push(color_scheme),
+                    Err(e) => errors.push((candidate, e)),
+                }
+            }
+            let mut candidates = readable;
+            // Broken files are left out of the choices, but not silently.
+            if candidates.is_empty() {
+                let (candidate, e) = errors.remove(0);
+                return Err(e.context(format!(
+                    "Failed to read the cached color scheme {} of {}",
+                    candidate,
+                    provider.name()
+                )));
+            }
+            for (candidate, e) in &errors {
+                eprintln!(
+                    "warning: {}: {}: {:#} (run `colortty prune` to fix broken files)",
+                    provider.name(),
+                    candidate,
+                    e
+                );
+            }
+            if candidates.len() == 1 {
+                return Ok(candidates.remove(0).0);
+            }
+            if !io::stdin().is_terminal() {
+                for (candidate, color_scheme) in &candidates {
+                    eprintln!("{} {}", candidate, color_scheme.to_preview());
+                }
+                bail!(UsageError(format!(
+                    "{} matches {} color schemes. Use one of the names above or --exact",
+                    name,
+                    candidates.len()
+                )));
+            }
+            let (candidate, _) = choose(&candidates)?;
+            Ok(candidate.to_owned())
+        }
+    }
+}
+
+/// Asks which color scheme to use, showing the choices on stderr so that only the result goes
+/// to stdout.
+fn choose(color_schemes: &[(String, ColorScheme)]) -> Result<&(String, ColorScheme)> {
+    let max_name_length = color_schemes
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    for (index, (name, color_scheme)) in color_schemes.iter().enumerate() {
+        eprintln!(
+            "{:>4} {:width$} {}",
+            index + 1,
+            name,
+            color_scheme.to_preview(),
+            width = max_name_length
+        );
+    }
+    eprint!("Pick a color scheme [1-{}]: ", color_schemes.len());
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .context("Failed to read stdin")?;
+    let answer = answer.trim();
+    if answer.is_empty() {
+        bail!("No color scheme was picked");
+    }
+    answer
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| color_schemes.get(number.wrapping_sub(1)))
+        .or_else(|| color_schemes.iter().find(|(name, _)| name == answer))
+        .ok_or_else(|| UsageError(format!("No such choice: {}", answer)).into())
+}
+
+/// Formats a color scheme from a provider for alacritty with where it came from.
+fn get_output(
+    provider: &Provider,
+    name: &str,
+    color_scheme: &ColorScheme,
+    format: &OutputFormat,
+) -> String {
+    let comments = [
+        name.to_owned(),
+        format!("Provider: {}", provider.name()),
+        format!("Repository: {}", provider.repository_url()),
+        format!("Path: {}", provider.source_path(name)),
+        format!("Fingerprint: {}", color_scheme.fingerprint()),
+    ];
+    let mut output = String::new();
+    for comment in comments.iter().filter_map(|text| format.comment(text)) {
+        output.push_str(&comment);
+        output.push('\n');
+    }
+    output.push_str(&color_scheme.serialize(format, name));
+    output
+}
+
+/// The terminal that reads files of the output format, which is recorded as the target of the
+/// applied color scheme.
+fn terminal_of(format: &OutputFormat) -> Option<&'static str> {
+    match format {
+        OutputFormat::Alacritty(_) => Some("alacritty"),
+        OutputFormat::Kitty => Some("kitty"),
+        OutputFormat::WezTerm => Some("wezterm"),
+        OutputFormat::Foot => Some("foot"),
+        OutputFormat::Ghostty => Some("ghostty"),
+        OutputFormat::Konsole => Some("konsole"),
+        OutputFormat::Mintty => Some("mintty"),
+        _ => None,
+    }
+}
+
+/// Replaces characters that are not allowed in file names on some platforms.
+fn sanitize_filename(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    // Leading dots would make hidden files.
+    sanitized.trim().trim_start_matches('.').to_owned()
+}
+
+fn remove_duplicates(color_schemes: Vec<(String, ColorScheme)>) -> Vec<(String, ColorScheme)> {
+    let schemes: Vec<_> = color_schemes.iter().map(|(_, scheme)| scheme).collect();
+    let firsts: Vec<usize> = dedupe::group_duplicates(&schemes, dedupe::DEFAULT_THRESHOLD)
+        .iter()
+        .map(|group| group[0])
+        .collect();
+    color_schemes
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| firsts.contains(index))
+        .map(|(_, entry)| entry)
+        .collect()
+}
+
+#[derive(Args)]
+struct InputFormatOption {
+    /// input format
+    #[arg(
+        short,
+        long,
+        value_name = "INPUT_FORMAT",
+        value_parser = PossibleValuesParser::new(ColorSchemeFormat::NAMES)
+            .map(|name| ColorSchemeFormat::from_string(&name).unwrap()),
+    )]
+    input_format: Option<ColorSchemeFormat>,
+}
+
+#[derive(Args)]
+struct OutputOptions {
+    /// file to write into instead of stdout, or directory to write <name>.<extension> into
+    #[arg(short = 'O', long, value_name = "PATH")]
+    output: Option<String>,
+    /// directory to write <name>.<extension> into
+    #[arg(long, value_name = "DIR")]
+    output_dir: Option<String>,
+}
+
+#[derive(Args)]
+struct OutputFormatOption {
+    /// output format
+    #[arg(
+        short,
+        long,
+        visible_short_alias = 't',
+        visible_alias = "to",
+        value_name = "OUTPUT_FORMAT",
+        default_value = "toml",
+        value_parser = output_format_parser(),
+    )]
+    output_format: OutputFormat,
+}
+
+/// Parses the names of output formats, also taking aliases like `nvim` for `neovim` without
+/// listing them.
+fn output_format_parser() -> impl TypedValueParser<Value = OutputFormat> {
+    let aliases = ["sway", "wsltty", "nvim"]
+        .iter()
+        .map(|name| PossibleValue::new(*name).hide(true));
+    let names = OutputFormat::NAMES
+        .iter()
+        .map(|name| PossibleValue::new(*name));
+    PossibleValuesParser::new(names.chain(aliases))
+        .map(|name| OutputFormat::from_string(&name).unwrap())
+}
+
+/// The provider and the input format of a color scheme that is given by name or file.
+#[derive(Args)]
+struct SourceOptions {
+    #[command(flatten)]
+    provider: ProviderOption,
+    #[command(flatten)]
+    input_format: InputFormatOption,
+}
+
+/// Reads a color scheme from a file if `source` is an existing file, or fetches it from the
+/// provider otherwise.
+async fn load_color_scheme(source: &str, options: &SourceOptions) -> Result<ColorScheme> {
+    if !Path::new(source).is_file() {
+        let provider = options.provider.get();
+        return get_color_scheme(&provider, source).await;
+    }
+
+    let content = std::fs::read(source).with_context(|| format!("Failed to read: {}", source))?;
+    let format = options
+        .input_format
+        .input_format
+        .clone()
+        .or_else(|| ColorSchemeFormat::from_filename(source))
+        .or_else(|| ColorSchemeFormat::from_content(&String::from_utf8_lossy(&content)))
+        .ok_or_else(|| {
+            UsageError(
+                "Input format is not specified and failed to guess from the source file name"
+                    .to_owned(),
+            )
+        })?;
+    ColorScheme::parse_bytes(&content, &format)
+}
+
+/// Describes how long ago something happened, like "3 days ago".
+fn format_age(age: Duration) -> String {
+    let minutes = age.as_secs() / 60;
+    let (count, unit) = if minutes < 1 {
+        return "just now".to_owned();
+    } else if minutes < 60 {
+        (minutes, "minute")
+    } else if minutes < 60 * 24 {
+        (minutes / 60, "hour")
+    } else {
+        (minutes / (60 * 24), "day")
     };
-    Ok(provider)
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{} {}{} ago", count, unit, plural)
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// The width of the terminal, or 80 columns if stdout is not a terminal.
+fn terminal_width() -> usize {
+    match terminal_size::terminal_size() {
+        Some((terminal_size::Width(width), _)) => width as usize,
+        None => 80,
+    }
+}
+
+fn contrast_ratio(color_scheme: &ColorScheme) -> f64 {
+    color_scheme
+        .foreground()
+        .contrast_ratio(color_scheme.background())
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SortOrder {
+    Name,
+    Contrast,
+    BackgroundLuminance,
+    Hue,
+}
+
+fn sort_color_schemes(color_schemes: &mut [(String, ColorScheme)], order: SortOrder) {
+    match order {
+        SortOrder::Name => color_schemes.sort_by(|(a, _), (b, _)| a.cmp(b)),
+        // Highest contrast first.
+        SortOrder::Contrast => {
+            color_schemes.sort_by(|(_, a), (_, b)| contrast_ratio(b).total_cmp(&contrast_ratio(a)))
+        }
+        // Lightest background first.
+        SortOrder::BackgroundLuminance => color_schemes.sort_by(|(_, a), (_, b)| {
+            let luminance = |scheme: &ColorScheme| scheme.background().relative_luminance();
+            luminance(b).total_cmp(&luminance(a))
+        }),
+        // Red, yellow, green, cyan, blue, magenta, and then untinted ones.
+        SortOrder::Hue => color_schemes.sort_by(|(_, a), (_, b)| {
+            let hue = |scheme: &ColorScheme| scheme.dominant_hue().unwrap_or(f64::INFINITY);
+            hue(a).total_cmp(&hue(b))
+        }),
+    }
 }
@@ -0,0 +1,109 @@
+/// A man page of a command in section 1, rendered as troff.
+pub struct ManPage {
+    /// The name of the page, like `colortty-convert`.
+    pub name: String,
+    /// A one-line description for the NAME section.
+    pub summary: String,
+    /// The command as it's invoked, like `colortty convert`.
+    pub command: String,
+    /// Arguments after the command, one usage per item.
+    pub synopsis: Vec<String>,
+    /// Option headings like `-i, --input-format INPUT_FORMAT` with their descriptions.
+    pub options: Vec<(String, String)>,
+    /// Extra sections with titles and paragraphs. Paragraphs with a tag and text separated by two
+    /// spaces, like `2  invalid usage`, are rendered as tagged paragraphs.
+    pub sections: Vec<(String, Vec<String>)>,
+    /// Names of related pages in section 1.
+    pub see_also: Vec<String>,
+}
+
+impl ManPage {
+    pub fn to_troff(&self, version: &str) -> String {
+        let mut lines = vec![
+            format!(
+                ".TH {} 1 \"\" \"{}\" \"User Commands\"",
+                escape(&self.name.to_uppercase()),
+                escape(version)
+            ),
+            ".SH NAME".to_owned(),
+            format!("{} \\- {}", escape(&self.name), escape(&self.summary)),
+            ".SH SYNOPSIS".to_owned(),
+        ];
+        for (i, usage) in self.synopsis.iter().enumerate() {
+            if i > 0 {
+                lines.push(".br".to_owned());
+            }
+            lines.push(format!(
+                "\\fB{}\\fR {}",
+                escape(&self.command),
+                escape(usage)
+            ));
+        }
+
+        if !self.options.is_empty() {
+            lines.push(".SH OPTIONS".to_owned());
+            for (heading, description) in &self.options {
+                lines.push(".TP".to_owned());
+                lines.push(option_heading(heading));
+                lines.push(escape(description));
+            }
+        }
+
+        for (title, paragraphs) in &self.sections {
+            lines.push(format!(".SH {}", escape(&title.to_uppercase())));
+            for paragraph in paragraphs {
+                match paragraph.split_once("  ") {
+                    Some((tag, text)) => {
+                        lines.push(".TP".to_owned());
+                        lines.push(format!("\\fB{}\\fR", escape(tag)));
+                        lines.push(escape(text.trim()));
+                    }
+                    None => {
+                        lines.push(".PP".to_owned());
+                        lines.push(escape(paragraph));
+                    }
+                }
+            }
+        }
+
+        if !self.see_also.is_empty() {
+            lines.push(".SH SEE ALSO".to_owned());
+            let pages: Vec<String> = self
+                .see_also
+                .iter()
+                .map(|name| format!("\\fB{}\\fR(1)", escape(name)))
+                .collect();
+            lines.push(pages.join(", "));
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// Renders an option heading with flags in bold and value names in italics.
+fn option_heading(heading: &str) -> String {
+    let words: Vec<String> = heading
+        .split_whitespace()
+        .map(|word| {
+            let (word, comma) = match word.strip_suffix(',') {
+                Some(word) => (word, ","),
+                None => (word, ""),
+            };
+            let font = if word.starts_with('-') { "B" } else { "I" };
+            format!("\\f{}{}\\fR{}", font, escape(word), comma)
+        })
+        .collect();
+    words.join(" ")
+}
+
+/// Escapes text so that troff prints it as is.
+pub fn escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    // A leading dot or quote would start a request.
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
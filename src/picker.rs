@@ -0,0 +1,337 @@
+use crate::color::ColorScheme;
+use crate::preview::{self, MIN_SCREEN_WIDTH, PREVIEW_WIDTH, SCREEN_WIDTH};
+#[cfg(unix)]
+use crate::terminal;
+
+/// Names in the list are cut to this many columns.
+const MAX_NAME_WIDTH: usize = 30;
+
+/// The number of columns between the list and the sample screen.
+const GAP: usize = 2;
+
+/// Scores how well a name matches a query, or returns `None` if the name doesn't contain all the
+/// characters of the query in order. Case is ignored.
+///
+/// Consecutive characters and characters at the start of words score higher, and characters
+/// skipped between matches lower the score. An empty query matches everything with zero.
+pub fn fuzzy_score(query: &str, name: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let chars: Vec<char> = name.chars().collect();
+    let lower: Vec<char> = name.to_lowercase().chars().collect();
+    // Lowercasing can change the number of characters, which would misalign word starts.
+    let word_start = |i: usize| {
+        lower.len() == chars.len()
+            && (i == 0
+                || !chars[i - 1].is_alphanumeric()
+                || (chars[i - 1].is_lowercase() && chars[i].is_uppercase()))
+    };
+
+    // Start from a substring match if there is one so that "drac" matches the start of "Dracula"
+    // instead of the "d" of "Andromeda Dracula".
+    let query_string: String = query.iter().collect();
+    let lower_string: String = lower.iter().collect();
+    let mut position = match lower_string.find(&query_string) {
+        Some(byte_index) if !query.is_empty() => lower_string[..byte_index].chars().count(),
+        _ => 0,
+    };
+
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    for c in query {
+        let index = (position..lower.len()).find(|&i| lower[i] == c)?;
+        score += 16;
+        if word_start(index) {
+            score += 8;
+        }
+        match previous {
+            Some(previous) if previous + 1 == index => score += 8,
+            Some(previous) => score -= (index - previous - 1) as i64,
+            None => score -= index as i64,
+        }
+        previous = Some(index);
+        position = index + 1;
+    }
+    Some(score)
+}
+
+/// A key pressed in the picker.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Key {
+    Char(char),
+    Backspace,
+    /// Ctrl-U, which clears the query.
+    ClearQuery,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Enter,
+    /// Escape or Ctrl-C.
+    Cancel,
+}
+
+/// Decodes keys from bytes that a terminal sends in raw mode. Unknown sequences are ignored.
+pub fn parse_keys(input: &[u8]) -> Vec<Key> {
+    let text = String::from_utf8_lossy(input);
+    let mut keys = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let key = match c {
+            '\x1b' => match chars.peek() {
+                Some('[') | Some('O') => {
+                    chars.next();
+                    let mut sequence = String::new();
+                    for c in chars.by_ref() {
+                        sequence.push(c);
+                        if c.is_ascii_alphabetic() || c == '~' {
+                            break;
+                        }
+                    }
+                    match sequence.as_str() {
+                        "A" => Key::Up,
+                        "B" => Key::Down,
+                        "H" | "1~" | "7~" => Key::Home,
+                        "F" | "4~" | "8~" => Key::End,
+                        "5~" => Key::PageUp,
+                        "6~" => Key::PageDown,
+                        _ => continue,
+                    }
+                }
+                _ => Key::Cancel,
+            },
+            '\r' | '\n' => Key::Enter,
+            '\x7f' | '\x08' => Key::Backspace,
+            '\x03' => Key::Cancel,
+            '\x10' => Key::Up,
+            '\x0e' => Key::Down,
+            '\x15' => Key::ClearQuery,
+            c if c.is_control() => continue,
+            c => Key::Char(c),
+        };
+        keys.push(key);
+    }
+    keys
+}
+
+/// What to do after a key is handled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Continue,
+    /// A color scheme was picked, with its index in the given color schemes.
+    Pick(usize),
+    Cancel,
+}
+
+/// The state of the full-screen picker: a query, the color schemes that match it and the
+/// highlighted one.
+pub struct Picker<'a> {
+    schemes: &'a [(String, ColorScheme)],
+    query: String,
+    matches: Vec<usize>,
+    selected: usize,
+    scroll: usize,
+    page: usize,
+}
+
+impl<'a> Picker<'a> {
+    pub fn new(schemes: &'a [(String, ColorScheme)]) -> Self {
+        let mut picker = Picker {
+            schemes,
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            scroll: 0,
+            page: 10,
+        };
+        picker.filter();
+        picker
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Indices of the color schemes that match the query, the best match first.
+    pub fn matches(&self) -> &[usize] {
+        &self.matches
+    }
+
+    /// The index of the highlighted color scheme, if any matches the query.
+    pub fn highlighted(&self) -> Option<usize> {
+        self.matches.get(self.selected).copied()
+    }
+
+    pub fn handle(&mut self, key: Key) -> Action {
+        let last = self.matches.len().saturating_sub(1);
+        match key {
+            Key::Char(c) => {
+                self.query.push(c);
+                self.filter();
+            }
+            Key::Backspace => {
+                self.query.pop();
+                self.filter();
+            }
+            Key::ClearQuery => {
+                self.query.clear();
+                self.filter();
+            }
+            Key::Up => self.selected = self.selected.saturating_sub(1),
+            Key::Down => self.selected = (self.selected + 1).min(last),
+            Key::PageUp => self.selected = self.selected.saturating_sub(self.page),
+            Key::PageDown => self.selected = (self.selected + self.page).min(last),
+            Key::Home => self.selected = 0,
+            Key::End => self.selected = last,
+            Key::Enter => {
+                if let Some(index) = self.highlighted() {
+                    return Action::Pick(index);
+                }
+            }
+            Key::Cancel => return Action::Cancel,
+        }
+        Action::Continue
+    }
+
+    fn filter(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .schemes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (name, _))| {
+                fuzzy_score(&self.query, name).map(|score| (score, index))
+            })
+            .collect();
+        // Stable, so color schemes with the same score stay in the given order.
+        scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.selected = 0;
+        self.scroll = 0;
+    }
+
+    /// Renders the picker in `width` columns and `height` lines: the query, the matching color
+    /// schemes with the highlighted one marked, and a sample screen of the highlighted one on the
+    /// right if there is room for it.
+    pub fn render(&mut self, width: usize, height: usize) -> Vec<String> {
+        let rows = height.saturating_sub(2).max(1);
+        self.page = rows;
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + rows {
+            self.scroll = self.selected + 1 - rows;
+        }
+
+        let name_width = self
+            .schemes
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(MAX_NAME_WIDTH);
+        let list_width = 2 + name_width + 1 + PREVIEW_WIDTH;
+        let screen_width = width.saturating_sub(list_width + GAP).min(SCREEN_WIDTH);
+        let screen = match self.highlighted() {
+            Some(index) if screen_width >= MIN_SCREEN_WIDTH => {
+                let (name, scheme) = &self.schemes[index];
+                preview::sample_screen(scheme, name, screen_width)
+            }
+            _ => Vec::new(),
+        };
+
+        let mut lines = vec![format!(
+            "> {}  \x1b[2m{}/{}\x1b[0m",
+            self.query,
+            self.matches.len(),
+            self.schemes.len()
+        )];
+        for row in 0..rows {
+            let position = self.scroll + row;
+            let mut line = match self.matches.get(position) {
+                Some(&index) => {
+                    let (name, scheme) = &self.schemes[index];
+                    let name: String = name.chars().take(name_width).collect();
+                    let marker = if position == self.selected {
+                        "\x1b[1m> "
+                    } else {
+                        "  "
+                    };
+                    format!(
+                        "{}{:width$}\x1b[0m {}",
+                        marker,
+                        name,
+                        scheme.to_preview(),
+                        width = name_width
+                    )
+                }
+                None => " ".repeat(list_width),
+            };
+            if let Some(screen_line) = screen.get(row) {
+                line.push_str(&" ".repeat(GAP));
+                line.push_str(screen_line);
+            }
+            lines.push(line);
+        }
+        lines.push("\x1b[2m↑/↓ move  enter pick  esc cancel\x1b[0m".to_owned());
+        lines
+    }
+}
+
+/// Runs the picker full-screen on the controlling terminal and returns the index of the picked
+/// color scheme, or `None` if it was cancelled.
+///
+/// With `live`, the highlighted color scheme is applied to the terminal with OSC escape sequences
+/// while it's highlighted, and the colors are reset afterwards.
+#[cfg(unix)]
+pub fn run(schemes: &[(String, ColorScheme)], live: bool) -> anyhow::Result<Option<usize>> {
+    use anyhow::Context;
+    use std::io::{Read, Write};
+
+    let mut tty = terminal::open_tty()?;
+    let mut restore = String::from("\x1b[?25h\x1b[?1049l");
+    if live {
+        restore.push_str("\x1b]104\x1b\\\x1b]110\x1b\\\x1b]111\x1b\\\x1b]112\x1b\\");
+    }
+    let _raw = terminal::RawMode::enable(&tty, "\x1b[?1049h\x1b[?25l", restore)?;
+
+    let mut picker = Picker::new(schemes);
+    let mut applied = None;
+    let mut buffer = [0; 64];
+    loop {
+        let (width, height) = match terminal_size::terminal_size() {
+            Some((terminal_size::Width(w), terminal_size::Height(h))) => (w as usize, h as usize),
+            None => (80, 24),
+        };
+        let mut frame = String::from("\x1b[H");
+        if live && picker.highlighted() != applied {
+            applied = picker.highlighted();
+            if let Some(index) = applied {
+                frame.push_str(&schemes[index].1.to_osc());
+            }
+        }
+        for (i, line) in picker.render(width, height).iter().enumerate() {
+            if i > 0 {
+                frame.push_str("\r\n");
+            }
+            frame.push_str(line);
+            frame.push_str("\x1b[K");
+        }
+        frame.push_str("\x1b[J");
+        tty.write_all(frame.as_bytes())
+            .context("Failed to write to the terminal")?;
+
+        let length = tty
+            .read(&mut buffer)
+            .context("Failed to read the terminal")?;
+        if length == 0 {
+            return Ok(None);
+        }
+        for key in parse_keys(&buffer[..length]) {
+            match picker.handle(key) {
+                Action::Continue => {}
+                Action::Pick(index) => return Ok(Some(index)),
+                Action::Cancel => return Ok(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use anyhow::{Context, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::convert::TryInto;
+
+use crate::color::ParseError;
+
+/// A value in an XML property list, like iTerm 2 color schemes.
+#[derive(Debug, PartialEq)]
+pub enum Value {
+    /// Keys and values in the order of the file.
+    Dict(Vec<(String, Value)>),
+    Array(Vec<Value>),
+    String(String),
+    Real(f64),
+    Integer(i64),
+    Boolean(bool),
+    Data(Vec<u8>),
+    Date(String),
+    /// A reference to another object in keyed archives, only in binary property lists.
+    Uid(u64),
+}
+
+impl Value {
+    pub fn as_dict(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Dict(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_dict()?
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) | Value::Date(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_data(&self) -> Option<&[u8]> {
+        match self {
+            Value::Data(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The number of a `<real>` or an `<integer>`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Real(value) => Some(*value),
+            Value::Integer(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the root value of an XML property list.
+///
+/// Reads the XML as a stream of events without building a DOM. The XML declaration, DOCTYPE,
+/// comments, whitespace between elements and the `<plist>` element around the root value are
+/// skipped.
+pub fn parse(content: &str) -> Result<Value> {
+    let mut reader = Reader::from_str(content);
+    loop {
+        match reader.read_event().context(ParseError::XMLParse)? {
+            Event::Start(element) if element.name().as_ref() == b"plist" => {}
+            Event::Start(element) => {
+                let tag = element.name().as_ref().to_vec();
+                return read_value(&mut reader, &tag);
+            }
+            Event::Empty(element) => return empty_value(element.name().as_ref()),
+            Event::Eof => return Err(ParseError::XMLParse.into()),
+            _ => {}
+        }
+    }
+}
+
+/// Reads the value of an element whose start tag has just been read.
+fn read_value(reader: &mut Reader<&[u8]>, tag: &[u8]) -> Result<Value> {
+    match tag {
+        b"dict" => {
+            let mut entries = Vec::new();
+            let mut key = None;
+            while let Some(event) = read_child(reader)? {
+                match event {
+                    Child::Start(tag) if tag == b"key" => key = Some(read_text(reader)?),
+                    child => {
+                        let key = key.take().ok_or_else(|| unexpected(child.tag()))?;
+                        entries.push((key, child.into_value(reader)?));
+                    }
+                }
+            }
+            Ok(Value::Dict(entries))
+        }
+        b"array" => {
+            let mut values = Vec::new();
+            while let Some(child) = read_child(reader)? {
+                values.push(child.into_value(reader)?);
+            }
+            Ok(Value::Array(values))
+        }
+        b"string" => Ok(Value::String(read_text(reader)?)),
+        b"data" => Ok(Value::Data(decode_base64(&read_text(reader)?)?)),
+        b"date" => Ok(Value::Date(read_text(reader)?)),
+        b"real" => Ok(Value::Real(
+            read_text(reader)?
+                .trim()
+                .parse()
+                .context(ParseError::ParseFloat)?,
+        )),
+        b"integer" => Ok(Value::Integer(
+            read_text(reader)?
+                .trim()
+                .parse()
+                .context(ParseError::ParseInt)?,
+        )),
+        b"true" | b"false" => {
+            read_text(reader)?;
+            empty_value(tag)
+        }
+        _ => Err(unexpected(tag)),
+    }
+}
+
+/// A child element of a dict or an array.
+enum Child {
+    Start(Vec<u8>),
+    Empty(Vec<u8>),
+}
+
+impl Child {
+    fn tag(&self) -> &[u8] {
+        match self {
+            Child::Start(tag) | Child::Empty(tag) => tag,
+        }
+    }
+
+    fn into_value(self, reader: &mut Reader<&[u8]>) -> Result<Value> {
+        match self {
+            Child::Start(tag) => read_value(reader, &tag),
+            Child::Empty(tag) => empty_value(&tag),
+        }
+    }
+}
+
+/// Reads the next child element. Returns `None` at the end of the parent.
+fn read_child(reader: &mut Reader<&[u8]>) -> Result<Option<Child>> {
+    loop {
+        match reader.read_event().context(ParseError::XMLParse)? {
+            Event::Start(element) => {
+                return Ok(Some(Child::Start(element.name().as_ref().to_vec())))
+            }
+            Event::Empty(element) => {
+                return Ok(Some(Child::Empty(element.name().as_ref().to_vec())))
+            }
+            Event::End(_) => return Ok(None),
+            Event::Eof => return Err(ParseError::XMLParse.into()),
+            _ => {}
+        }
+    }
+}
+
+/// Reads text and CDATA until the end of the current element.
+fn read_text(reader: &mut Reader<&[u8]>) -> Result<String> {
+    let mut text = String::new();
+    loop {
+        match reader.read_event().context(ParseError::XMLParse)? {
+            Event::Text(t) => text.push_str(&t.unescape().context(ParseError::XMLParse)?),
+            Event::CData(t) => text.push_str(&t.decode().context(ParseError::XMLParse)?),
+            Event::End(_) => return Ok(text),
+            Event::Start(element) | Event::Empty(element) => {
+                return Err(unexpected(element.name().as_ref()))
+            }
+            Event::Eof => return Err(ParseError::XMLParse.into()),
+            _ => {}
+        }
+    }
+}
+
+fn empty_value(tag: &[u8]) -> Result<Value> {
+    match tag {
+        b"true" => Ok(Value::Boolean(true)),
+        b"false" => Ok(Value::Boolean(false)),
+        b"string" => Ok(Value::String(String::new())),
+        b"data" => Ok(Value::Data(Vec::new())),
+        b"dict" => Ok(Value::Dict(Vec::new())),
+        b"array" => Ok(Value::Array(Vec::new())),
+        _ => Err(unexpected(tag)),
+    }
+}
+
+fn unexpected(tag: &[u8]) -> anyhow::Error {
+    ParseError::UnexpectedElement(String::from_utf8_lossy(tag).into_owned()).into()
+}
+
+/// Decodes base64 text of `<data>`, which is usually wrapped into lines.
+fn decode_base64(text: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in text.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            c if c.is_ascii_whitespace() => continue,
+            _ => return Err(ParseError::InvalidPlistData.into()),
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Parses the root value of a binary property list (`bplist00`), like keyed archives of macOS.
+pub fn parse_binary(content: &[u8]) -> Result<Value> {
+    let invalid = || anyhow::Error::from(ParseError::InvalidPlistData);
+    if !content.starts_with(b"bplist00") || content.len() < 8 + 32 {
+        return Err(invalid());
+    }
+    let trailer = &content[content.len() - 32..];
+    let offset_size = usize::from(trailer[6]);
+    let ref_size = usize::from(trailer[7]);
+    let object_count = read_uint(&trailer[8..16]) as usize;
+    let top_object = read_uint(&trailer[16..24]) as usize;
+    let table_offset = read_uint(&trailer[24..32]) as usize;
+    let table_end = object_count
+        .checked_mul(offset_size)
+        .and_then(|size| size.checked_add(table_offset))
+        .filter(|&end| end <= content.len() && offset_size > 0 && ref_size > 0)
+        .ok_or_else(invalid)?;
+    let offsets: Vec<usize> = content[table_offset..table_end]
+        .chunks(offset_size)
+        .map(|offset| read_uint(offset) as usize)
+        .collect();
+    let reader = BinaryReader {
+        content,
+        offsets,
+        ref_size,
+    };
+    reader.read_object(top_object, 0)
+}
+
+/// Objects nested deeper than this are taken as a cycle of references.
+const MAX_DEPTH: usize = 64;
+
+struct BinaryReader<'a> {
+    content: &'a [u8],
+    offsets: Vec<usize>,
+    ref_size: usize,
+}
+
+impl BinaryReader<'_> {
+    fn read_object(&self, index: usize, depth: usize) -> Result<Value> {
+        let invalid = || anyhow::Error::from(ParseError::InvalidPlistData);
+        if depth > MAX_DEPTH {
+            return Err(invalid());
+        }
+        let offset = *self.offsets.get(index).ok_or_else(invalid)?;
+        let marker = *self.content.get(offset).ok_or_else(invalid)?;
+        let (kind, info) = (marker >> 4, usize::from(marker & 0x0f));
+        let bytes =
+            |start: usize, len: usize| self.content.get(start..start + len).ok_or_else(invalid);
+        match kind {
+            0x0 => match info {
+                0x8 => Ok(Value::Boolean(false)),
+                0x9 => Ok(Value::Boolean(true)),
+                _ => Err(invalid()),
+            },
+            0x1 => Ok(Value::Integer(
+                read_uint(bytes(offset + 1, 1 << info)?) as i64
+            )),
+            0x2 | 0x3 => {
+                let value = match info {
+                    2 => f64::from(f32::from_be_bytes(bytes(offset + 1, 4)?.try_into()?)),
+                    3 => f64::from_be_bytes(bytes(offset + 1, 8)?.try_into()?),
+                    _ => return Err(invalid()),
+                };
+                if kind == 0x2 {
+                    Ok(Value::Real(value))
+                } else {
+                    // Seconds since 2001-01-01.
+                    Ok(Value::Date(value.to_string()))
+                }
+            }
+            0x4 => {
+                let (start, len) = self.read_length(offset, info)?;
+                Ok(Value::Data(bytes(start, len)?.to_vec()))
+            }
+            0x5 => {
+                let (start, len) = self.read_length(offset, info)?;
+                Ok(Value::String(
+                    String::from_utf8_lossy(bytes(start, len)?).into_owned(),
+                ))
+            }
+            0x6 => {
+                let (start, len) = self.read_length(offset, info)?;
+                let units: Vec<u16> = bytes(start, len * 2)?
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect();
+                Ok(Value::String(String::from_utf16_lossy(&units)))
+            }
+            0x8 => Ok(Value::Uid(read_uint(bytes(offset + 1, info + 1)?))),
+            0xa => {
+                let (start, len) = self.read_length(offset, info)?;
+                let values = (0..len)
+                    .map(|i| self.read_ref(start + i * self.ref_size, depth))
+                    .collect::<Result<_>>()?;
+                Ok(Value::Array(values))
+            }
+            0xd => {
+                let (start, len) = self.read_length(offset, info)?;
+                let mut entries = Vec::new();
+                for i in 0..len {
+                    let key = match self.read_ref(start + i * self.ref_size, depth)? {
+                        Value::String(key) => key,
+                        _ => return Err(invalid()),
+                    };
+                    let value = self.read_ref(start + (len + i) * self.ref_size, depth)?;
+                    entries.push((key, value));
+                }
+                Ok(Value::Dict(entries))
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Reads the object referred at the position.
+    fn read_ref(&self, position: usize, depth: usize) -> Result<Value> {
+        let index = self
+            .content
+            .get(position..position + self.ref_size)
+            .ok_or(ParseError::InvalidPlistData)?;
+        self.read_object(read_uint(index) as usize, depth + 1)
+    }
+
+    /// The start and the length of the content of an object. Lengths of 15 or more follow the
+    /// marker as an integer object.
+    fn read_length(&self, offset: usize, info: usize) -> Result<(usize, usize)> {
+        if info < 0x0f {
+            return Ok((offset + 1, info));
+        }
+        let marker = *self
+            .content
+            .get(offset + 1)
+            .ok_or(ParseError::InvalidPlistData)?;
+        if marker >> 4 != 0x1 {
+            return Err(ParseError::InvalidPlistData.into());
+        }
+        let size = 1 << (marker & 0x0f);
+        let len = self
+            .content
+            .get(offset + 2..offset + 2 + size)
+            .ok_or(ParseError::InvalidPlistData)?;
+        Ok((offset + 2 + size, read_uint(len) as usize))
+    }
+}
+
+/// Reads a big-endian unsigned integer of up to 8 bytes.
+fn read_uint(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .take(8)
+        .rev()
+        .fold(0, |value, &byte| (value << 8) | u64::from(byte))
+}
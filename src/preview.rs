@@ -0,0 +1,269 @@
+use crate::color::{Color, ColorScheme};
+
+/// The width of a sample screen when the terminal is wide enough.
+pub const SCREEN_WIDTH: usize = 40;
+
+/// Sample screens are not narrowed further than this. They wrap to the next row instead.
+pub const MIN_SCREEN_WIDTH: usize = 20;
+
+/// The number of columns between sample screens.
+const GAP: usize = 2;
+
+/// The number of columns of `ColorScheme::to_preview`.
+pub const PREVIEW_WIDTH: usize = 23;
+
+/// Names in a grid are cut to this many columns.
+const MAX_GRID_NAME_WIDTH: usize = 24;
+
+/// A run of text in a single color.
+struct Span<'a> {
+    foreground: &'a Color,
+    background: Option<&'a Color>,
+    /// SGR parameters of text attributes like `1` for bold, or empty for none.
+    attributes: &'static str,
+    text: String,
+}
+
+impl<'a> Span<'a> {
+    fn new(foreground: &'a Color, text: &str) -> Self {
+        Span {
+            foreground,
+            background: None,
+            attributes: "",
+            text: text.to_owned(),
+        }
+    }
+
+    fn swatch(color: &'a Color, width: usize) -> Self {
+        Span {
+            foreground: color,
+            background: Some(color),
+            attributes: "",
+            text: " ".repeat(width),
+        }
+    }
+
+    fn styled(foreground: &'a Color, attributes: &'static str, text: &str) -> Self {
+        Span {
+            attributes,
+            ..Span::new(foreground, text)
+        }
+    }
+}
+
+/// Renders a sample terminal screen of a color scheme with the given title.
+///
+/// Returns one string per line. Every line is exactly `width` columns wide and filled with the
+/// background color of the scheme.
+pub fn sample_screen(scheme: &ColorScheme, title: &str, width: usize) -> Vec<String> {
+    let colors = scheme.ansi_colors();
+    let foreground = scheme.foreground();
+    let prompt = |command: &str| {
+        vec![
+            Span::new(colors[2], "user@host"),
+            Span::new(foreground, ":"),
+            Span::new(colors[4], "~"),
+            Span::new(foreground, &format!("$ {}", command)),
+        ]
+    };
+
+    let mut cursor_line = prompt("");
+    cursor_line.push(Span {
+        foreground: scheme.cursor_text().unwrap_or(scheme.background()),
+        background: Some(scheme.cursor().unwrap_or(foreground)),
+        attributes: "",
+        text: " ".to_owned(),
+    });
+    // Each ANSI color as text on the background, in the same columns as the swatches below.
+    let text_row = |range: std::ops::Range<usize>| -> Vec<Span> {
+        colors[range]
+            .iter()
+            .map(|color| Span::new(color, "Aa "))
+            .collect()
+    };
+
+    let lines = vec![
+        vec![Span::new(foreground, title)],
+        vec![],
+        prompt("ls"),
+        vec![
+            Span::new(colors[12], "src"),
+            Span::new(foreground, "  "),
+            Span::new(colors[14], "link"),
+            Span::new(foreground, "  "),
+            Span::new(colors[10], "run.sh"),
+            Span::new(foreground, "  README.md"),
+        ],
+        prompt("git status"),
+        vec![Span::new(colors[2], "  new file:   src/lib.rs")],
+        vec![Span::new(colors[1], "  modified:   src/main.rs")],
+        vec![
+            Span::new(colors[3], "warning"),
+            Span::new(foreground, ": unused "),
+            Span::new(colors[5], "variable"),
+        ],
+        vec![],
+        // Code in the roles that most syntax highlighters give to ANSI colors
+        vec![
+            Span::new(colors[5], "fn "),
+            Span::new(colors[4], "main"),
+            Span::new(foreground, "() {"),
+        ],
+        vec![Span::new(colors[8], "    // Greet the world")],
+        vec![
+            Span::new(colors[5], "    let "),
+            Span::new(foreground, "count: "),
+            Span::new(colors[6], "u32"),
+            Span::new(foreground, " = "),
+            Span::new(colors[3], "42"),
+            Span::new(foreground, ";"),
+        ],
+        vec![
+            Span::new(colors[4], "    println!"),
+            Span::new(foreground, "("),
+            Span::new(colors[2], "\"hello {}\""),
+            Span::new(foreground, ", count);"),
+        ],
+        vec![Span::new(foreground, "}")],
+        vec![],
+        prompt("git diff"),
+        vec![Span::new(colors[6], "@@ -1,2 +1,2 @@")],
+        vec![Span::new(foreground, " [colors]")],
+        vec![Span::new(colors[1], "-theme = \"light\"")],
+        vec![Span::new(colors[2], "+theme = \"dark\"")],
+        vec![],
+        vec![
+            Span::styled(foreground, "1", "bold"),
+            Span::new(foreground, " "),
+            Span::styled(foreground, "2", "dim"),
+            Span::new(foreground, " "),
+            Span::styled(foreground, "3", "italic"),
+            Span::new(foreground, " "),
+            Span::styled(foreground, "4", "underline"),
+            Span::new(foreground, " "),
+            Span::styled(foreground, "7", "reverse"),
+        ],
+        vec![
+            Span::styled(colors[1], "1", "bold red"),
+            Span::new(foreground, " "),
+            Span::styled(colors[4], "4", "underlined blue"),
+        ],
+        vec![],
+        text_row(0..8),
+        text_row(8..16),
+        colors[..8]
+            .iter()
+            .map(|color| Span::swatch(color, 3))
+            .collect(),
+        colors[8..]
+            .iter()
+            .map(|color| Span::swatch(color, 3))
+            .collect(),
+        vec![],
+        cursor_line,
+    ];
+
+    lines
+        .into_iter()
+        .map(|spans| render_line(scheme, spans, width))
+        .collect()
+}
+
+/// Renders sample screens of color schemes next to each other to fit in `width` columns.
+///
+/// Screens that don't fit in a row wrap to the next row.
+pub fn side_by_side(schemes: &[(&str, &ColorScheme)], width: usize) -> String {
+    if schemes.is_empty() {
+        return String::new();
+    }
+    let per_row = ((width + GAP) / (MIN_SCREEN_WIDTH + GAP)).clamp(1, schemes.len());
+    let screen_width = ((width + GAP) / per_row)
+        .saturating_sub(GAP)
+        .clamp(1, SCREEN_WIDTH);
+
+    let mut rows = Vec::new();
+    for chunk in schemes.chunks(per_row) {
+        let screens: Vec<_> = chunk
+            .iter()
+            .map(|(title, scheme)| sample_screen(scheme, title, screen_width))
+            .collect();
+        let lines: Vec<String> = (0..screens[0].len())
+            .map(|i| {
+                let line: Vec<&str> = screens.iter().map(|screen| screen[i].as_str()).collect();
+                line.join(&" ".repeat(GAP))
+            })
+            .collect();
+        rows.push(lines.join("\n"));
+    }
+    rows.join("\n\n")
+}
+
+/// The number of columns of a grid of the color schemes that fits in `width` columns, at least one.
+pub fn grid_columns(schemes: &[(&str, &ColorScheme)], width: usize) -> usize {
+    ((width + GAP) / (grid_cell_width(schemes) + GAP)).max(1)
+}
+
+/// Renders names and previews of color schemes in a grid of `columns` columns, from left to right
+/// and then top to bottom.
+///
+/// Names longer than the cells are cut with an ellipsis.
+pub fn grid(schemes: &[(&str, &ColorScheme)], columns: usize) -> String {
+    let name_width = grid_cell_width(schemes) - 1 - PREVIEW_WIDTH;
+    let rows: Vec<String> = schemes
+        .chunks(columns.max(1))
+        .map(|chunk| {
+            let cells: Vec<String> = chunk
+                .iter()
+                .map(|(name, scheme)| {
+                    let name = if name.chars().count() > name_width {
+                        let cut: String = name.chars().take(name_width - 1).collect();
+                        format!("{}…", cut)
+                    } else {
+                        name.to_string()
+                    };
+                    format!(
+                        "{:width$} {}",
+                        name,
+                        scheme.to_preview(),
+                        width = name_width
+                    )
+                })
+                .collect();
+            cells.join(&" ".repeat(GAP))
+        })
+        .collect();
+    rows.join("\n")
+}
+
+/// The number of columns of a cell of a grid: a name, a space and a preview.
+fn grid_cell_width(schemes: &[(&str, &ColorScheme)]) -> usize {
+    let name_width = schemes
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_GRID_NAME_WIDTH);
+    name_width + 1 + PREVIEW_WIDTH
+}
+
+/// Renders spans, cutting them at `width` columns and filling the rest with the background color.
+fn render_line(scheme: &ColorScheme, spans: Vec<Span>, width: usize) -> String {
+    let mut line = String::new();
+    let mut remaining = width;
+    for span in spans {
+        let text: String = span.text.chars().take(remaining).collect();
+        remaining -= text.chars().count();
+        line.push_str(&span.background.unwrap_or(scheme.background()).to_24bit_be());
+        line.push_str(&span.foreground.to_24bit_fe());
+        if span.attributes.is_empty() {
+            line.push_str(&text);
+        } else {
+            // Resetting the attributes also resets the colors, which the next span sets again.
+            line.push_str(&format!("\x1b[{}m{}\x1b[0m", span.attributes, text));
+        }
+    }
+    line.push_str(&scheme.background().to_24bit_be());
+    line.push_str(&" ".repeat(remaining));
+    line.push_str("\x1b[0m");
+    line
+}
@@ -1,14 +1,84 @@
 use anyhow::{anyhow, bail, Context, Result};
-use async_std::{fs, prelude::*};
+use async_std::fs;
 use dirs;
-use futures::future;
+use futures::{stream, StreamExt, TryStreamExt};
 use std::path::PathBuf;
-use surf::RequestBuilder;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime};
 
 use crate::color::ColorScheme;
+use crate::jobs;
+use crate::{debug, info};
+
+/// Errors of requests to GitHub.
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum FetchError {
+    #[error("failed to send an HTTP request")]
+    Connection,
+
+    #[error("received non-success status code: {0}")]
+    Status(u16),
+
+    /// With whether a GitHub token was sent.
+    #[error("GitHub API rate limit exceeded{}", rate_limit_hint(*.0))]
+    RateLimited(bool),
+}
+
+fn rate_limit_hint(authenticated: bool) -> &'static str {
+    if authenticated {
+        ""
+    } else {
+        ". Set a token with GITHUB_TOKEN or --github-token to raise the limit"
+    }
+}
+
+/// The environment variable to set a GitHub token with.
+pub const GITHUB_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+static GITHUB_TOKEN: Mutex<Option<String>> = Mutex::new(None);
+
+/// Sets a GitHub token to send to GitHub for the process, which takes precedence over
+/// `GITHUB_TOKEN`.
+pub fn set_github_token(token: String) {
+    *GITHUB_TOKEN.lock().unwrap_or_else(|e| e.into_inner()) = Some(token);
+}
+
+/// The GitHub token set with `set_github_token()` or `GITHUB_TOKEN`, if any.
+fn github_token() -> Option<String> {
+    GITHUB_TOKEN
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .or_else(|| std::env::var(GITHUB_TOKEN_ENV).ok())
+        .filter(|token| !token.is_empty())
+}
+
+/// Whether a URL is of GitHub, which is the only host that a GitHub token is sent to.
+fn is_github_url(url: &str) -> bool {
+    [
+        "https://api.github.com/",
+        "https://raw.githubusercontent.com/",
+    ]
+    .iter()
+    .any(|prefix| url.starts_with(prefix))
+}
+
+/// The file in a repository cache that lists the cached files with their Git blob SHAs.
+const MANIFEST_FILE: &str = ".manifest";
+
+/// The file in a repository cache that has the ETag of the last file list.
+const ETAG_FILE: &str = ".etag";
+
+/// The cache directory of colortty, which has the caches of all providers.
+pub fn cache_dir() -> Result<PathBuf> {
+    let mut dir = dirs::cache_dir().ok_or(anyhow!("There is no cache directory"))?;
+    dir.push("colortty");
+    Ok(dir)
+}
 
 /// A GitHub repository that provides color schemes.
 pub struct Provider {
+    name: String,
     user_name: String,
     repo_name: String,
     list_path: String,
@@ -19,6 +89,7 @@ impl Provider {
     /// Returns a provider for `mbadolato/iTerm2-Color-Schemes`.
     pub fn iterm() -> Self {
         Provider::new(
+            "iterm",
             "mbadolato",
             "iTerm2-Color-Schemes",
             "schemes",
@@ -27,13 +98,22 @@ impl Provider {
     }
 
     /// Returns a provider for `Gogh-Co/Gogh`.
+    ///
+    /// Gogh keeps its themes in YAML. The shell scripts that it used to have in `themes` are now
+    /// generated into another directory.
     pub fn gogh() -> Self {
-        Provider::new("Gogh-Co", "Gogh", "themes", ".sh")
+        Provider::new("gogh", "Gogh-Co", "Gogh", "themes", ".yml")
+    }
+
+    /// Returns all the supported providers.
+    pub fn all() -> Vec<Self> {
+        vec![Provider::iterm(), Provider::gogh()]
     }
 
     /// Returns a provider instance.
-    fn new(user_name: &str, repo_name: &str, list_path: &str, extension: &str) -> Self {
+    fn new(name: &str, user_name: &str, repo_name: &str, list_path: &str, extension: &str) -> Self {
         Provider {
+            name: name.to_string(),
             user_name: user_name.to_string(),
             repo_name: repo_name.to_string(),
             list_path: list_path.to_string(),
@@ -41,38 +121,68 @@ impl Provider {
         }
     }
 
+    /// The name to specify the provider with on the command line.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Fetches the raw content of the color scheme for the given name.
     pub async fn get(&self, name: &str) -> Result<ColorScheme> {
-        let req = surf::get(&self.individual_url(name));
-        let body = send_http_request(req)
+        let body = send_http_request(&self.individual_url(name))
             .await
             .with_context(|| format!("Failed to get color scheme raw content for {}", name))?;
-        self.parse_color_scheme(&body)
+        let start = Instant::now();
+        let color_scheme = self.parse_color_scheme(&body);
+        debug!("Parsed {} in {:?}", name, start.elapsed());
+        color_scheme
     }
 
     /// Returns all color schemes in the provider.
     ///
     /// This function caches color schemes in the file system.
-    pub async fn list(self) -> Result<Vec<(String, ColorScheme)>> {
-        match self.read_color_schemes().await {
-            Ok(color_schemes) => {
-                if color_schemes.len() > 0 {
-                    return Ok(color_schemes);
-                }
+    pub async fn list(&self) -> Result<Vec<(String, ColorScheme)>> {
+        if let Ok(color_schemes) = self.read_color_schemes().await {
+            if !color_schemes.is_empty() {
+                debug!(
+                    "Cache hit: {} color schemes of {}",
+                    color_schemes.len(),
+                    self.name
+                );
+                return Ok(color_schemes);
             }
-            _ => {}
         }
+        debug!("Cache miss: no color schemes of {}", self.name);
 
         // If there are no cached files, download them.
         self.download_all().await?;
         self.read_color_schemes().await
     }
 
+    /// Returns the color schemes in the cache without downloading them. Returns an empty list if
+    /// they are not cached, and skips files that can't be read.
+    pub async fn cached(&self) -> Vec<(String, ColorScheme)> {
+        self.read_color_schemes().await.unwrap_or_default()
+    }
+
+    /// Returns when the cache was last updated, or `None` if there is no cache.
+    pub async fn cache_updated_at(&self) -> Option<SystemTime> {
+        let repo_dir = self.repo_dir().ok()?;
+        // Caches from before the manifest only have the directory.
+        let metadata = match fs::metadata(repo_dir.join(MANIFEST_FILE)).await {
+            Ok(metadata) => metadata,
+            Err(_) => fs::metadata(&repo_dir).await.ok()?,
+        };
+        metadata.modified().ok()
+    }
+
     /// Download color scheme files into the cache directory.
-    pub async fn download_all(&self) -> Result<()> {
+    ///
+    /// Only files that changed since the last download are downloaded, and nothing is downloaded
+    /// if the repository didn't change at all. Returns the number of downloaded files.
+    pub async fn download_all(&self) -> Result<usize> {
         let repo_dir = self.repo_dir()?;
 
-        eprintln!(
+        info!(
             "Downloading color schemes into {}",
             repo_dir.to_str().unwrap()
         );
@@ -82,67 +192,200 @@ impl Provider {
             .await
             .context("Failed to create the cache directory")?;
 
-        let list_req = surf::get(&self.list_url());
-        let list_body = send_http_request(list_req)
+        let etag_path = repo_dir.join(ETAG_FILE);
+        let manifest_path = repo_dir.join(MANIFEST_FILE);
+        let old_manifest = fs::read_to_string(&manifest_path).await.unwrap_or_default();
+        // Without the manifest, the ETag doesn't tell whether the cached files are complete.
+        let etag = if old_manifest.is_empty() {
+            None
+        } else {
+            fs::read_to_string(&etag_path).await.ok()
+        };
+
+        let (list_body, new_etag) =
+            match send_conditional_request(&self.list_url(), etag.as_deref())
+                .await
+                .context("Failed to download a color scheme list")?
+            {
+                Some(response) => response,
+                None => return Ok(0),
+            };
+        let files = self.files_from_tree(&list_body)?;
+
+        // Download and save color scheme files that changed.
+        let old_shas: Vec<&str> = old_manifest.lines().collect();
+        let mut changed = Vec::new();
+        for (name, sha) in &files {
+            let line = format!("{} {}", sha, name);
+            if old_shas.contains(&line.as_str()) && self.individual_path(name)?.exists() {
+                continue;
+            }
+            changed.push(name.clone());
+        }
+        let count = changed.len();
+
+        // Limit the number of parallel downloads.
+        //
+        // If this requests all files in parallel, the HTTP client (isahc) throws the
+        // following error:
+        //
+        //   HTTP request error: ConnectFailed: failed to connect to the server
+        //
+        // isahc doesn't limit the number of connections per client by default, but
+        // it exposes an API to limit it. However, surf doesn't expose the API.
+        stream::iter(
+            changed
+                .into_iter()
+                .map(|name| self.download_color_scheme(name)),
+        )
+        .buffer_unordered(jobs::get())
+        .try_for_each(|_| async { Ok(()) })
+        .await?;
+
+        // Record what was downloaded only after everything succeeded.
+        let manifest: Vec<String> = files
+            .iter()
+            .map(|(name, sha)| format!("{} {}\n", sha, name))
+            .collect();
+        fs::write(&manifest_path, manifest.concat())
             .await
-            .context("Failed to download a color scheme list")?;
-        let items = json::parse(&list_body).context("Failed to parse a color scheme list")?;
+            .context("Failed to write the cache manifest")?;
+        if let Some(etag) = new_etag {
+            fs::write(&etag_path, etag)
+                .await
+                .context("Failed to write the cache ETag")?;
+        }
 
-        // Download and save color scheme files.
-        let mut futures = Vec::new();
-        for item in items.members() {
-            let filename = item["name"].as_str().unwrap();
+        Ok(count)
+    }
+
+    /// Extracts color scheme names and their Git blob SHAs from a response of the Git trees API
+    /// for the color scheme directory.
+    ///
+    /// Unlike the contents API, the trees API lists all files of large directories in one
+    /// response. Fails if GitHub truncated the response anyway.
+    pub fn files_from_tree(&self, body: &str) -> Result<Vec<(String, String)>> {
+        let tree = json::parse(body).context("Failed to parse a color scheme list")?;
+        if tree["truncated"].as_bool() == Some(true) {
+            bail!("The color scheme list is truncated by GitHub");
+        }
+
+        let mut files = Vec::new();
+        for item in tree["tree"].members() {
+            // Subdirectories are listed as trees without their contents.
+            let filename = match item["path"].as_str() {
+                Some(filename) if item["type"] == "blob" => filename,
+                _ => continue,
+            };
 
             // Ignoring files starting with `_` for Gogh.
             if filename.starts_with('_') || !filename.ends_with(&self.extension) {
                 continue;
             }
-
-            let name = filename.replace(&self.extension, "");
-            let req = surf::get(&self.individual_url(&name));
-            futures.push(self.download_color_scheme(req, name));
-
-            // Download files in batches.
-            //
-            // If this requests all files in parallel, the HTTP client (isahc) throws the
-            // following error:
-            //
-            //   HTTP request error: ConnectFailed: failed to connect to the server
-            //
-            // isahc doesn't limit the number of connections per client by default, but
-            // it exposes an API to limit it. However, surf doesn't expose the API.
-            if futures.len() > 10 {
-                future::try_join_all(futures).await?;
-                futures = Vec::new();
-            }
+            let sha = item["sha"].as_str().unwrap_or_default().to_owned();
+            files.push((filename.replace(&self.extension, ""), sha));
         }
+        Ok(files)
+    }
+
+    /// Returns cached color schemes that can't be read or parsed, with the errors.
+    pub async fn broken(&self) -> Result<Vec<(String, anyhow::Error)>> {
+        let names = self.cached_names().await?;
+        let results: Vec<_> = stream::iter(
+            names
+                .iter()
+                .map(|name| self.read_color_scheme(name.clone())),
+        )
+        .buffered(jobs::get())
+        .collect()
+        .await;
+        Ok(names
+            .into_iter()
+            .zip(results)
+            .filter_map(|(name, result)| result.err().map(|e| (name, e)))
+            .collect())
+    }
 
+    /// Removes a color scheme from the cache.
+    ///
+    /// The ETag is removed too so that the next update checks all files and downloads the color
+    /// scheme again if it has been fixed.
+    pub async fn remove_cached(&self, name: &str) -> Result<()> {
+        fs::remove_file(self.individual_path(name)?)
+            .await
+            .with_context(|| format!("Failed to remove the color scheme file for {}", name))?;
+        let etag_path = self.repo_dir()?.join(ETAG_FILE);
+        if etag_path.exists() {
+            fs::remove_file(etag_path)
+                .await
+                .context("Failed to remove the cache ETag")?;
+        }
         Ok(())
     }
 
-    /// Read color schemes from the cache directory.
+    /// Downloads a color scheme into the cache again, and returns the error of parsing the
+    /// downloaded file if it's still unparsable.
+    pub async fn redownload(&self, name: &str) -> Result<Option<anyhow::Error>> {
+        let body = self.download_color_scheme(name.to_owned()).await?;
+        Ok(self.parse_color_scheme(&body).err())
+    }
+
+    /// Read color schemes from the cache directory, skipping files that can't be read or parsed.
     async fn read_color_schemes(&self) -> Result<Vec<(String, ColorScheme)>> {
+        // Read files in parallel, keeping the order.
+        let start = Instant::now();
+        let names = self.cached_names().await?;
+        let results: Vec<_> = stream::iter(
+            names
+                .iter()
+                .map(|name| self.read_color_scheme(name.clone())),
+        )
+        .buffered(jobs::get())
+        .collect()
+        .await;
+        let mut color_schemes = Vec::new();
+        for (name, result) in names.iter().zip(results) {
+            match result {
+                Ok(color_scheme) => color_schemes.push(color_scheme),
+                // A broken file shouldn't hide all the other color schemes.
+                Err(e) => eprintln!(
+                    "warning: {}: {}: {:#} (run `colortty prune` to fix broken files)",
+                    self.name, name, e
+                ),
+            }
+        }
+        debug!(
+            "Read and parsed {} color schemes of {} in {:?}",
+            color_schemes.len(),
+            self.name,
+            start.elapsed()
+        );
+
+        Ok(color_schemes)
+    }
+
+    /// Names of color schemes in the cache directory, without parsing them.
+    pub async fn cached_names(&self) -> Result<Vec<String>> {
         let mut entries = fs::read_dir(self.repo_dir()?)
             .await
             .context("Failed to read the cache directory")?;
 
-        // Collect futures and run them in parallel.
-        let mut futures = Vec::new();
+        let mut names = Vec::new();
         while let Some(entry) = entries.next().await {
             let dir_entry = entry.context("Failed to read the cache directory entry")?;
             let filename = dir_entry.file_name().into_string().unwrap();
+            // Skip the manifest and the ETag.
+            if !filename.ends_with(&self.extension) {
+                continue;
+            }
 
-            let name = filename.replace(&self.extension, "").to_string();
-            futures.push(self.read_color_scheme(name));
+            names.push(filename.replace(&self.extension, "").to_string());
         }
-
-        let color_schemes = future::try_join_all(futures).await?;
-
-        Ok(color_schemes)
+        Ok(names)
     }
 
     /// Reads a color scheme from the repository cache.
-    async fn read_color_scheme(&self, name: String) -> Result<(String, ColorScheme)> {
+    pub async fn read_color_scheme(&self, name: String) -> Result<(String, ColorScheme)> {
         let file_path = self.individual_path(&name)?;
 
         let body = fs::read_to_string(file_path)
@@ -153,21 +396,59 @@ impl Provider {
         Ok((name, color_scheme))
     }
 
-    /// Downloads a color scheme file and save it in the cache directory.
-    async fn download_color_scheme(&self, req: RequestBuilder, name: String) -> Result<()> {
-        let body = send_http_request(req)
+    /// Downloads a color scheme file, saves it in the cache directory and returns its content.
+    async fn download_color_scheme(&self, name: String) -> Result<String> {
+        let body = send_http_request(&self.individual_url(&name))
             .await
             .with_context(|| format!("Failed to download a color scheme file for {}", name))?;
-        fs::write(self.individual_path(&name)?, body)
+        fs::write(self.individual_path(&name)?, &body)
             .await
             .with_context(|| format!("Failed to write a color scheme file for {}", name))?;
-        Ok(())
+        Ok(body)
+    }
+
+    /// The number of cached color scheme files and the total size of the cache in bytes. Returns
+    /// zeros if nothing is cached.
+    pub async fn cache_size(&self) -> (usize, u64) {
+        let repo_dir = match self.repo_dir() {
+            Ok(repo_dir) => repo_dir,
+            Err(_) => return (0, 0),
+        };
+        let mut entries = match fs::read_dir(&repo_dir).await {
+            Ok(entries) => entries,
+            Err(_) => return (0, 0),
+        };
+        let (mut count, mut size) = (0, 0);
+        while let Some(Ok(entry)) = entries.next().await {
+            if let Ok(metadata) = entry.metadata().await {
+                size += metadata.len();
+            }
+            if entry
+                .file_name()
+                .to_string_lossy()
+                .ends_with(&self.extension)
+            {
+                count += 1;
+            }
+        }
+        (count, size)
+    }
+
+    /// Removes the whole cache of the provider. Returns whether there was a cache.
+    pub async fn clear_cache(&self) -> Result<bool> {
+        let repo_dir = self.repo_dir()?;
+        if !repo_dir.exists() {
+            return Ok(false);
+        }
+        fs::remove_dir_all(&repo_dir)
+            .await
+            .with_context(|| format!("Failed to remove: {}", repo_dir.display()))?;
+        Ok(true)
     }
 
     /// The repository cache directory.
-    fn repo_dir(&self) -> Result<PathBuf> {
-        let mut repo_dir = dirs::cache_dir().ok_or(anyhow!("There is no cache directory"))?;
-        repo_dir.push("colortty");
+    pub fn repo_dir(&self) -> Result<PathBuf> {
+        let mut repo_dir = cache_dir()?;
         repo_dir.push("repositories");
         repo_dir.push(&self.user_name);
         repo_dir.push(&self.repo_name);
@@ -182,18 +463,30 @@ impl Provider {
         Ok(file_path)
     }
 
+    /// Returns the URL of the repository on GitHub.
+    pub fn repository_url(&self) -> String {
+        format!("https://github.com/{}/{}", self.user_name, self.repo_name)
+    }
+
+    /// Returns the path of a color scheme file in the repository.
+    pub fn source_path(&self, name: &str) -> String {
+        format!("{}/{}{}", self.list_path, name, self.extension)
+    }
+
     /// Returns the URL for a color scheme on GitHub.
     fn individual_url(&self, name: &str) -> String {
         format!(
-            "https://raw.githubusercontent.com/{}/{}/master/{}/{}{}",
-            self.user_name, self.repo_name, self.list_path, name, self.extension
+            "https://raw.githubusercontent.com/{}/{}/master/{}",
+            self.user_name,
+            self.repo_name,
+            self.source_path(name)
         )
     }
 
-    /// Returns the URL for the color scheme list on GitHub API.
+    /// Returns the URL for the list of files in the color scheme directory on GitHub API.
     fn list_url(&self) -> String {
         format!(
-            "https://api.github.com/repos/{}/{}/contents/{}",
+            "https://api.github.com/repos/{}/{}/git/trees/master:{}",
             self.user_name, self.repo_name, self.list_path
         )
     }
@@ -202,33 +495,142 @@ impl Provider {
     fn parse_color_scheme(&self, body: &str) -> Result<ColorScheme> {
         // TODO: Think about better abstraction.
         if self.extension == ".itermcolors" {
-            ColorScheme::from_iterm(&body)
+            ColorScheme::from_iterm(body)
         } else {
-            ColorScheme::from_gogh(&body)
+            ColorScheme::from_gogh(body)
         }
     }
 }
 
-/// Sends an HTTP request and returns the body of the given request.
+/// Whether a source is an HTTP or HTTPS URL rather than a file.
+pub fn is_url(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+/// The path of a URL without the query and the fragment, with percent-encoded characters decoded,
+/// like `/Gruvbox Dark.itermcolors` of `https://example.com/Gruvbox%20Dark.itermcolors?raw=1`.
+pub fn url_path(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let path = rest.split(['?', '#']).next().unwrap_or_default();
+    let path = path.find('/').map_or("", |start| &path[start..]);
+
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Downloads a file like a color scheme given as a URL.
+pub async fn download(url: &str) -> Result<Vec<u8>> {
+    let mut res = send(url, surf::get(url)).await?;
+
+    if !res.status().is_success() {
+        bail!(FetchError::Status(res.status().into()));
+    }
+
+    let body = res
+        .body_bytes()
+        .await
+        .map_err(|e| e.into_inner())
+        .context(FetchError::Connection)
+        .context("Failed to read HTTP response body")?;
+    Ok(body)
+}
+
+/// Sends a GET request with `If-None-Match` if there is an ETag, and returns the body with the new
+/// ETag.
 ///
-/// Fails when the URL responds with non-200 status code. Also sends
-/// `colortty` as `User-Agent` header.
-async fn send_http_request(req: RequestBuilder) -> Result<String> {
-    let mut res = req
-        .header("User-Agent", "colortty")
+/// Returns `None` if the server responds that the content is not modified.
+async fn send_conditional_request(
+    url: &str,
+    etag: Option<&str>,
+) -> Result<Option<(String, Option<String>)>> {
+    let mut req = surf::get(url);
+    if let Some(etag) = etag {
+        req = req.header("If-None-Match", etag);
+    }
+    let mut res = send(url, req).await?;
+
+    if res.status() == surf::StatusCode::NotModified {
+        return Ok(None);
+    }
+    if !res.status().is_success() {
+        bail!(FetchError::Status(res.status().into()));
+    }
+
+    let etag = res.header("ETag").map(|values| values.last().to_string());
+    let body = res
+        .body_string()
         .await
-        // Surf::Error (http_types::Error) is not a std::error:Error.
         .map_err(|e| e.into_inner())
-        .context("Failed to send an HTTP request")?;
+        .context(FetchError::Connection)
+        .context("Failed to read HTTP response body")?;
+    Ok(Some((body, etag)))
+}
+
+/// Sends a GET request and returns the body of the response.
+///
+/// Fails when the URL responds with non-200 status code.
+async fn send_http_request(url: &str) -> Result<String> {
+    let mut res = send(url, surf::get(url)).await?;
 
     if !res.status().is_success() {
-        bail!("Received non-success status code: {}", res.status());
+        bail!(FetchError::Status(res.status().into()));
     }
 
     let body = res
         .body_string()
         .await
         .map_err(|e| e.into_inner())
+        .context(FetchError::Connection)
         .context("Failed to read HTTP response body")?;
-    return Ok(body);
+    Ok(body)
+}
+
+/// Sends a request to the URL with `colortty` as `User-Agent` header.
+///
+/// A GitHub token is sent only to GitHub. Fails with `FetchError::RateLimited` if GitHub refuses
+/// the request for the rate limit.
+async fn send(url: &str, mut req: surf::RequestBuilder) -> Result<surf::Response> {
+    debug!("GET {}", url);
+    let token = github_token().filter(|_| is_github_url(url));
+    if let Some(token) = &token {
+        req = req.header("Authorization", format!("Bearer {}", token));
+    }
+    let start = Instant::now();
+    let res = req
+        .header("User-Agent", "colortty")
+        .await
+        // Surf::Error (http_types::Error) is not a std::error:Error.
+        .map_err(|e| e.into_inner())
+        .context(FetchError::Connection)?;
+    debug!("{} {} in {:?}", res.status(), url, start.elapsed());
+
+    let remaining = res
+        .header("X-RateLimit-Remaining")
+        .map(|values| values.last().as_str().to_owned());
+    let status = res.status();
+    if is_github_url(url)
+        && (status == surf::StatusCode::TooManyRequests
+            || (status == surf::StatusCode::Forbidden && remaining.as_deref() == Some("0")))
+    {
+        bail!(FetchError::RateLimited(token.is_some()));
+    }
+    Ok(res)
 }
@@ -0,0 +1,101 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A color scheme that was applied to a terminal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Applied {
+    /// The terminal that the color scheme was applied to, like `alacritty`.
+    pub target: String,
+    pub name: String,
+    /// The provider of the color scheme, if it came from one.
+    pub provider: Option<String>,
+    /// The file that the color scheme was written into.
+    pub path: Option<PathBuf>,
+    pub applied_at: SystemTime,
+}
+
+/// The last applied color scheme of each target, kept in a JSON file.
+#[derive(Debug, Default, PartialEq)]
+pub struct State {
+    applied: Vec<Applied>,
+}
+
+impl State {
+    /// The default state file in the local data directory.
+    pub fn default_path() -> Result<PathBuf> {
+        let mut path = dirs::data_local_dir().ok_or(anyhow!("There is no data directory"))?;
+        path.push("colortty");
+        path.push("state.json");
+        Ok(path)
+    }
+
+    /// Reads the state file. Returns an empty state if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(State::default()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read: {}", path.display())),
+        };
+        let root = json::parse(&content)
+            .with_context(|| format!("Failed to parse: {}", path.display()))?;
+
+        let mut applied = Vec::new();
+        for (target, entry) in root["targets"].entries() {
+            let name = match entry["name"].as_str() {
+                Some(name) => name.to_owned(),
+                None => continue,
+            };
+            applied.push(Applied {
+                target: target.to_owned(),
+                name,
+                provider: entry["provider"].as_str().map(str::to_owned),
+                path: entry["path"].as_str().map(PathBuf::from),
+                applied_at: UNIX_EPOCH
+                    + Duration::from_secs(entry["applied_at"].as_u64().unwrap_or_default()),
+            });
+        }
+        Ok(State { applied })
+    }
+
+    /// Writes the state file, creating its directory if necessary.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create: {}", dir.display()))?;
+        }
+        fs::write(path, self.to_json().pretty(2))
+            .with_context(|| format!("Failed to write: {}", path.display()))
+    }
+
+    /// Replaces the color scheme of the target.
+    pub fn record(&mut self, applied: Applied) {
+        self.applied.retain(|a| a.target != applied.target);
+        self.applied.push(applied);
+        self.applied.sort_by(|a, b| a.target.cmp(&b.target));
+    }
+
+    /// Color schemes by target, sorted by target.
+    pub fn applied(&self) -> &[Applied] {
+        &self.applied
+    }
+
+    pub fn to_json(&self) -> json::JsonValue {
+        let mut targets = json::JsonValue::new_object();
+        for applied in &self.applied {
+            let applied_at = applied
+                .applied_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            targets[applied.target.as_str()] = json::object! {
+                "name" => applied.name.as_str(),
+                "provider" => applied.provider.as_deref(),
+                "path" => applied.path.as_ref().map(|path| path.display().to_string()),
+                "applied_at" => applied_at,
+            };
+        }
+        json::object! { "targets" => targets }
+    }
+}
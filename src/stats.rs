@@ -0,0 +1,93 @@
+use crate::color::{Color, ColorScheme};
+
+/// Names of the hue buckets, in the order of `PaletteStats::hues`.
+pub const HUE_NAMES: [&str; 7] = ["red", "yellow", "green", "cyan", "blue", "magenta", "gray"];
+
+/// Colors with a lower HSL saturation than this are counted as gray.
+const GRAY_SATURATION: f64 = 0.15;
+
+/// Numbers that describe the ANSI colors of one or more color schemes.
+#[derive(Debug, PartialEq)]
+pub struct PaletteStats {
+    /// The number of color schemes summarized.
+    pub count: usize,
+    /// The average CIE L* of the ANSI colors, from 0.0 to 100.0.
+    pub brightness: f64,
+    /// The number of ANSI colors in each bucket of `HUE_NAMES`.
+    pub hues: [usize; 7],
+    /// The lowest and highest HSL saturation of the ANSI colors.
+    pub saturation: (f64, f64),
+    /// The lowest, average and highest contrast ratio of the foreground on the background.
+    pub contrast: (f64, f64, f64),
+}
+
+impl PaletteStats {
+    /// Analyzes a color scheme.
+    pub fn new(scheme: &ColorScheme) -> Self {
+        let colors = scheme.ansi_colors();
+
+        let brightness =
+            colors.iter().map(|color| color.to_lab().0).sum::<f64>() / colors.len() as f64;
+
+        let mut hues = [0; 7];
+        let mut saturation = (f64::MAX, f64::MIN);
+        for color in colors.iter() {
+            let (_, s, _) = color.to_hsl();
+            hues[hue_bucket(color)] += 1;
+            saturation = (saturation.0.min(s), saturation.1.max(s));
+        }
+
+        let contrast = scheme.foreground().contrast_ratio(scheme.background());
+
+        PaletteStats {
+            count: 1,
+            brightness,
+            hues,
+            saturation,
+            contrast: (contrast, contrast, contrast),
+        }
+    }
+
+    /// Summarizes stats of multiple color schemes.
+    ///
+    /// Returns `None` if `stats` is empty.
+    pub fn aggregate(stats: &[PaletteStats]) -> Option<Self> {
+        let first = stats.first()?;
+        let mut total = PaletteStats {
+            count: 0,
+            brightness: 0.0,
+            hues: [0; 7],
+            saturation: first.saturation,
+            contrast: (first.contrast.0, 0.0, first.contrast.2),
+        };
+        for s in stats {
+            total.count += s.count;
+            total.brightness += s.brightness * s.count as f64;
+            for (total_hue, hue) in total.hues.iter_mut().zip(s.hues.iter()) {
+                *total_hue += hue;
+            }
+            total.saturation = (
+                total.saturation.0.min(s.saturation.0),
+                total.saturation.1.max(s.saturation.1),
+            );
+            total.contrast = (
+                total.contrast.0.min(s.contrast.0),
+                total.contrast.1 + s.contrast.1 * s.count as f64,
+                total.contrast.2.max(s.contrast.2),
+            );
+        }
+        total.brightness /= total.count as f64;
+        total.contrast.1 /= total.count as f64;
+        Some(total)
+    }
+}
+
+/// Returns the index of the `HUE_NAMES` bucket that the color belongs to.
+fn hue_bucket(color: &Color) -> usize {
+    let (hue, saturation, _) = color.to_hsl();
+    if saturation < GRAY_SATURATION {
+        return 6;
+    }
+    // Each bucket spans 60 degrees centered on its primary or secondary color.
+    (((hue + 30.0) / 60.0) as usize) % 6
+}
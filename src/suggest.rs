@@ -0,0 +1,39 @@
+/// The number of characters to insert, delete or replace to turn one string into another, which
+/// is known as the Levenshtein distance.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let replace = previous[j] + if a_char == *b_char { 0 } else { 1 };
+            current[j + 1] = replace.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Names that are a few edits away from a name ignoring case, the closest first.
+///
+/// A third of the characters of the name, or at least one, can be different.
+pub fn similar_names<'a>(name: &str, names: &'a [String], limit: usize) -> Vec<&'a str> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(1);
+    let mut similar: Vec<(usize, &str)> = names
+        .iter()
+        .map(|candidate| {
+            (
+                edit_distance(&name, &candidate.to_lowercase()),
+                candidate.as_str(),
+            )
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    similar.sort();
+    similar
+        .into_iter()
+        .take(limit)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
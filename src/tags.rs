@@ -0,0 +1,114 @@
+use anyhow::{anyhow, Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::a11y::ENHANCED_TEXT_CONTRAST;
+use crate::color::ColorScheme;
+
+/// Tags that are given to color schemes by analysis, which can't be added or removed.
+pub const AUTO_TAGS: [&str; 3] = ["dark", "light", "high-contrast"];
+
+/// Tags that users attached to color schemes, kept in a JSON file.
+#[derive(Debug, Default, PartialEq)]
+pub struct Tags {
+    tags: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Tags {
+    /// The default tag file in the local data directory.
+    pub fn default_path() -> Result<PathBuf> {
+        let mut path = dirs::data_local_dir().ok_or(anyhow!("There is no data directory"))?;
+        path.push("colortty");
+        path.push("tags.json");
+        Ok(path)
+    }
+
+    /// Reads the tag file. Returns no tags if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Tags::default()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read: {}", path.display())),
+        };
+        let root = json::parse(&content)
+            .with_context(|| format!("Failed to parse: {}", path.display()))?;
+
+        let mut tags = Tags::default();
+        for (name, names) in root.entries() {
+            let names: Vec<&str> = names.members().filter_map(|tag| tag.as_str()).collect();
+            tags.add(name, &names);
+        }
+        Ok(tags)
+    }
+
+    /// Writes the tag file, creating its directory if necessary.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create: {}", dir.display()))?;
+        }
+        let mut root = json::JsonValue::new_object();
+        for (name, tags) in &self.tags {
+            let tags: Vec<&str> = tags.iter().map(String::as_str).collect();
+            root[name.as_str()] = tags.into();
+        }
+        fs::write(path, root.pretty(2))
+            .with_context(|| format!("Failed to write: {}", path.display()))
+    }
+
+    pub fn add(&mut self, name: &str, tags: &[&str]) {
+        let entry = self.tags.entry(name.to_owned()).or_default();
+        entry.extend(tags.iter().map(|tag| tag.to_lowercase()));
+    }
+
+    pub fn remove(&mut self, name: &str, tags: &[&str]) {
+        if let Some(entry) = self.tags.get_mut(name) {
+            for tag in tags {
+                entry.remove(&tag.to_lowercase());
+            }
+            if entry.is_empty() {
+                self.tags.remove(name);
+            }
+        }
+    }
+
+    /// Tags that users attached to a color scheme, sorted.
+    pub fn of(&self, name: &str) -> Vec<&str> {
+        self.tags
+            .get(name)
+            .map(|tags| tags.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// All tags that users attached, with the numbers of color schemes.
+    pub fn counts(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for tags in self.tags.values() {
+            for tag in tags {
+                *counts.entry(tag.as_str()).or_default() += 1;
+            }
+        }
+        counts
+    }
+
+    /// Tags of a color scheme from both users and analysis, sorted.
+    pub fn all_of(&self, name: &str, scheme: &ColorScheme) -> Vec<String> {
+        let mut tags: BTreeSet<String> = auto_tags(scheme)
+            .iter()
+            .map(|&tag| tag.to_owned())
+            .collect();
+        tags.extend(self.of(name).into_iter().map(str::to_owned));
+        tags.into_iter().collect()
+    }
+}
+
+/// Tags given by analysis: `dark` or `light`, and `high-contrast` if the foreground meets WCAG AAA
+/// on the background.
+pub fn auto_tags(scheme: &ColorScheme) -> Vec<&'static str> {
+    let mut tags = vec![if scheme.is_dark() { "dark" } else { "light" }];
+    if scheme.foreground().contrast_ratio(scheme.background()) >= ENHANCED_TEXT_CONTRAST {
+        tags.push("high-contrast");
+    }
+    tags
+}
@@ -0,0 +1,194 @@
+#[cfg(unix)]
+use crate::color::ColorScheme;
+#[cfg(unix)]
+use anyhow::{bail, Context, Result};
+#[cfg(unix)]
+use std::fs::File;
+#[cfg(unix)]
+use std::io::{Read, Write};
+
+/// Dynamic colors of OSC 10, 11 and 12 that a palette query asks for besides the 16 ANSI colors.
+const DYNAMIC_COLORS: [u8; 3] = [10, 11, 12];
+
+/// Escape sequences that ask the terminal for its 16 ANSI colors, foreground, background and cursor
+/// color, followed by a request for the device attributes.
+///
+/// Terminals that don't answer color queries still answer the device attributes, so the answer to
+/// it marks the end of the answers.
+pub fn palette_query() -> String {
+    let mut query = String::new();
+    for index in 0..16 {
+        query.push_str(&format!("\x1b]4;{};?\x1b\\", index));
+    }
+    for code in DYNAMIC_COLORS.iter() {
+        query.push_str(&format!("\x1b]{};?\x1b\\", code));
+    }
+    query.push_str("\x1b[c");
+    query
+}
+
+/// Turns the answers to `palette_query` into escape sequences that set the colors back.
+///
+/// Answers are terminated by either ST or BEL. Colors that the terminal didn't answer are reset to
+/// its defaults instead.
+pub fn restore_sequence(replies: &str) -> String {
+    let mut ansi: [Option<&str>; 16] = [None; 16];
+    let mut dynamic: [Option<&str>; 3] = [None; 3];
+    for reply in replies.split("\x1b]").skip(1) {
+        let end = [reply.find('\x07'), reply.find("\x1b\\")]
+            .iter()
+            .flatten()
+            .min()
+            .copied()
+            .unwrap_or(reply.len());
+        let mut parts = reply[..end].splitn(3, ';');
+        let (code, index) = match parts.next() {
+            Some("4") => ("4", parts.next().and_then(|i| i.parse::<usize>().ok())),
+            code => (code.unwrap_or_default(), None),
+        };
+        let spec = match parts.next() {
+            Some(spec) if spec.starts_with("rgb:") => spec,
+            _ => continue,
+        };
+        match (code, index) {
+            ("4", Some(index)) if index < ansi.len() => ansi[index] = Some(spec),
+            ("10", _) => dynamic[0] = Some(spec),
+            ("11", _) => dynamic[1] = Some(spec),
+            ("12", _) => dynamic[2] = Some(spec),
+            _ => {}
+        }
+    }
+
+    let mut sequence = String::new();
+    for (index, spec) in ansi.iter().enumerate() {
+        match spec {
+            Some(spec) => sequence.push_str(&format!("\x1b]4;{};{}\x1b\\", index, spec)),
+            None => sequence.push_str(&format!("\x1b]104;{}\x1b\\", index)),
+        }
+    }
+    for (code, spec) in DYNAMIC_COLORS.iter().zip(dynamic.iter()) {
+        match spec {
+            Some(spec) => sequence.push_str(&format!("\x1b]{};{}\x1b\\", code, spec)),
+            None => sequence.push_str(&format!("\x1b]1{}\x1b\\", code)),
+        }
+    }
+    sequence
+}
+
+/// Applies a color scheme to the controlling terminal until a key is pressed, and then sets the
+/// colors of the terminal back to what they were.
+#[cfg(unix)]
+pub fn try_on(scheme: &ColorScheme, name: &str) -> Result<()> {
+    let mut tty = open_tty()?;
+    let mut raw = RawMode::enable(&tty, "", String::new())?;
+
+    // Wait for the answers for half a second at most.
+    stty(&tty, &["min", "0", "time", "5"]).context("Failed to set the terminal timeout")?;
+    tty.write_all(palette_query().as_bytes())
+        .context("Failed to write to the terminal")?;
+    let mut replies = Vec::new();
+    let mut buffer = [0; 1024];
+    while !has_device_attributes(&replies) {
+        let length = tty
+            .read(&mut buffer)
+            .context("Failed to read the terminal")?;
+        if length == 0 {
+            break;
+        }
+        replies.extend_from_slice(&buffer[..length]);
+    }
+    raw.restore = restore_sequence(&String::from_utf8_lossy(&replies));
+
+    let mut screen = scheme.to_osc();
+    screen.push_str(&format!(
+        "Trying {}. Press any key to restore the colors.\r\n",
+        name
+    ));
+    for row in [40, 100].iter() {
+        for index in 0..8 {
+            screen.push_str(&format!("\x1b[{}m    ", row + index));
+        }
+        screen.push_str("\x1b[0m\r\n");
+    }
+    tty.write_all(screen.as_bytes())
+        .context("Failed to write to the terminal")?;
+
+    stty(&tty, &["min", "1", "time", "0"]).context("Failed to set the terminal timeout")?;
+    if tty
+        .read(&mut buffer)
+        .context("Failed to read the terminal")?
+        == 0
+    {
+        bail!("The terminal was closed");
+    }
+    Ok(())
+}
+
+/// Whether the bytes contain an answer to the device attributes request like `ESC [ ? 62 ; 22 c`.
+#[cfg(unix)]
+fn has_device_attributes(bytes: &[u8]) -> bool {
+    bytes.windows(3).enumerate().any(|(start, window)| {
+        window == b"\x1b[?"
+            && bytes[start + 3..]
+                .iter()
+                .find(|b| !(b.is_ascii_digit() || **b == b';'))
+                == Some(&b'c')
+    })
+}
+
+/// Opens the controlling terminal for reading and writing.
+#[cfg(unix)]
+pub(crate) fn open_tty() -> Result<File> {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .context("Failed to open the terminal")
+}
+
+/// Puts the terminal in raw mode, and restores it when dropped.
+#[cfg(unix)]
+pub(crate) struct RawMode {
+    tty: File,
+    saved: String,
+    /// Written to the terminal when dropped, before the settings are restored.
+    pub(crate) restore: String,
+}
+
+#[cfg(unix)]
+impl RawMode {
+    pub(crate) fn enable(tty: &File, enter: &str, restore: String) -> Result<Self> {
+        let saved = stty(tty, &["-g"]).context("Failed to get the terminal settings")?;
+        stty(tty, &["raw", "-echo"]).context("Failed to set the terminal in raw mode")?;
+        let mut tty = tty.try_clone()?;
+        tty.write_all(enter.as_bytes())?;
+        Ok(RawMode {
+            tty,
+            saved: saved.trim().to_owned(),
+            restore,
+        })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = self.tty.write_all(self.restore.as_bytes());
+        let _ = stty(&self.tty, &[self.saved.as_str()]);
+    }
+}
+
+/// Runs `stty` on the terminal and returns what it printed.
+#[cfg(unix)]
+pub(crate) fn stty(tty: &File, args: &[&str]) -> std::io::Result<String> {
+    let output = std::process::Command::new("stty")
+        .args(args)
+        .stdin(tty.try_clone()?)
+        .output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod a11y_tests {
+    use colortty::a11y::{contrast_ratios, Category, Deficiency, Report, Severity};
+    use colortty::{Color, ColorScheme};
+    use std::fs;
+
+    #[test]
+    fn simulate_keeps_grays() {
+        let gray = Color::from_mintty_color("128,128,128").unwrap();
+        for deficiency in Deficiency::all().iter() {
+            assert!(deficiency.simulate(&gray).delta_e(&gray) < 1.0);
+        }
+    }
+
+    #[test]
+    fn report_passes() {
+        let content = fs::read_to_string("tests/fixtures/Dracula.itermcolors").unwrap();
+        let report = Report::new(&ColorScheme::from_iterm(&content).unwrap());
+        assert!(report.passed());
+        assert_eq!(report.count(Severity::Warning), 0);
+    }
+
+    #[test]
+    fn report_fails() {
+        let content = "ForegroundColour=40,40,40
+BackgroundColour=0,0,0
+Red=200,60,60
+Green=200,60,60";
+        let report = Report::new(&ColorScheme::from_minttyrc(content).unwrap());
+        assert!(!report.passed());
+        assert!(report
+            .checks
+            .iter()
+            .any(|c| c.category == Category::Contrast && c.severity == Severity::Failure));
+        assert!(report
+            .checks
+            .iter()
+            .any(|c| c.category == Category::ColorBlindness && c.severity == Severity::Failure));
+    }
+
+    #[test]
+    fn contrast_ratios_of_slots() {
+        let content = "ForegroundColour=255,255,255
+BackgroundColour=0,0,0
+CursorColour=255,255,255
+Red=128,0,0";
+        let contrasts = contrast_ratios(&ColorScheme::from_minttyrc(content).unwrap());
+        // No cursor text without the color of the text.
+        assert_eq!(contrasts.len(), 17);
+        assert_eq!(contrasts[0].slot, "foreground");
+        assert!((contrasts[0].ratio - 21.0).abs() < 0.01);
+        assert!(contrasts[0].is_text);
+        // Black is exempt in a dark color scheme.
+        assert_eq!(contrasts[1].slot, "black");
+        assert!(contrasts[1].is_exempt);
+        assert_eq!(contrasts[2].slot, "red");
+        assert!(contrasts[2].ratio < 3.0);
+        assert!(!contrasts[2].is_text && !contrasts[2].is_exempt);
+    }
+}
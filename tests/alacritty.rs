@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod alacritty_tests {
+    use colortty::alacritty::import_theme;
+    use std::path::Path;
+
+    const THEME: &str = "/home/me/.config/alacritty/themes/Dracula.toml";
+
+    #[test]
+    fn import_in_new_general_table() {
+        let config = "[window]\nopacity = 0.9\n";
+        assert_eq!(
+            import_theme(config, Path::new(THEME)).unwrap(),
+            format!(
+                "[window]\nopacity = 0.9\n\n[general]\nimport = [\"{}\"]\n",
+                THEME
+            )
+        );
+        assert_eq!(
+            import_theme("", Path::new(THEME)).unwrap(),
+            format!("[general]\nimport = [\"{}\"]\n", THEME)
+        );
+    }
+
+    #[test]
+    fn replace_import_of_another_theme() {
+        let config = "[general]
+live_config_reload = true
+import = [
+  \"/home/me/.config/alacritty/keys.toml\",
+  \"/home/me/.config/alacritty/themes/Nord.toml\",
+]
+
+[font]
+size = 12
+";
+        assert_eq!(
+            import_theme(config, Path::new(THEME)).unwrap(),
+            format!(
+                "[general]
+live_config_reload = true
+import = [\"/home/me/.config/alacritty/keys.toml\", \"{}\"]
+
+[font]
+size = 12
+",
+                THEME
+            )
+        );
+    }
+
+    #[test]
+    fn replace_top_level_import() {
+        let config =
+            "import = [\"/home/me/.config/alacritty/themes/Nord.toml\"]\n\n[font]\nsize = 12\n";
+        assert_eq!(
+            import_theme(config, Path::new(THEME)).unwrap(),
+            format!("import = [\"{}\"]\n\n[font]\nsize = 12\n", THEME)
+        );
+    }
+
+    #[test]
+    fn import_after_general_header() {
+        let config = "[general]\nlive_config_reload = true\n";
+        assert_eq!(
+            import_theme(config, Path::new(THEME)).unwrap(),
+            format!(
+                "[general]\nimport = [\"{}\"]\nlive_config_reload = true\n",
+                THEME
+            )
+        );
+    }
+
+    #[test]
+    fn remove_inline_colors() {
+        let config = "[colors.primary]
+background = \"#000000\"
+foreground = \"#ffffff\"
+
+[colors.normal]
+black = \"#000000\"
+
+# Fonts
+[font]
+size = 12
+";
+        assert_eq!(
+            import_theme(config, Path::new(THEME)).unwrap(),
+            format!(
+                "# Fonts
+[font]
+size = 12
+
+[general]
+import = [\"{}\"]
+",
+                THEME
+            )
+        );
+    }
+
+    #[test]
+    fn invalid_config() {
+        assert!(import_theme("[general", Path::new(THEME)).is_err());
+    }
+}
@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod appearance_tests {
+    use colortty::appearance::{from_gnome_color_scheme, from_macos_defaults, Appearance};
+
+    #[test]
+    fn macos_defaults() {
+        assert_eq!(from_macos_defaults(true, "Dark\n"), Appearance::Dark);
+        // The key doesn't exist in light mode.
+        assert_eq!(
+            from_macos_defaults(false, "The domain/default pair does not exist\n"),
+            Appearance::Light
+        );
+    }
+
+    #[test]
+    fn gnome_color_scheme() {
+        assert_eq!(
+            from_gnome_color_scheme("'prefer-dark'\n"),
+            Some(Appearance::Dark)
+        );
+        assert_eq!(
+            from_gnome_color_scheme("'prefer-light'\n"),
+            Some(Appearance::Light)
+        );
+        assert_eq!(
+            from_gnome_color_scheme("'default'\n"),
+            Some(Appearance::Light)
+        );
+        assert_eq!(from_gnome_color_scheme(""), None);
+    }
+}
@@ -7,11 +7,7 @@ mod color_tests {
         fn from_mintty_color_works() {
             assert_eq!(
                 Color::from_mintty_color("12,3,255").unwrap(),
-                Color {
-                    red: 12,
-                    green: 3,
-                    blue: 255
-                }
+                Color::from_rgb8(12, 3, 255)
             );
         }
 
@@ -26,21 +22,137 @@ mod color_tests {
         }
 
         #[test]
-        fn to_hex() {
+        fn from_hex() {
+            let color = Color::from_rgb8(123, 4, 255);
+            assert_eq!(Color::from_hex("#7B04ff").unwrap(), color);
+            assert_eq!(Color::from_hex("#7b04ff80").unwrap(), color);
+            assert_eq!(
+                Color::from_hex("#7bf").unwrap(),
+                Color::from_rgb8(0x77, 0xbb, 0xff)
+            );
+            assert!(Color::from_hex("7b04ff").is_err());
+            assert!(Color::from_hex("#7b04f").is_err());
+            assert!(Color::from_hex("#7b04fg").is_err());
+        }
+
+        #[test]
+        fn from_css_rgb() {
+            let color = Color::from_rgb8(40, 42, 54);
+            assert_eq!(Color::from_css_rgb("rgb(40,42,54)").unwrap(), color);
+            assert_eq!(Color::from_css_rgb("rgb(40, 42, 54)").unwrap(), color);
+            assert_eq!(Color::from_css_rgb("rgb(40 42 54 / 0.5)").unwrap(), color);
+            assert_eq!(Color::from_css_rgb("rgba(40, 42, 54, 0.5)").unwrap(), color);
+            assert_eq!(
+                Color::from_css_rgb("rgb(100%, 0%, 50%)").unwrap(),
+                Color::from_rgb8(255, 0, 128)
+            );
+            assert!(Color::from_css_rgb("rgb(40, 42)").is_err());
+            assert!(Color::from_css_rgb("rgb(40, 42, 256)").is_err());
+            assert!(Color::from_css_rgb("hsl(0, 0%, 0%)").is_err());
+        }
+
+        #[test]
+        fn from_str() {
+            let color = Color::from_rgb8(40, 42, 54);
+            assert_eq!("#282a36".parse::<Color>().unwrap(), color);
+            assert_eq!("0x282a36".parse::<Color>().unwrap(), color);
+            assert_eq!(" rgb(40, 42, 54) ".parse::<Color>().unwrap(), color);
+            assert!("DarkSlateGray".parse::<Color>().is_err());
+        }
+
+        #[test]
+        fn from_x11() {
+            let dark_slate_gray = Color::from_rgb8(47, 79, 79);
+            assert_eq!(Color::from_x11("DarkSlateGray").unwrap(), dark_slate_gray);
+            assert_eq!(Color::from_x11("dark slate gray").unwrap(), dark_slate_gray);
+            assert_eq!(Color::from_x11("rgb:2f/4f/4f").unwrap(), dark_slate_gray);
+            assert_eq!(
+                Color::from_x11("rgb:2f2f/4f4f/4f4f").unwrap(),
+                dark_slate_gray
+            );
+            assert_eq!(Color::from_x11("#2f4f4f").unwrap(), dark_slate_gray);
+            assert_eq!(
+                Color::from_x11("rgb:f/0/8").unwrap(),
+                Color::from_rgb8(255, 0, 136)
+            );
+            assert_eq!(
+                Color::from_x11("#f08").unwrap(),
+                Color::from_rgb8(255, 0, 136)
+            );
+            assert!(Color::from_x11("no such color").is_err());
+            assert!(Color::from_x11("rgb:ff/ff").is_err());
+            assert!(Color::from_x11("#ff00f").is_err());
+            assert_ne!(
+                Color::from_x11("rgb:2f2e/4f4f/4f4f").unwrap(),
+                dark_slate_gray
+            );
+        }
+
+        #[test]
+        fn from_real_rgb() {
+            let color = Color::from_real_rgb(0.5, 0.0, 1.0);
+            assert_eq!(color.red, 32768);
+            assert_eq!(color.blue, 65535);
+            assert_eq!(color.to_rgb8(), (128, 0, 255));
+        }
+
+        #[test]
+        fn to_rgb8_rounds() {
             assert_eq!(
-                Color {
-                    red: 123,
-                    green: 4,
-                    blue: 255
-                }
-                .to_hex(),
-                "0x7b04ff"
+                Color::from_x11("rgb:1e1f/1f45/28e7").unwrap().to_rgb8(),
+                (30, 31, 41)
             );
+            assert_eq!(Color::from_rgb8(30, 31, 40).to_rgb8(), (30, 31, 40));
+        }
+
+        #[test]
+        fn to_x11_rgb() {
+            assert_eq!(
+                Color::from_x11("rgb:1e1f/1f45/28e7").unwrap().to_x11_rgb(),
+                "rgb:1e1f/1f45/28e7"
+            );
+            assert_eq!(
+                Color::from_rgb8(255, 85, 0).to_x11_rgb(),
+                "rgb:ffff/5555/0000"
+            );
+        }
+
+        #[test]
+        fn to_hex() {
+            assert_eq!(Color::from_rgb8(123, 4, 255).to_hex(), "0x7b04ff");
+        }
+
+        #[test]
+        fn delta_e() {
+            let black = Color::from_mintty_color("0,0,0").unwrap();
+            let white = Color::from_mintty_color("255,255,255").unwrap();
+            assert_eq!(black.delta_e(&black), 0.0);
+            assert!((black.delta_e(&white) - 100.0).abs() < 0.01);
+        }
+
+        #[test]
+        fn contrast_ratio() {
+            let black = Color::from_mintty_color("0,0,0").unwrap();
+            let white = Color::from_mintty_color("255,255,255").unwrap();
+            assert_eq!(black.contrast_ratio(&black), 1.0);
+            assert!((black.contrast_ratio(&white) - 21.0).abs() < 0.01);
+            assert_eq!(black.contrast_ratio(&white), white.contrast_ratio(&black));
+        }
+
+        #[test]
+        fn to_hsl() {
+            let (hue, saturation, lightness) =
+                Color::from_mintty_color("255,0,0").unwrap().to_hsl();
+            assert_eq!((hue, saturation, lightness), (0.0, 1.0, 0.5));
+            let (hue, _, _) = Color::from_mintty_color("0,0,255").unwrap().to_hsl();
+            assert_eq!(hue, 240.0);
         }
     }
 
     mod color_scheme {
-        use colortty::ColorScheme;
+        use colortty::{
+            AlacrittyConfigFormat, Color, ColorScheme, ColorSchemeFormat, OutputFormat,
+        };
         use std::fs::File;
         use std::io::Read;
 
@@ -50,7 +162,7 @@ mod color_tests {
                 .unwrap()
                 .read_to_string(&mut fixture)
                 .unwrap();
-            return fixture;
+            fixture
         }
 
         #[test]
@@ -89,13 +201,49 @@ mod color_tests {
             assert_eq!(scheme.to_yaml(), dracula_alacritty);
         }
 
+        #[test]
+        fn convert_iterm_to_toml() {
+            let dracula_iterm = read_fixture("tests/fixtures/Dracula.itermcolors");
+            let dracula_alacritty: String = "[colors.primary]
+background = '0x1e1f29'
+foreground = '0xf8f8f2'
+
+[colors.cursor]
+text = '0xffffff'
+cursor = '0xbbbbbb'
+
+[colors.normal]
+black = '0x000000'
+red = '0xff5555'
+green = '0x50fa7b'
+yellow = '0xf1fa8c'
+blue = '0xbd93f9'
+magenta = '0xff79c6'
+cyan = '0x8be9fd'
+white = '0xbbbbbb'
+
+[colors.bright]
+black = '0x555555'
+red = '0xff5555'
+green = '0x50fa7b'
+yellow = '0xf1fa8c'
+blue = '0xbd93f9'
+magenta = '0xff79c6'
+cyan = '0x8be9fd'
+white = '0xffffff'
+"
+            .to_string();
+            let scheme = ColorScheme::from_iterm(&dracula_iterm).unwrap();
+            assert_eq!(scheme.to_toml(), dracula_alacritty);
+        }
+
         #[test]
         fn convert_iterm() {
             let dracula_iterm = read_fixture("tests/fixtures/Dracula.itermcolors");
             let dracula_alacritty: String = "colors:
   # Default colors
   primary:
-    background: '0x1e1f28'
+    background: '0x1e1f29'
     foreground: '0xf8f8f2'
 
   # Cursor colors
@@ -137,13 +285,13 @@ mod color_tests {
             let firewatch_alacritty: String = "colors:
   # Default colors
   primary:
-    background: '0xf8f6f2'
-    foreground: '0x75541b'
+    background: '0xf9f6f2'
+    foreground: '0x75551c'
 
   # Cursor colors
   cursor:
     text:   '0xd5deff'
-    cursor: '0xda4181'
+    cursor: '0xda4282'
 
   # Normal colors
   normal:
@@ -171,6 +319,63 @@ mod color_tests {
             assert_eq!(scheme.to_yaml(), firewatch_alacritty);
         }
 
+        #[test]
+        fn convert_minttyrc_with_other_keys() {
+            let content = "# Theme
+ForegroundColour=248,248,242
+BackgroundColour=40,42,54
+CursorColour=255,121,198
+IMECursorColour=80,250,123
+BoldColour=255,255,255
+BoldAsColour=yes
+Font=Consolas
+
+Red=255,85,85
+";
+            let scheme = ColorScheme::from_minttyrc(content).unwrap();
+            assert_eq!(scheme.cursor(), Some(&Color::from_rgb8(255, 121, 198)));
+            assert_eq!(scheme.ansi_colors()[1], &Color::from_rgb8(255, 85, 85));
+            assert_eq!(
+                scheme.extended_color("ime_cursor"),
+                Some(&Color::from_rgb8(80, 250, 123))
+            );
+            assert_eq!(
+                scheme.extended_color("bold"),
+                Some(&Color::from_rgb8(255, 255, 255))
+            );
+            assert_eq!(scheme.extended_colors().len(), 2);
+        }
+
+        #[test]
+        fn xterm_256() {
+            assert_eq!(Color::from_rgb8(0, 0, 0).to_xterm_256(), 16);
+            assert_eq!(Color::from_rgb8(255, 255, 255).to_xterm_256(), 231);
+            assert_eq!(Color::from_rgb8(255, 0, 0).to_xterm_256(), 196);
+            assert_eq!(Color::from_rgb8(95, 135, 175).to_xterm_256(), 67);
+            assert_eq!(Color::from_rgb8(128, 128, 128).to_xterm_256(), 244);
+        }
+
+        #[test]
+        fn dominant_hue() {
+            let dracula =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let hue = dracula.dominant_hue().unwrap();
+            assert!((200.0..250.0).contains(&hue), "{}", hue);
+
+            let gray =
+                ColorScheme::from_minttyrc("BackgroundColour=0,0,0\nForegroundColour=200,200,200")
+                    .unwrap();
+            assert_eq!(gray.dominant_hue(), None);
+        }
+
+        #[test]
+        fn convert_minttyrc_invalid_color() {
+            assert!(ColorScheme::from_minttyrc("Red=255,85").is_err());
+            assert!(ColorScheme::from_minttyrc("CursorColour=oops,0,0").is_err());
+            assert!(ColorScheme::from_minttyrc("Red").is_err());
+        }
+
         #[test]
         fn convert_gogh() {
             let dracula_gogh = read_fixture("tests/fixtures/dracula.sh");
@@ -206,5 +411,1020 @@ mod color_tests {
             let scheme = ColorScheme::from_gogh(&dracula_gogh).unwrap();
             assert_eq!(scheme.to_yaml(), dracula_alacritty);
         }
+
+        #[test]
+        fn convert_gogh_yaml() {
+            let sh = ColorScheme::from_gogh(&read_fixture("tests/fixtures/dracula.sh")).unwrap();
+            let yml = read_fixture("tests/fixtures/dracula.yml");
+            assert!(matches!(
+                ColorSchemeFormat::from_content(&yml),
+                Some(ColorSchemeFormat::Gogh)
+            ));
+            let scheme = ColorScheme::from_gogh(&yml).unwrap();
+            assert_eq!(scheme.fingerprint(), sh.fingerprint());
+            assert_eq!(scheme.cursor(), Some(sh.foreground()));
+        }
+
+        #[test]
+        fn convert_gogh_variations() {
+            let content = "export BACKGROUND_COLOR='#282a36'
+  export   FOREGROUND_COLOR = \"#94A3A5\"  # Text
+COLOR_01=#44475a
+export COLOR_02=\"#ff555\"
+export CURSOR_COLOR=\"$FOREGROUND_COLOR\"
+";
+            let scheme = ColorScheme::from_gogh(content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(scheme.foreground(), &Color::from_hex("#94a3a5").unwrap());
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[0], &Color::from_hex("#44475a").unwrap());
+            assert_eq!(colors[1], &Color::default());
+        }
+
+        #[test]
+        fn full_config() {
+            let mut scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let plain = scheme.to_toml();
+            scheme.derive_ui_colors();
+            let toml = scheme.to_toml();
+            assert!(toml.starts_with(&plain));
+            assert_eq!(
+                &toml[plain.len()..],
+                "
+[colors.selection]
+text = '0x1e1f29'
+background = '0xf8f8f2'
+
+[colors.search.matches]
+foreground = '0x1e1f29'
+background = '0xf1fa8c'
+
+[colors.search.focused_match]
+foreground = '0x1e1f29'
+background = '0x50fa7b'
+
+[colors.hints.start]
+foreground = '0x1e1f29'
+background = '0xf1fa8c'
+
+[colors.hints.end]
+foreground = '0xf1fa8c'
+background = '0x1e1f29'
+"
+            );
+
+            let yaml = scheme.to_yaml();
+            assert!(yaml.contains(
+                "
+  # Search colors
+  search:
+    matches:
+      foreground: '0x1e1f29'
+      background: '0xf1fa8c'
+    focused_match:
+"
+            ));
+        }
+
+        #[test]
+        fn full_config_keeps_parsed_colors() {
+            let mut scheme = ColorScheme::from_minttyrc(
+                "HighlightBackgroundColour=1,2,3\nBackgroundColour=0,0,0",
+            )
+            .unwrap();
+            scheme.derive_ui_colors();
+            assert_eq!(
+                scheme.extended_color("selection_background"),
+                Some(&Color::from_rgb8(1, 2, 3))
+            );
+        }
+
+        #[test]
+        fn windows_terminal_fragment() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let fragment = scheme.to_windows_terminal_fragment("Dracula");
+            let value = json::parse(&fragment).unwrap();
+            assert_eq!(value["schemes"][0]["name"], "Dracula");
+            assert_eq!(value["schemes"][0]["background"], "#1e1f29");
+            assert_eq!(value["schemes"][0]["brightPurple"], "#ff79c6");
+
+            // The fragment can be read as settings again.
+            let schemes = ColorScheme::from_windows_terminal_settings(&fragment).unwrap();
+            assert_eq!(schemes[0].0, "Dracula");
+            assert_eq!(schemes[0].1.fingerprint(), scheme.fingerprint());
+        }
+
+        #[test]
+        fn windows_terminal_scheme() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let output = scheme.to_windows_terminal("Dracula");
+            let value = json::parse(&output).unwrap();
+            assert_eq!(value["name"], "Dracula");
+            assert_eq!(value["background"], "#1e1f29");
+            assert_eq!(value["cursorColor"], "#bbbbbb");
+            assert_eq!(value["brightBlack"], "#555555");
+            assert_eq!(value["purple"], "#ff79c6");
+
+            // It can be read back.
+            let read = ColorScheme::from_windows_terminal(&output).unwrap();
+            assert_eq!(read.ansi_colors(), scheme.ansi_colors());
+            assert_eq!(OutputFormat::WindowsTerminal.comment("Dracula"), None);
+        }
+
+        #[test]
+        fn i3_colors() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let i3 = scheme.to_i3();
+            assert!(
+                i3.contains("client.focused          #bd93f9 #bd93f9 #1e1f29 #8be9fd #bd93f9\n")
+            );
+            assert!(i3.contains("client.background       #1e1f29\n"));
+            assert!(i3.contains("    urgent_workspace   #ff5555 #ff5555 #1e1f29\n"));
+        }
+
+        #[test]
+        fn polybar_and_waybar_colors() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let polybar = scheme.to_polybar();
+            assert!(polybar.starts_with("[colors]\nforeground = #f8f8f2\nbackground = #1e1f29\n"));
+            assert!(polybar.contains("\nbright_magenta = #ff79c6\n"));
+            assert_eq!(polybar.lines().count(), 20);
+
+            let waybar = scheme.to_waybar_css();
+            assert!(waybar.starts_with("@define-color foreground #f8f8f2;\n"));
+            assert!(waybar.contains("\n@define-color bright_magenta #ff79c6;\n"));
+        }
+
+        #[test]
+        fn rofi_theme() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let rofi = scheme.to_rofi();
+            assert!(rofi.starts_with("* {\n    foreground: #f8f8f2;\n    background: #1e1f29;\n"));
+            assert!(rofi.contains("\n    selected-normal-foreground: #1e1f29;\n    selected-normal-background: @blue;\n"));
+            assert!(rofi.ends_with("}\n"));
+
+            let format = OutputFormat::from_string("rofi").unwrap();
+            assert_eq!(format.extension(), "rasi");
+            assert_eq!(format.comment("Dracula").unwrap(), "// Dracula");
+            assert_eq!(scheme.serialize(&format, "Dracula"), rofi);
+        }
+
+        #[test]
+        fn notification_daemon_colors() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let dunst = scheme.to_dunst();
+            assert!(dunst.contains(
+                "[urgency_critical]
+    background = \"#1e1f29\"
+    foreground = \"#f8f8f2\"
+    frame_color = \"#ff5555\"
+"
+            ));
+            assert_eq!(dunst.matches("[urgency_").count(), 3);
+
+            let mako = scheme.to_mako();
+            assert!(mako.starts_with("background-color=#1e1f29\ntext-color=#f8f8f2\n"));
+            assert!(mako.ends_with("[urgency=critical]\nborder-color=#ff5555\n"));
+        }
+
+        #[test]
+        fn jetbrains_color_scheme() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let icls = scheme.to_jetbrains("Dracula & Co");
+            assert!(icls.starts_with(
+                "<scheme name=\"Dracula &amp; Co\" version=\"142\" parent_scheme=\"Darcula\">
+  <colors>
+    <option name=\"CONSOLE_BACKGROUND_KEY\" value=\"1e1f29\" />
+  </colors>
+"
+            ));
+            assert!(icls.contains(
+                "    <option name=\"CONSOLE_MAGENTA_BRIGHT_OUTPUT\">
+      <value>
+        <option name=\"FOREGROUND\" value=\"ff79c6\" />
+      </value>
+    </option>
+"
+            ));
+            assert_eq!(icls.matches("name=\"FOREGROUND\"").count(), 17);
+        }
+
+        #[test]
+        fn cmd_registry_file() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let reg = scheme.to_cmd_reg();
+            assert!(reg.starts_with(
+                "Windows Registry Editor Version 5.00\r\n\r\n[HKEY_CURRENT_USER\\Console]\r\n\"ColorTable00\"=dword:00291f1e\r\n"
+            ));
+            // Blue comes before red in the console.
+            assert!(reg.contains("\"ColorTable01\"=dword:00f993bd\r\n"));
+            assert!(reg.contains("\"ColorTable07\"=dword:00f2f8f8\r\n"));
+            assert!(reg.contains("\"ColorTable13\"=dword:00c679ff\r\n"));
+            assert!(reg.ends_with(
+                "\"ScreenColors\"=dword:00000007\r\n\"PopupColors\"=dword:00000070\r\n"
+            ));
+            assert_eq!(OutputFormat::CmdReg.comment("Dracula"), None);
+        }
+
+        #[test]
+        fn kitty_theme() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let kitty = scheme.to_kitty();
+            assert!(kitty.starts_with(
+                "foreground           #f8f8f2\nbackground           #1e1f29\ncursor               #bbbbbb\n"
+            ));
+            assert!(kitty.contains("\ncolor4               #bd93f9\n"));
+            assert!(kitty.ends_with("\ncolor15              #ffffff\n"));
+            assert!(matches!(
+                OutputFormat::from_string("kitty"),
+                Some(OutputFormat::Kitty)
+            ));
+        }
+
+        #[test]
+        fn wezterm_color_scheme() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let output = scheme.to_wezterm();
+            assert!(output.starts_with(
+                "[colors]\nforeground = '#f8f8f2'\nbackground = '#1e1f29'\ncursor_bg = '#bbbbbb'\n"
+            ));
+            assert!(output.contains("\nbrights = ['#555555', "));
+
+            // It can be read back.
+            let read = ColorScheme::from_wezterm(&output).unwrap();
+            assert_eq!(read.ansi_colors(), scheme.ansi_colors());
+            assert_eq!(read.cursor(), scheme.cursor());
+        }
+
+        #[test]
+        fn foot_colors() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let output = scheme.to_foot();
+            assert!(output.starts_with("[colors]\nforeground=f8f8f2\nbackground=1e1f29\n"));
+            assert!(output.contains("\nregular4=bd93f9\n"));
+            assert!(output.contains("\nbright7=ffffff\n"));
+            assert!(output.ends_with("\n[cursor]\ncolor=ffffff bbbbbb\n"));
+
+            // It can be read back.
+            let read = ColorScheme::from_foot(&output).unwrap();
+            assert_eq!(read.ansi_colors(), scheme.ansi_colors());
+            assert_eq!(read.cursor(), scheme.cursor());
+        }
+
+        #[test]
+        fn ghostty_theme() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let output = scheme.to_ghostty();
+            assert!(output.starts_with("palette = 0=#000000\npalette = 1=#ff5555\n"));
+            assert!(output.contains("\npalette = 15=#ffffff\nbackground = #1e1f29\n"));
+            assert!(output.contains("\ncursor-color = #bbbbbb\n"));
+
+            // It can be read back.
+            let read = ColorScheme::from_ghostty(&output).unwrap();
+            assert_eq!(read.ansi_colors(), scheme.ansi_colors());
+            assert_eq!(read.cursor(), scheme.cursor());
+        }
+
+        #[test]
+        fn konsole_color_scheme() {
+            let content = read_fixture("tests/fixtures/Dracula.colorscheme");
+            let scheme = ColorScheme::from_konsole(&content).unwrap();
+            let output = scheme.to_konsole("Dracula");
+            assert!(output.starts_with("[Background]\nColor=40,42,54\n\n[BackgroundFaint]\n"));
+            assert!(output.contains("\n[Color4Intense]\nColor=202,169,250\n"));
+            assert!(output.contains("\n[ForegroundIntense]\nColor=255,255,255\n"));
+            assert!(output.ends_with("[General]\nDescription=Dracula\nOpacity=1\n"));
+
+            // It can be read back.
+            let read = ColorScheme::from_konsole(&output).unwrap();
+            assert_eq!(read.ansi_colors(), scheme.ansi_colors());
+            assert_eq!(read.extended_colors(), scheme.extended_colors());
+        }
+
+        #[test]
+        fn iterm_color_preset() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let output = scheme.to_iterm();
+            assert!(output.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+            assert!(output.contains("\t<key>Ansi 1 Color</key>\n\t<dict>\n\t\t<key>Alpha Component</key>\n\t\t<real>1</real>\n\t\t<key>Blue Component</key>\n\t\t<real>0.3333333333333333</real>\n"));
+            assert!(output.ends_with("</dict>\n</plist>\n"));
+
+            // It can be read back without losing precision.
+            let read = ColorScheme::from_iterm(&output).unwrap();
+            assert_eq!(read.ansi_colors(), scheme.ansi_colors());
+            assert_eq!(read.background(), scheme.background());
+            assert_eq!(read.cursor(), scheme.cursor());
+        }
+
+        #[test]
+        fn minttyrc() {
+            let content = read_fixture("tests/fixtures/Dracula.minttyrc");
+            let scheme = ColorScheme::from_minttyrc(&content).unwrap();
+            let output = scheme.to_minttyrc();
+            assert!(output.starts_with("ForegroundColour=248,248,242\nBackgroundColour=40,42,54\nBlack=0,0,0\nBoldBlack=40,42,53\n"));
+            assert!(output.ends_with("White=191,191,191\nBoldWhite=230,230,230\n"));
+
+            // It can be read back.
+            let read = ColorScheme::from_minttyrc(&output).unwrap();
+            assert_eq!(read.ansi_colors(), scheme.ansi_colors());
+            assert!(matches!(
+                OutputFormat::from_string("wsltty"),
+                Some(OutputFormat::Mintty)
+            ));
+        }
+
+        #[test]
+        fn tmux_styles() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let output = scheme.to_tmux();
+            assert!(output.starts_with("set -g status-style \"fg=#f8f8f2,bg=#1e1f29\"\n"));
+            assert!(output
+                .contains("\nset -g window-status-current-style \"fg=#1e1f29,bg=#bd93f9,bold\"\n"));
+            assert!(output.contains("\nset -g pane-active-border-style \"fg=#bd93f9\"\n"));
+            assert!(output.ends_with("\nset -g clock-mode-colour \"#bd93f9\"\n"));
+        }
+
+        #[test]
+        fn vscode_color_customizations() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let output = scheme.to_vscode();
+            let value = json::parse(&output).unwrap();
+            let colors = &value["workbench.colorCustomizations"];
+            assert_eq!(colors["terminal.background"], "#1e1f29");
+            assert_eq!(colors["terminal.ansiMagenta"], "#ff79c6");
+            assert_eq!(colors["terminal.ansiBrightBlack"], "#555555");
+            assert_eq!(colors["terminalCursor.foreground"], "#bbbbbb");
+
+            // It can be read back.
+            let read = ColorScheme::from_vscode(&output).unwrap();
+            assert_eq!(read.ansi_colors(), scheme.ansi_colors());
+            assert_eq!(read.background().to_css_hex(), "#1e1f29");
+        }
+
+        #[test]
+        fn neovim_lua() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let output = scheme.to_neovim();
+            assert!(output.starts_with("vim.g.terminal_color_0 = '#000000'\n"));
+            assert!(output.contains("\nvim.g.terminal_color_15 = '#ffffff'\n"));
+            assert!(output.contains(
+                "\nvim.api.nvim_set_hl(0, 'Normal', { fg = '#f8f8f2', bg = '#1e1f29' })\n"
+            ));
+            assert_eq!(
+                OutputFormat::Neovim.comment("Dracula"),
+                Some("-- Dracula".to_owned())
+            );
+        }
+
+        #[test]
+        fn base16_scheme() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let output = scheme.to_base16("Dracula", "Zeno \"Z\" Rocha");
+            assert!(output.starts_with(
+                "scheme: \"Dracula\"\nauthor: \"Zeno \\\"Z\\\" Rocha\"\nbase00: \"1e1f29\"\n"
+            ));
+            assert!(output.contains("\nbase05: \"f8f8f2\"\n"));
+            assert!(output.contains("\nbase08: \"ff5555\"\n"));
+            assert!(output.contains("\nbase0D: \"bd93f9\"\n"));
+            assert_eq!(output.lines().count(), 18);
+        }
+
+        #[test]
+        fn osc_escape_sequences() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            let output = scheme.to_osc();
+            assert!(output
+                .starts_with("\x1b]4;0;rgb:0000/0000/0000\x1b\\\x1b]4;1;rgb:ffff/5555/5555\x1b\\"));
+            assert!(output.ends_with(
+                "\x1b]10;rgb:f8f8/f8f8/f2f2\x1b\\\x1b]11;rgb:1e1f/1f45/28e7\x1b\\\x1b]12;rgb:bbbb/bbbb/bbbb\x1b\\"
+            ));
+            assert_eq!(OutputFormat::Osc.comment("Dracula"), None);
+        }
+
+        #[test]
+        fn fzf_colors() {
+            let scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            assert_eq!(
+                scheme.to_fzf(),
+                "export FZF_DEFAULT_OPTS=\"$FZF_DEFAULT_OPTS --color=fg:#f8f8f2,bg:#1e1f29,hl:#bd93f9,fg+:#f8f8f2,bg+:#3f4047,hl+:#bd93f9,info:#f1fa8c,prompt:#50fa7b,pointer:#ff79c6,marker:#ff79c6,spinner:#f1fa8c,header:#555555\"\n"
+            );
+        }
+
+        #[test]
+        fn gogh_theme() {
+            let scheme =
+                ColorScheme::from_gogh(&read_fixture("tests/fixtures/dracula.sh")).unwrap();
+            let output = scheme.to_gogh("Dracula \"$HOME\"");
+            assert!(output.starts_with("#!/usr/bin/env bash\n\n# ====================CONFIG THIS =============================== #\nexport COLOR_01=\"#44475a\"\n"));
+            assert!(
+                output.contains("\nexport COLOR_08=\"#94a3a5\"\n\nexport COLOR_09=\"#000000\"\n")
+            );
+            assert!(output.contains("\nexport CURSOR_COLOR=\"$FOREGROUND_COLOR\"\nexport PROFILE_NAME=\"Dracula \\\"\\$HOME\\\"\"\n"));
+            assert!(output.contains("\n  bash \"${PARENT_PATH}/apply-colors.sh\"\n"));
+
+            // It can be read back.
+            let read = ColorScheme::from_gogh(&output).unwrap();
+            assert_eq!(read.ansi_colors(), scheme.ansi_colors());
+            assert_eq!(read.background(), scheme.background());
+        }
+
+        #[test]
+        fn input_format_names() {
+            for name in ColorSchemeFormat::NAMES.iter() {
+                assert!(ColorSchemeFormat::from_string(name).is_some(), "{}", name);
+            }
+        }
+
+        #[test]
+        fn output_format_names() {
+            for name in OutputFormat::NAMES.iter() {
+                assert!(OutputFormat::from_string(name).is_some(), "{}", name);
+            }
+            assert!(matches!(
+                OutputFormat::default(),
+                OutputFormat::Alacritty(AlacrittyConfigFormat::Toml)
+            ));
+        }
+
+        #[test]
+        fn convert_alacritty_yaml() {
+            let content = read_fixture("tests/fixtures/alacritty.yml");
+            assert!(matches!(
+                ColorSchemeFormat::from_content(&content),
+                Some(ColorSchemeFormat::AlacrittyYaml)
+            ));
+            let scheme = ColorScheme::from_alacritty_yaml(&content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(scheme.foreground(), &Color::from_hex("#f8f8f2").unwrap());
+            assert_eq!(scheme.cursor(), None);
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[0], &Color::from_hex("#21222c").unwrap());
+            assert_eq!(colors[15], &Color::from_hex("#ffffff").unwrap());
+            assert_eq!(
+                scheme.extended_color("selection_background"),
+                Some(&Color::from_hex("#44475a").unwrap())
+            );
+            assert_eq!(
+                scheme.extended_color("primary_bright_foreground"),
+                Some(&Color::from_hex("#ffffff").unwrap())
+            );
+            assert!(scheme.to_toml().contains(
+                "
+[colors.search.matches]
+foreground = '0x44475a'
+background = '0x50fa7b'
+"
+            ));
+        }
+
+        #[test]
+        fn convert_alacritty_yaml_without_colors() {
+            assert!(ColorScheme::from_alacritty_yaml("window:\n  opacity: 0.9\n").is_err());
+        }
+
+        #[test]
+        fn convert_alacritty_toml() {
+            let mut scheme =
+                ColorScheme::from_iterm(&read_fixture("tests/fixtures/Dracula.itermcolors"))
+                    .unwrap();
+            scheme.derive_ui_colors();
+            let toml = scheme.to_toml();
+            let parsed = ColorScheme::from_alacritty_toml(&toml).unwrap();
+            assert_eq!(parsed.fingerprint(), scheme.fingerprint());
+            assert_eq!(parsed.to_toml(), toml);
+
+            let config = "[window]
+opacity = 0.9
+
+[colors]
+primary = { background = '#1e1f28', foreground = '#f8f8f2' }
+cursor = { text = 'CellBackground', cursor = 'CellForeground' }
+normal.red = '0xff5555'
+";
+            let parsed = ColorScheme::from_alacritty_toml(config).unwrap();
+            assert_eq!(parsed.background(), &Color::from_hex("#1e1f28").unwrap());
+            assert_eq!(parsed.cursor(), None);
+            assert_eq!(
+                parsed.ansi_colors()[1],
+                &Color::from_hex("#ff5555").unwrap()
+            );
+
+            assert!(ColorScheme::from_alacritty_toml("[colors").is_err());
+        }
+
+        #[test]
+        fn convert_windows_terminal_settings() {
+            let settings = read_fixture("tests/fixtures/windows-terminal-settings.json");
+            let schemes = ColorScheme::from_windows_terminal_settings(&settings).unwrap();
+            let names: Vec<_> = schemes.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(names, vec!["Dracula", "One Half Dark"]);
+
+            let dracula = &schemes[0].1;
+            assert_eq!(dracula.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(dracula.cursor(), Some(&Color::from_hex("#f8f8f2").unwrap()));
+            assert_eq!(
+                dracula.ansi_colors()[5],
+                &Color::from_hex("#ff79c6").unwrap()
+            );
+            assert_eq!(
+                dracula.ansi_colors()[13],
+                &Color::from_hex("#ff92df").unwrap()
+            );
+        }
+
+        #[test]
+        fn convert_windows_terminal_scheme() {
+            let content = r##"{
+    "name": "Campbell",
+    "cursorColor": "#FFFFFF",
+    "selectionBackground": "#FFFFFF",
+    "background": "#0C0C0C",
+    "foreground": "#CCCCCC",
+    "black": "#0C0C0C",
+    "purple": "#881798",
+    "brightBlack": "#767676",
+    "brightPurple": "#B4009E"
+}"##;
+            let scheme = ColorScheme::from_windows_terminal(content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#0c0c0c").unwrap());
+            assert_eq!(scheme.cursor(), Some(&Color::from_hex("#ffffff").unwrap()));
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[5], &Color::from_hex("#881798").unwrap());
+            assert_eq!(colors[8], &Color::from_hex("#767676").unwrap());
+            assert_eq!(colors[13], &Color::from_hex("#b4009e").unwrap());
+
+            let settings = read_fixture("tests/fixtures/windows-terminal-settings.json");
+            assert!(ColorScheme::from_windows_terminal(&settings).is_err());
+            assert!(ColorScheme::from_windows_terminal(r#"{"profiles": {}}"#).is_err());
+        }
+
+        #[test]
+        fn convert_konsole() {
+            let content = read_fixture("tests/fixtures/Dracula.colorscheme");
+            let scheme = ColorScheme::from_konsole(&content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_rgb8(40, 42, 54));
+            assert_eq!(scheme.foreground(), &Color::from_rgb8(248, 248, 242));
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[0], &Color::from_rgb8(0, 0, 0));
+            assert_eq!(colors[8], &Color::from_rgb8(77, 77, 77));
+            assert_eq!(colors[7], &Color::from_rgb8(191, 191, 191));
+            assert_eq!(colors[15], &Color::from_rgb8(230, 230, 230));
+            assert_eq!(
+                scheme.extended_color("bold"),
+                Some(&Color::from_rgb8(255, 255, 255))
+            );
+            assert!(ColorScheme::from_konsole("[Color0]\nColor=1,2\n").is_err());
+        }
+
+        #[test]
+        fn convert_vscode() {
+            let settings = r##"{
+    // Match the terminal with the editor.
+    "editor.fontSize": 13,
+    "workbench.colorCustomizations": {
+        "editor.background": "#1e1f28",
+        "terminal.foreground": "#f8f8f2",
+        "terminalCursor.foreground": "#f8f8f2",
+        "terminal.selectionBackground": "#44475a80",
+        "terminal.ansiRed": "#ff5555",
+        "terminal.ansiBrightMagenta": "#ff92df",
+    },
+}"##;
+            assert!(matches!(
+                ColorSchemeFormat::from_content(settings),
+                Some(ColorSchemeFormat::VSCode)
+            ));
+            let scheme = ColorScheme::from_vscode(settings).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#1e1f28").unwrap());
+            assert_eq!(scheme.foreground(), &Color::from_hex("#f8f8f2").unwrap());
+            assert_eq!(scheme.cursor(), Some(&Color::from_hex("#f8f8f2").unwrap()));
+            assert_eq!(
+                scheme.extended_color("selection_background"),
+                Some(&Color::from_hex("#44475a").unwrap())
+            );
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[1], &Color::from_hex("#ff5555").unwrap());
+            assert_eq!(colors[13], &Color::from_hex("#ff92df").unwrap());
+
+            let theme = r##"{"name": "Dracula", "colors": {"terminal.background": "#282a36", "editor.background": "#000000"}}"##;
+            let scheme = ColorScheme::from_vscode(theme).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+
+            assert!(ColorScheme::from_vscode(r#"{"editor.fontSize": 13}"#).is_err());
+        }
+
+        #[test]
+        fn convert_wezterm() {
+            let content = read_fixture("tests/fixtures/Dracula.wezterm.toml");
+            assert!(matches!(
+                ColorSchemeFormat::from_content(&content),
+                Some(ColorSchemeFormat::WezTerm)
+            ));
+            let scheme = ColorScheme::from_wezterm(&content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(scheme.cursor(), Some(&Color::from_hex("#f8f8f2").unwrap()));
+            assert_eq!(
+                scheme.cursor_text(),
+                Some(&Color::from_hex("#282a36").unwrap())
+            );
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[0], &Color::from_hex("#21222c").unwrap());
+            assert_eq!(colors[15], &Color::from_hex("#ffffff").unwrap());
+            assert_eq!(
+                scheme.extended_color("selection_background"),
+                Some(&Color::from_hex("#44475a").unwrap())
+            );
+        }
+
+        #[test]
+        fn guess_alacritty_toml() {
+            let content = "[colors.primary]\nbackground = '#282a36'\n";
+            assert!(matches!(
+                ColorSchemeFormat::from_content(content),
+                Some(ColorSchemeFormat::AlacrittyToml)
+            ));
+        }
+
+        #[test]
+        fn convert_putty_sessions() {
+            let content = std::fs::read("tests/fixtures/putty-sessions.reg").unwrap();
+            let schemes =
+                ColorScheme::parse_all_bytes(&content, &ColorSchemeFormat::PuTTY).unwrap();
+            let names: Vec<_> = schemes.iter().map(|(name, _)| name.as_deref()).collect();
+            assert_eq!(
+                names,
+                vec![Some("Dracula Server"), Some("Default Settings")]
+            );
+
+            let dracula = &schemes[0].1;
+            assert_eq!(dracula.foreground(), &Color::from_rgb8(248, 248, 242));
+            assert_eq!(dracula.background(), &Color::from_rgb8(40, 42, 54));
+            assert_eq!(dracula.cursor(), Some(&Color::from_rgb8(248, 248, 242)));
+            let colors = dracula.ansi_colors();
+            assert_eq!(colors[0], &Color::from_rgb8(33, 34, 44));
+            assert_eq!(colors[8], &Color::from_rgb8(98, 114, 164));
+            assert_eq!(colors[1], &Color::from_rgb8(255, 85, 85));
+            assert_eq!(colors[15], &Color::from_rgb8(255, 255, 255));
+            assert_eq!(
+                dracula.extended_color("bold"),
+                Some(&Color::from_rgb8(255, 255, 255))
+            );
+
+            let single = "[HKEY_CURRENT_USER\\Software\\SimonTatham\\PuTTY\\Sessions\\Dark]\n\"Colour2\"=\"1,2,3\"\n";
+            let scheme = ColorScheme::from_putty(single).unwrap();
+            assert_eq!(scheme.background(), &Color::from_rgb8(1, 2, 3));
+            assert!(ColorScheme::from_putty("Windows Registry Editor Version 5.00\n").is_err());
+        }
+
+        #[test]
+        fn convert_terminal_app() {
+            for path in &[
+                "tests/fixtures/Dracula.terminal",
+                "tests/fixtures/Dracula-binary.terminal",
+            ] {
+                let content = std::fs::read(path).unwrap();
+                let scheme = ColorScheme::from_terminal_app(&content).unwrap();
+                assert_eq!(scheme.background().to_css_hex(), "#282a36");
+                assert_eq!(scheme.foreground().to_css_hex(), "#f8f8f2");
+                assert_eq!(scheme.cursor().unwrap().to_css_hex(), "#ffffff");
+                let colors = scheme.ansi_colors();
+                assert_eq!(colors[0].to_css_hex(), "#21222c");
+                assert_eq!(colors[13].to_css_hex(), "#ff92df");
+                assert_eq!(
+                    scheme
+                        .extended_color("selection_background")
+                        .unwrap()
+                        .to_css_hex(),
+                    "#44475a"
+                );
+            }
+        }
+
+        #[test]
+        fn convert_xfce4_terminal() {
+            let content = read_fixture("tests/fixtures/dracula.theme");
+            let scheme = ColorScheme::from_xfce4_terminal(&content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(scheme.foreground(), &Color::from_hex("#f8f8f2").unwrap());
+            assert_eq!(scheme.cursor(), Some(&Color::from_hex("#f8f8f2").unwrap()));
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[0], &Color::from_hex("#21222c").unwrap());
+            assert_eq!(colors[8], &Color::from_hex("#6272a4").unwrap());
+            assert_eq!(colors[15], &Color::from_hex("#ffffff").unwrap());
+            assert_eq!(
+                scheme.extended_color("bold"),
+                Some(&Color::from_hex("#ffffff").unwrap())
+            );
+
+            let too_many = format!("ColorPalette={}", "#000000;".repeat(17));
+            assert!(ColorScheme::from_xfce4_terminal(&too_many).is_err());
+        }
+
+        #[test]
+        fn convert_dconf_profiles() {
+            let content = read_fixture("tests/fixtures/gnome-terminal.dconf");
+            assert!(matches!(
+                ColorSchemeFormat::from_content(&content),
+                Some(ColorSchemeFormat::Dconf)
+            ));
+            let schemes = ColorScheme::from_dconf_profiles(&content).unwrap();
+            let names: Vec<_> = schemes.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(
+                names,
+                vec![
+                    "Dracula",
+                    "legacy/profiles:/:e4a9cbd3-6c7a-4c53-a0e4-7a0c9b34f1d6"
+                ]
+            );
+
+            let dracula = &schemes[0].1;
+            assert_eq!(dracula.background(), &Color::from_rgb8(40, 42, 54));
+            assert_eq!(dracula.cursor(), Some(&Color::from_rgb8(248, 248, 242)));
+            assert_eq!(dracula.ansi_colors()[0], &Color::from_rgb8(33, 34, 44));
+            assert_eq!(dracula.ansi_colors()[15], &Color::from_rgb8(255, 255, 255));
+
+            let light = &schemes[1].1;
+            assert_eq!(light.foreground(), &Color::from_hex("#171421").unwrap());
+            assert_eq!(light.ansi_colors()[1], &Color::from_hex("#c01c28").unwrap());
+
+            assert!(ColorScheme::from_dconf_profiles("[legacy]\ntheme-variant='dark'\n").is_err());
+        }
+
+        #[test]
+        fn convert_foot() {
+            let content = read_fixture("tests/fixtures/foot.ini");
+            let scheme = ColorScheme::from_foot(&content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(scheme.foreground(), &Color::from_hex("#f8f8f2").unwrap());
+            assert_eq!(scheme.cursor(), Some(&Color::from_hex("#f8f8f2").unwrap()));
+            assert_eq!(
+                scheme.cursor_text(),
+                Some(&Color::from_hex("#282a36").unwrap())
+            );
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[0], &Color::from_hex("#21222c").unwrap());
+            assert_eq!(colors[8], &Color::from_hex("#6272a4").unwrap());
+            assert_eq!(colors[15], &Color::from_hex("#ffffff").unwrap());
+            assert_eq!(
+                scheme.extended_color("selection_background"),
+                Some(&Color::from_hex("#44475a").unwrap())
+            );
+        }
+
+        #[test]
+        fn convert_ghostty() {
+            let content = read_fixture("tests/fixtures/ghostty-dracula");
+            assert!(matches!(
+                ColorSchemeFormat::from_content(&content),
+                Some(ColorSchemeFormat::Ghostty)
+            ));
+            let scheme = ColorScheme::from_ghostty(&content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(scheme.foreground(), &Color::from_hex("#f8f8f2").unwrap());
+            assert_eq!(scheme.cursor(), Some(&Color::from_hex("#f8f8f2").unwrap()));
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[0], &Color::from_hex("#21222c").unwrap());
+            assert_eq!(colors[10], &Color::from_hex("#69ff94").unwrap());
+            assert_eq!(colors[15], &Color::from_hex("#ffffff").unwrap());
+
+            let extended = ColorScheme::from_ghostty("palette=16=#ffb86c\nbackground=black\n");
+            assert_eq!(extended.unwrap().background(), &Color::from_rgb8(0, 0, 0));
+        }
+
+        #[test]
+        fn convert_termux() {
+            assert!(matches!(
+                ColorSchemeFormat::from_filename("colors.properties"),
+                Some(ColorSchemeFormat::Termux)
+            ));
+            let content = read_fixture("tests/fixtures/colors.properties");
+            let scheme = ColorScheme::from_termux(&content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(scheme.foreground(), &Color::from_hex("#f8f8f2").unwrap());
+            assert_eq!(scheme.cursor(), Some(&Color::from_hex("#f8f8f2").unwrap()));
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[0], &Color::from_hex("#21222c").unwrap());
+            assert_eq!(colors[15], &Color::from_hex("#ffffff").unwrap());
+        }
+
+        #[test]
+        fn convert_st() {
+            let content = read_fixture("tests/fixtures/st-config.h");
+            let scheme = ColorScheme::from_st(&content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(scheme.foreground(), &Color::from_hex("#f8f8f2").unwrap());
+            assert_eq!(scheme.cursor(), Some(&Color::from_hex("#f8f8f2").unwrap()));
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[0], &Color::from_hex("#21222c").unwrap());
+            assert_eq!(colors[15], &Color::from_hex("#ffffff").unwrap());
+
+            // Older themes set the default colors to the ANSI colors.
+            let content = "static const char *colorname[] = { \"black\", \"red3\" };\n\
+                           unsigned int defaultfg = 1;\n";
+            let scheme = ColorScheme::from_st(content).unwrap();
+            assert_eq!(scheme.foreground(), &Color::from_rgb8(205, 0, 0));
+            assert_eq!(scheme.background(), &Color::from_rgb8(0, 0, 0));
+        }
+
+        #[test]
+        fn convert_hyper() {
+            let content = read_fixture("tests/fixtures/hyper-dracula.js");
+            let scheme = ColorScheme::from_hyper(&content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(scheme.foreground(), &Color::from_hex("#f8f8f2").unwrap());
+            assert_eq!(scheme.cursor(), Some(&Color::from_hex("#f8f8f2").unwrap()));
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[0], &Color::from_hex("#21222c").unwrap());
+            assert_eq!(colors[7], &Color::from_hex("#f8f8f2").unwrap());
+            assert_eq!(colors[15], &Color::from_hex("#ffffff").unwrap());
+        }
+
+        #[test]
+        fn convert_rio() {
+            let content = read_fixture("tests/fixtures/rio-dracula.toml");
+            assert!(matches!(
+                ColorSchemeFormat::from_content(&content),
+                Some(ColorSchemeFormat::Rio)
+            ));
+            let scheme = ColorScheme::from_rio(&content).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(scheme.foreground(), &Color::from_hex("#f8f8f2").unwrap());
+            assert_eq!(scheme.cursor(), Some(&Color::from_hex("#f8f8f2").unwrap()));
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[0], &Color::from_hex("#21222c").unwrap());
+            assert_eq!(colors[12], &Color::from_hex("#d6acff").unwrap());
+            assert_eq!(colors[15], &Color::from_hex("#ffffff").unwrap());
+        }
+
+        #[test]
+        fn convert_iterm_dynamic_profiles() {
+            let profiles = read_fixture("tests/fixtures/iterm-dynamic-profiles.json");
+            let schemes = ColorScheme::from_iterm_dynamic_profiles(&profiles).unwrap();
+            let names: Vec<_> = schemes.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(names, vec!["Dracula", "Plain"]);
+            assert_eq!(
+                schemes[1].1.background(),
+                &Color::from_hex("#ffffff").unwrap()
+            );
+        }
+
+        #[test]
+        fn convert_xresources() {
+            let xresources = read_fixture("tests/fixtures/Dracula.Xresources");
+            let scheme = ColorScheme::from_xresources(&xresources).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#282a36").unwrap());
+            assert!(matches!(
+                ColorSchemeFormat::from_filename("dracula.xresources"),
+                Some(ColorSchemeFormat::Xresources)
+            ));
+
+            let solarized = "#define S_base03 #002b36
+#define S_base0  #839496
+URxvt*background: S_base03
+URxvt*foreground: S_base0
+URxvt*color1:     #dc322f
+";
+            let scheme = ColorScheme::from_xresources(solarized).unwrap();
+            assert_eq!(scheme.background(), &Color::from_hex("#002b36").unwrap());
+            assert_eq!(scheme.foreground(), &Color::from_hex("#839496").unwrap());
+            assert_eq!(
+                scheme.ansi_colors()[1],
+                &Color::from_hex("#dc322f").unwrap()
+            );
+
+            let multiple = "URxvt.background: #000000\nXTerm.background: #ffffff\n";
+            assert!(ColorScheme::from_xresources(multiple).is_err());
+        }
+
+        #[test]
+        fn convert_xresources_by_prefix() {
+            let xresources = read_fixture("tests/fixtures/Dracula.Xresources");
+            let schemes = ColorScheme::from_xresources_by_prefix(&xresources).unwrap();
+            let names: Vec<_> = schemes.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(names, vec!["*", "URxvt"]);
+
+            let (_, wildcard) = &schemes[0];
+            let (_, urxvt) = &schemes[1];
+            assert_eq!(wildcard.background(), &Color::from_hex("#282a36").unwrap());
+            assert_eq!(urxvt.background(), &Color::from_hex("#1e1f29").unwrap());
+            // Inherited from the wildcard resources.
+            assert_eq!(urxvt.foreground(), wildcard.foreground());
+            assert_eq!(urxvt.ansi_colors(), wildcard.ansi_colors());
+            assert_eq!(wildcard.cursor(), None);
+            assert_eq!(urxvt.cursor(), Some(&Color::from_hex("#f8f8f2").unwrap()));
+        }
+
+        #[test]
+        fn parse_single_and_multiple() {
+            let settings = read_fixture("tests/fixtures/windows-terminal-settings.json");
+            let format = ColorSchemeFormat::from_content(&settings).unwrap();
+            assert!(ColorScheme::parse(&settings, &format).is_err());
+            assert_eq!(ColorScheme::parse_all(&settings, &format).unwrap().len(), 2);
+
+            let minttyrc = read_fixture("tests/fixtures/Dracula.minttyrc");
+            let schemes = ColorScheme::parse_all(&minttyrc, &ColorSchemeFormat::Mintty).unwrap();
+            assert_eq!(schemes.len(), 1);
+            assert_eq!(schemes[0].0, None);
+        }
+
+        #[test]
+        fn convert_gpl() {
+            let scheme = ColorScheme::from_gpl(&read_fixture("tests/fixtures/brand.gpl")).unwrap();
+            assert_eq!(scheme.background(), &Color::from_rgb8(40, 42, 54));
+            assert_eq!(scheme.foreground(), &Color::from_rgb8(248, 248, 242));
+            let colors = scheme.ansi_colors();
+            assert_eq!(colors[1], &Color::from_rgb8(255, 85, 85));
+            assert_eq!(colors[4], &Color::from_rgb8(189, 147, 249));
+            // Named after a slot.
+            assert_eq!(colors[8], &Color::from_rgb8(98, 114, 164));
+            assert!(ColorScheme::from_gpl("GIMP Palette\n").is_err());
+            assert!(ColorScheme::from_gpl("255 0 0 Red").is_err());
+        }
+
+        #[test]
+        fn convert_ase() {
+            let content = std::fs::read("tests/fixtures/brand.ase").unwrap();
+            let scheme = ColorScheme::parse_bytes(&content, &ColorSchemeFormat::Ase).unwrap();
+            assert_eq!(scheme.background(), &Color::from_rgb8(40, 42, 54));
+            assert_eq!(scheme.foreground(), &Color::from_real_rgb(0.95, 0.95, 0.95));
+            assert_eq!(scheme.ansi_colors()[1], &Color::from_rgb8(255, 85, 85));
+            assert!(ColorScheme::from_ase(&content[..20]).is_err());
+            assert!(ColorScheme::from_ase(b"GIMP").is_err());
+        }
+
+        #[test]
+        fn fingerprint() {
+            let minttyrc = read_fixture("tests/fixtures/Dracula.minttyrc");
+            let iterm = read_fixture("tests/fixtures/Dracula.itermcolors");
+            let mintty_scheme = ColorScheme::from_minttyrc(&minttyrc).unwrap();
+            let iterm_scheme = ColorScheme::from_iterm(&iterm).unwrap();
+            // Stable across runs and platforms.
+            assert_eq!(iterm_scheme.fingerprint(), "e851ce822dff5212");
+            assert_ne!(mintty_scheme.fingerprint(), iterm_scheme.fingerprint());
+            // Differences below 8 bits don't matter.
+            let gray = |value: u16| Color {
+                red: value,
+                green: value,
+                blue: value,
+            };
+            let scheme =
+                |value: u16| ColorScheme::from_colors(gray(value), gray(0), Default::default());
+            assert_eq!(scheme(0x8080).fingerprint(), scheme(0x8081).fingerprint());
+            assert_ne!(scheme(0x8080).fingerprint(), scheme(0x8181).fingerprint());
+        }
+
+        #[test]
+        fn distance() {
+            let minttyrc = read_fixture("tests/fixtures/Dracula.minttyrc");
+            let iterm = read_fixture("tests/fixtures/Dracula.itermcolors");
+            let mintty_scheme = ColorScheme::from_minttyrc(&minttyrc).unwrap();
+            let iterm_scheme = ColorScheme::from_iterm(&iterm).unwrap();
+            assert_eq!(mintty_scheme.distance(&mintty_scheme), 0.0);
+            assert!(mintty_scheme.distance(&iterm_scheme) > 0.0);
+            assert_eq!(
+                mintty_scheme.distance(&iterm_scheme),
+                iterm_scheme.distance(&mintty_scheme)
+            );
+        }
     }
 }
@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod dedupe_tests {
+    use colortty::{dedupe, ColorScheme};
+    use std::fs;
+
+    #[test]
+    fn group_duplicates() {
+        let dracula = fs::read_to_string("tests/fixtures/Dracula.itermcolors").unwrap();
+        let firewatch =
+            fs::read_to_string("tests/fixtures/two-firewatch-light.itermcolors").unwrap();
+        let schemes = [
+            ColorScheme::from_iterm(&dracula).unwrap(),
+            ColorScheme::from_iterm(&firewatch).unwrap(),
+            ColorScheme::from_iterm(&dracula).unwrap(),
+        ];
+        let scheme_refs: Vec<_> = schemes.iter().collect();
+        assert_eq!(
+            dedupe::group_duplicates(&scheme_refs, dedupe::DEFAULT_THRESHOLD),
+            vec![vec![0, 2], vec![1]]
+        );
+    }
+}
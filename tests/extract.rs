@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod extract_tests {
+    use colortty::extract::{clusters, from_picture, from_terminal_screenshot};
+    use colortty::{Color, ColorScheme};
+    use std::fs;
+
+    fn dracula() -> ColorScheme {
+        let content = fs::read_to_string("tests/fixtures/Dracula.itermcolors").unwrap();
+        ColorScheme::from_iterm(&content).unwrap()
+    }
+
+    // A fake screenshot: mostly background, some text and a little of each ANSI color.
+    fn screenshot(scheme: &ColorScheme) -> Vec<Color> {
+        let mut pixels = vec![scheme.background().clone(); 9000];
+        pixels.extend(vec![scheme.foreground().clone(); 500]);
+        for color in scheme.ansi_colors().iter() {
+            pixels.extend(vec![(*color).clone(); 30]);
+        }
+        pixels
+    }
+
+    #[test]
+    fn clusters_most_common_first() {
+        let red = Color::from_rgb8(255, 0, 0);
+        let dark_red = Color::from_rgb8(250, 4, 2);
+        let blue = Color::from_rgb8(0, 0, 255);
+        let mut pixels = vec![blue.clone(); 10];
+        pixels.extend(vec![red.clone(); 20]);
+        pixels.extend(vec![dark_red; 5]);
+
+        let result = clusters(&pixels);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].count, 25);
+        assert!(result[0].color.delta_e(&red) < 2.0);
+        assert_eq!(result[1].count, 10);
+        assert_eq!(result[1].color, blue);
+    }
+
+    #[test]
+    fn terminal_screenshot() {
+        let scheme = dracula();
+        let result = from_terminal_screenshot(&screenshot(&scheme)).unwrap();
+        assert_eq!(result.background(), scheme.background());
+        assert!(result.foreground().delta_e(scheme.foreground()) < 2.0);
+        let colors = result.ansi_colors();
+        let expected = scheme.ansi_colors();
+        for index in 1..7 {
+            assert!(colors[index].delta_e(expected[index]) < 5.0);
+        }
+    }
+
+    #[test]
+    fn picture() {
+        let scheme = dracula();
+        let result = from_picture(&screenshot(&scheme)).unwrap();
+        assert!(result.background().to_lab().0 < 5.0);
+        assert!(result.foreground().to_lab().0 > 95.0);
+    }
+
+    #[test]
+    fn no_pixels() {
+        assert!(from_terminal_screenshot(&[]).is_none());
+        assert!(from_picture(&[]).is_none());
+    }
+}
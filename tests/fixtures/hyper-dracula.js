@@ -0,0 +1,34 @@
+// Dracula for Hyper
+const background = '#282a36';
+const foreground = '#f8f8f2';
+const selection = 'rgba(68, 71, 90, 0.5)';
+
+const colors = {
+  black: '#21222c',
+  red: '#ff5555',
+  green: '#50fa7b',
+  yellow: '#f1fa8c',
+  blue: '#bd93f9',
+  magenta: '#ff79c6',
+  cyan: '#8be9fd',
+  white: foreground,
+  lightBlack: '#6272a4',
+  lightRed: '#ff6e6e',
+  lightGreen: '#69ff94',
+  lightYellow: '#ffffa5',
+  lightBlue: '#d6acff',
+  lightMagenta: '#ff92df',
+  lightCyan: '#a4ffff',
+  lightWhite: "#ffffff",
+};
+
+exports.decorateConfig = (config) => {
+  return Object.assign({}, config, {
+    foregroundColor: foreground,
+    backgroundColor: background,
+    selectionColor: selection,
+    cursorColor: foreground,
+    colors,
+    css: `${config.css || ''}`,
+  });
+};
@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod gallery_tests {
+    use colortty::gallery::to_html;
+    use colortty::ColorScheme;
+    use std::fs;
+
+    fn dracula() -> ColorScheme {
+        let content = fs::read_to_string("tests/fixtures/Dracula.itermcolors").unwrap();
+        ColorScheme::from_iterm(&content).unwrap()
+    }
+
+    #[test]
+    fn cards() {
+        let schemes = vec![
+            ("Dracula".to_owned(), dracula()),
+            ("<Dracula & Co>".to_owned(), dracula()),
+        ];
+        let html = to_html("Gallery", &schemes);
+        assert_eq!(html.matches("<div class=\"card\">").count(), 2);
+        assert!(html.contains("<h2>Dracula</h2>"));
+        assert!(html.contains("<h2>&lt;Dracula &amp; Co&gt;</h2>"));
+        assert!(html.contains("color: #f8f8f2; background: #1e1f29"));
+        assert!(html.contains("<p>2 color schemes</p>"));
+    }
+}
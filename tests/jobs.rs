@@ -0,0 +1,34 @@
+use colortty::jobs;
+use std::path::Path;
+
+#[test]
+fn set_at_least_one_job() {
+    jobs::set(0);
+    assert_eq!(jobs::get(), 1);
+    jobs::set(3);
+    assert_eq!(jobs::get(), 3);
+}
+
+#[test]
+fn parse_config() {
+    assert_eq!(jobs::parse_config("jobs = 4\n").unwrap(), Some(4));
+    assert_eq!(jobs::parse_config("").unwrap(), None);
+    assert_eq!(jobs::parse_config("other = true\n").unwrap(), None);
+    assert!(jobs::parse_config("jobs = 0\n").is_err());
+    assert!(jobs::parse_config("jobs = \"4\"\n").is_err());
+    assert!(jobs::parse_config("jobs =").is_err());
+}
+
+#[test]
+fn parse_env() {
+    assert_eq!(jobs::parse_env("4").unwrap(), 4);
+    assert!(jobs::parse_env("0").is_err());
+    assert!(jobs::parse_env("-1").is_err());
+    assert!(jobs::parse_env("four").is_err());
+    assert!(jobs::parse_env("").is_err());
+}
+
+#[test]
+fn load_missing_config() {
+    assert!(jobs::load_config(Path::new("tests/fixtures/no-such-config.toml")).is_ok());
+}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod lint_tests {
+    use colortty::lint::lint;
+    use colortty::ColorScheme;
+    use std::fs;
+
+    fn rules(scheme: &ColorScheme) -> Vec<&'static str> {
+        lint(scheme).iter().map(|problem| problem.rule).collect()
+    }
+
+    #[test]
+    fn no_problems() {
+        let content = fs::read_to_string("tests/fixtures/two-firewatch-light.itermcolors").unwrap();
+        assert!(lint(&ColorScheme::from_iterm(&content).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn identical_bright() {
+        let content = fs::read_to_string("tests/fixtures/Dracula.itermcolors").unwrap();
+        let scheme = ColorScheme::from_iterm(&content).unwrap();
+        assert_eq!(rules(&scheme), vec!["identical-bright"; 6]);
+    }
+
+    #[test]
+    fn default_black_and_foreground_background() {
+        let content = "ForegroundColour=20,20,20
+BackgroundColour=10,10,10
+Red=255,0,0
+BoldRed=255,80,80";
+        let scheme = ColorScheme::from_minttyrc(content).unwrap();
+        let rules = rules(&scheme);
+        assert_eq!(
+            rules
+                .iter()
+                .filter(|rule| **rule == "default-black")
+                .count(),
+            13
+        );
+        assert!(rules.contains(&"foreground-background"));
+    }
+}
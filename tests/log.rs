@@ -0,0 +1,11 @@
+use colortty::log::{self, Verbosity};
+
+#[test]
+fn set_verbosity() {
+    assert_eq!(log::get(), Verbosity::Normal);
+    log::set(Verbosity::Quiet);
+    assert_eq!(log::get(), Verbosity::Quiet);
+    log::set(Verbosity::Verbose);
+    assert_eq!(log::get(), Verbosity::Verbose);
+    assert!(Verbosity::Quiet < Verbosity::Normal && Verbosity::Normal < Verbosity::Verbose);
+}
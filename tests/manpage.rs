@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod manpage_tests {
+    use colortty::manpage::{escape, ManPage};
+
+    #[test]
+    fn escape_text() {
+        assert_eq!(escape("--out-dir"), "\\-\\-out\\-dir");
+        assert_eq!(escape("C:\\themes"), "C:\\ethemes");
+        assert_eq!(escape(".config"), "\\&.config");
+        assert_eq!(escape("'quoted'"), "\\&'quoted'");
+    }
+
+    #[test]
+    fn to_troff() {
+        let page = ManPage {
+            name: "colortty-get".to_owned(),
+            summary: "get a color scheme".to_owned(),
+            command: "colortty get".to_owned(),
+            synopsis: vec!["[OPTIONS] NAME".to_owned()],
+            options: vec![
+                (
+                    "-p, --provider PROVIDER".to_owned(),
+                    "color scheme provider".to_owned(),
+                ),
+                ("--exact".to_owned(), "don't match partially".to_owned()),
+            ],
+            sections: vec![(
+                "Exit status".to_owned(),
+                vec![
+                    "0  success".to_owned(),
+                    "Other codes are errors.".to_owned(),
+                ],
+            )],
+            see_also: vec!["colortty".to_owned()],
+        };
+        let expected = r#".TH COLORTTY\-GET 1 "" "colortty 1.0" "User Commands"
+.SH NAME
+colortty\-get \- get a color scheme
+.SH SYNOPSIS
+\fBcolortty get\fR [OPTIONS] NAME
+.SH OPTIONS
+.TP
+\fB\-p\fR, \fB\-\-provider\fR \fIPROVIDER\fR
+color scheme provider
+.TP
+\fB\-\-exact\fR
+don't match partially
+.SH EXIT STATUS
+.TP
+\fB0\fR
+success
+.PP
+Other codes are errors.
+.SH SEE ALSO
+\fBcolortty\fR(1)
+"#;
+        assert_eq!(page.to_troff("colortty 1.0"), expected);
+    }
+}
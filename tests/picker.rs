@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod picker_tests {
+    use colortty::picker::{fuzzy_score, parse_keys, Action, Key, Picker};
+    use colortty::ColorScheme;
+    use std::fs;
+
+    fn schemes(names: &[&str]) -> Vec<(String, ColorScheme)> {
+        let content = fs::read_to_string("tests/fixtures/Dracula.itermcolors").unwrap();
+        names
+            .iter()
+            .map(|name| (name.to_string(), ColorScheme::from_iterm(&content).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn fuzzy_score_order() {
+        assert_eq!(fuzzy_score("", "Dracula"), Some(0));
+        assert_eq!(fuzzy_score("xyz", "Dracula"), None);
+        assert_eq!(fuzzy_score("cd", "Dracula"), None);
+        let prefix = fuzzy_score("drac", "Dracula").unwrap();
+        let word = fuzzy_score("drac", "Andromeda Dracula").unwrap();
+        let scattered = fuzzy_score("drac", "Darkside Rock Accent").unwrap();
+        assert!(prefix > word);
+        assert!(word > scattered);
+        assert_eq!(fuzzy_score("DRAC", "dracula"), Some(prefix));
+        // Word starts in camel case
+        assert!(
+            fuzzy_score("gb", "GruvboxDark").unwrap() < fuzzy_score("gd", "GruvboxDark").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_keys_sequences() {
+        assert_eq!(
+            parse_keys(b"d\x1b[A\x1b[B\x1bOA\x1b[5~\x1b[6~\x7f\r"),
+            vec![
+                Key::Char('d'),
+                Key::Up,
+                Key::Down,
+                Key::Up,
+                Key::PageUp,
+                Key::PageDown,
+                Key::Backspace,
+                Key::Enter,
+            ]
+        );
+        assert_eq!(parse_keys(b"\x1b"), vec![Key::Cancel]);
+        assert_eq!(parse_keys(b"\x03"), vec![Key::Cancel]);
+        assert_eq!(parse_keys("é".as_bytes()), vec![Key::Char('é')]);
+        assert_eq!(parse_keys(b"\x1b[2~\x01"), vec![]);
+    }
+
+    #[test]
+    fn filter_and_pick() {
+        let schemes = schemes(&["Andromeda", "Dracula", "Nord", "Pro"]);
+        let mut picker = Picker::new(&schemes);
+        assert_eq!(picker.matches(), &[0, 1, 2, 3]);
+        assert_eq!(picker.handle(Key::Down), Action::Continue);
+        assert_eq!(picker.highlighted(), Some(1));
+
+        for c in "dr".chars() {
+            picker.handle(Key::Char(c));
+        }
+        assert_eq!(picker.query(), "dr");
+        // "Dracula" starts with the query.
+        assert_eq!(picker.matches(), &[1, 0]);
+        assert_eq!(picker.highlighted(), Some(1));
+        picker.handle(Key::End);
+        assert_eq!(picker.handle(Key::Enter), Action::Pick(0));
+
+        picker.handle(Key::Char('z'));
+        assert!(picker.matches().is_empty());
+        assert_eq!(picker.handle(Key::Enter), Action::Continue);
+        picker.handle(Key::ClearQuery);
+        assert_eq!(picker.matches().len(), 4);
+        assert_eq!(picker.handle(Key::Cancel), Action::Cancel);
+    }
+
+    #[test]
+    fn render_scrolls_to_highlighted() {
+        let schemes = schemes(&["A", "B", "C", "D", "E", "F"]);
+        let mut picker = Picker::new(&schemes);
+        picker.handle(Key::End);
+        let lines = picker.render(80, 5);
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("> "));
+        assert!(lines[1].contains("D"));
+        assert!(lines[3].contains("\x1b[1m> F"));
+        picker.handle(Key::PageUp);
+        assert_eq!(picker.highlighted(), Some(2));
+    }
+
+    #[test]
+    fn render_sample_screen_when_wide() {
+        let schemes = schemes(&["Dracula"]);
+        let mut picker = Picker::new(&schemes);
+        assert!(!picker.render(50, 30).join("\n").contains("Greet the world"));
+        assert!(picker
+            .render(100, 30)
+            .join("\n")
+            .contains("Greet the world"));
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod plist_tests {
+    use colortty::plist::{parse, parse_binary, Value};
+    use colortty::ColorScheme;
+
+    #[test]
+    fn parse_values() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <!-- A comment -->
+    <key>Name</key>
+    <string><![CDATA[Tom & Jerry]]> &lt;3</string>
+    <key>Count</key>
+    <integer>3</integer>
+    <key>Enabled</key>
+    <true/>
+    <key>Items</key>
+    <array>
+        <real>0.5</real>
+        <dict/>
+    </array>
+</dict>
+</plist>"#;
+        let expected = Value::Dict(vec![
+            (
+                "Name".to_owned(),
+                Value::String("Tom & Jerry <3".to_owned()),
+            ),
+            ("Count".to_owned(), Value::Integer(3)),
+            ("Enabled".to_owned(), Value::Boolean(true)),
+            (
+                "Items".to_owned(),
+                Value::Array(vec![Value::Real(0.5), Value::Dict(vec![])]),
+            ),
+        ]);
+        assert_eq!(parse(content).unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(parse("").is_err());
+        assert!(parse("<plist><dict><key>A</key>").is_err());
+        assert!(parse("<plist><dict><string>no key</string></dict></plist>").is_err());
+    }
+
+    #[test]
+    fn iterm_with_other_entries() {
+        // Components in any order, and a non-color entry between colors.
+        let content = r#"<plist><dict>
+<key>Background Color</key>
+<dict>
+    <key>Color Space</key><string>sRGB</string>
+    <key>Blue Component</key><real>1</real>
+    <key>Green Component</key><integer>0</integer>
+    <key>Red Component</key><real>0.5</real>
+</dict>
+<key>Name</key>
+<string>Test</string>
+<key>Foreground Color</key>
+<dict>
+    <key>Red Component</key><real>1</real>
+    <key>Green Component</key><real>1</real>
+    <key>Blue Component</key><real>1</real>
+</dict>
+</dict></plist>"#;
+        let scheme = ColorScheme::from_iterm(content).unwrap();
+        assert_eq!(scheme.background().to_rgb8(), (128, 0, 255));
+        assert_eq!(scheme.foreground().to_rgb8(), (255, 255, 255));
+    }
+
+    #[test]
+    fn parse_data() {
+        let content = "<plist><data>\n\tSGVsbG8s\n\tIHdvcmxk\n</data></plist>";
+        assert_eq!(
+            parse(content).unwrap(),
+            Value::Data(b"Hello, world".to_vec())
+        );
+    }
+
+    #[test]
+    fn parse_binary_values() {
+        // {"Name": "Tom", "Items": [1, 0.5, true, UID(2)]} written by plistlib of Python.
+        let content = [
+            0x62, 0x70, 0x6c, 0x69, 0x73, 0x74, 0x30, 0x30, 0xd2, 0x01, 0x02, 0x03, 0x04, 0x54,
+            0x4e, 0x61, 0x6d, 0x65, 0x55, 0x49, 0x74, 0x65, 0x6d, 0x73, 0x53, 0x54, 0x6f, 0x6d,
+            0xa4, 0x05, 0x06, 0x07, 0x08, 0x10, 0x01, 0x23, 0x3f, 0xe0, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x09, 0x80, 0x02, 0x08, 0x0d, 0x12, 0x18, 0x1c, 0x21, 0x23, 0x2c, 0x2d,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x09, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x2f,
+        ];
+        let expected = Value::Dict(vec![
+            ("Name".to_owned(), Value::String("Tom".to_owned())),
+            (
+                "Items".to_owned(),
+                Value::Array(vec![
+                    Value::Integer(1),
+                    Value::Real(0.5),
+                    Value::Boolean(true),
+                    Value::Uid(2),
+                ]),
+            ),
+        ]);
+        assert_eq!(parse_binary(&content).unwrap(), expected);
+        assert!(parse_binary(&content[..40]).is_err());
+        assert!(parse_binary(b"bplist00").is_err());
+    }
+}
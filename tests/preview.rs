@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod preview_tests {
+    use colortty::preview::{grid, grid_columns, sample_screen, side_by_side, PREVIEW_WIDTH};
+    use colortty::ColorScheme;
+    use regex::Regex;
+    use std::fs;
+
+    fn dracula() -> ColorScheme {
+        let content = fs::read_to_string("tests/fixtures/Dracula.itermcolors").unwrap();
+        ColorScheme::from_iterm(&content).unwrap()
+    }
+
+    fn strip_escapes(s: &str) -> String {
+        Regex::new("\x1b\\[[0-9;]*m")
+            .unwrap()
+            .replace_all(s, "")
+            .into_owned()
+    }
+
+    #[test]
+    fn sample_screen_width() {
+        for width in [5, 30, 60].iter() {
+            for line in sample_screen(&dracula(), "Dracula", *width) {
+                assert_eq!(strip_escapes(&line).chars().count(), *width);
+            }
+        }
+    }
+
+    #[test]
+    fn sample_screen_syntax_roles() {
+        let scheme = dracula();
+        let colors = scheme.ansi_colors();
+        let screen = sample_screen(&scheme, "Dracula", 40).join("\n");
+        assert!(screen.contains(&format!("{}fn ", colors[5].to_24bit_fe())));
+        assert!(screen.contains(&format!("{}-theme", colors[1].to_24bit_fe())));
+        assert!(screen.contains(&format!("{}+theme", colors[2].to_24bit_fe())));
+    }
+
+    #[test]
+    fn sample_screen_attributes_and_colors() {
+        let scheme = dracula();
+        let colors = scheme.ansi_colors();
+        let screen = sample_screen(&scheme, "Dracula", 40).join("\n");
+        assert!(screen.contains("\x1b[1mbold\x1b[0m"));
+        assert!(screen.contains("\x1b[4munderline\x1b[0m"));
+        for color in colors.iter() {
+            assert!(screen.contains(&format!("{}Aa ", color.to_24bit_fe())));
+            assert!(screen.contains(&format!(
+                "{}{}   ",
+                color.to_24bit_be(),
+                color.to_24bit_fe()
+            )));
+        }
+    }
+
+    #[test]
+    fn side_by_side_columns() {
+        let scheme = dracula();
+        let output = side_by_side(&[("A", &scheme), ("B", &scheme)], 80);
+        let lines: Vec<String> = output.lines().map(strip_escapes).collect();
+        assert_eq!(lines[0], format!("A{:38}  B{:38}", "", ""));
+        assert!(lines.iter().all(|line| line.chars().count() == 80));
+    }
+
+    #[test]
+    fn side_by_side_wraps() {
+        let scheme = dracula();
+        let output = side_by_side(&[("A", &scheme), ("B", &scheme), ("C", &scheme)], 50);
+        let lines: Vec<String> = output.lines().map(strip_escapes).collect();
+        let screen_height = sample_screen(&scheme, "A", 10).len();
+        assert_eq!(lines.len(), screen_height * 2 + 1);
+        assert_eq!(lines[0], format!("A{:23}  B{:23}", "", ""));
+        assert_eq!(lines[screen_height + 1], format!("C{:23}", ""));
+    }
+
+    #[test]
+    fn grid_cuts_long_names() {
+        let scheme = dracula();
+        let schemes = [
+            ("Dracula", &scheme),
+            ("A very long name of a color scheme", &scheme),
+            ("B", &scheme),
+        ];
+        let cell_width = 24 + 1 + PREVIEW_WIDTH;
+        assert_eq!(grid_columns(&schemes, cell_width * 2 + 1), 1);
+        assert_eq!(grid_columns(&schemes, cell_width * 2 + 2), 2);
+        assert_eq!(grid_columns(&schemes, 10), 1);
+
+        let lines: Vec<String> = grid(&schemes, 2).lines().map(strip_escapes).collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(&format!("{:24} ", "Dracula")));
+        assert!(lines[0].contains("  A very long name of a c… "));
+        assert_eq!(lines[0].chars().count(), cell_width * 2 + 2);
+        assert!(lines[1].starts_with(&format!("{:24} ", "B")));
+    }
+}
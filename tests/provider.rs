@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod provider_tests {
+    use colortty::provider::{is_url, url_path, FetchError};
+    use colortty::Provider;
+
+    #[test]
+    fn files_from_tree() {
+        let body = r#"{
+            "sha": "abc",
+            "tree": [
+                { "path": "Dracula.itermcolors", "type": "blob", "sha": "d1" },
+                { "path": "Zenburn.itermcolors", "type": "blob", "sha": "z1" },
+                { "path": "old", "type": "tree", "sha": "o1" },
+                { "path": "notes.txt", "type": "blob" }
+            ],
+            "truncated": false
+        }"#;
+        assert_eq!(
+            Provider::iterm().files_from_tree(body).unwrap(),
+            vec![
+                ("Dracula".to_owned(), "d1".to_owned()),
+                ("Zenburn".to_owned(), "z1".to_owned())
+            ]
+        );
+
+        let gogh = r#"{ "tree": [
+            { "path": "_base.yml", "type": "blob" },
+            { "path": "dracula.yml", "type": "blob", "sha": "d2" }
+        ] }"#;
+        assert_eq!(
+            Provider::gogh().files_from_tree(gogh).unwrap(),
+            vec![("dracula".to_owned(), "d2".to_owned())]
+        );
+    }
+
+    #[test]
+    fn files_from_truncated_tree() {
+        let body = r#"{ "tree": [], "truncated": true }"#;
+        assert!(Provider::iterm().files_from_tree(body).is_err());
+    }
+
+    #[test]
+    fn url_sources() {
+        assert!(is_url("https://example.com/Dracula.itermcolors"));
+        assert!(is_url("http://example.com/Dracula.itermcolors"));
+        assert!(!is_url("Dracula.itermcolors"));
+        assert!(!is_url("-"));
+
+        assert_eq!(
+            url_path("https://example.com/schemes/Gruvbox%20Dark.itermcolors?raw=1#top"),
+            "/schemes/Gruvbox Dark.itermcolors"
+        );
+        assert_eq!(url_path("https://example.com"), "");
+        assert_eq!(url_path("https://example.com/100%.sh"), "/100%.sh");
+    }
+
+    #[test]
+    fn rate_limit_message() {
+        assert!(FetchError::RateLimited(false)
+            .to_string()
+            .contains("GITHUB_TOKEN or --github-token"));
+        assert_eq!(
+            FetchError::RateLimited(true).to_string(),
+            "GitHub API rate limit exceeded"
+        );
+    }
+
+    // The cache directory follows XDG_CACHE_HOME only on Linux.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn list_skips_broken_cached_files() {
+        let cache_home =
+            std::env::temp_dir().join(format!("colortty-provider-test-{}", std::process::id()));
+        std::env::set_var("XDG_CACHE_HOME", &cache_home);
+        let provider = Provider::iterm();
+        let repo_dir = provider.repo_dir().unwrap();
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        std::fs::copy(
+            "tests/fixtures/Dracula.itermcolors",
+            repo_dir.join("Dracula.itermcolors"),
+        )
+        .unwrap();
+        std::fs::write(repo_dir.join("Broken.itermcolors"), "not a plist").unwrap();
+
+        let color_schemes = async_std::task::block_on(provider.list()).unwrap();
+        let names: Vec<&str> = color_schemes
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Dracula"]);
+
+        std::fs::remove_dir_all(&cache_home).unwrap();
+    }
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod state_tests {
+    use colortty::state::{Applied, State};
+    use std::path::PathBuf;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn applied(target: &str, name: &str) -> Applied {
+        Applied {
+            target: target.to_owned(),
+            name: name.to_owned(),
+            provider: Some("iterm".to_owned()),
+            path: Some(PathBuf::from("/themes/Dracula.toml")),
+            applied_at: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        }
+    }
+
+    #[test]
+    fn record_replaces_target() {
+        let mut state = State::default();
+        state.record(applied("kitty", "Dracula"));
+        state.record(applied("alacritty", "Dracula"));
+        state.record(applied("kitty", "Nord"));
+        let names: Vec<_> = state
+            .applied()
+            .iter()
+            .map(|a| (a.target.as_str(), a.name.as_str()))
+            .collect();
+        assert_eq!(names, vec![("alacritty", "Dracula"), ("kitty", "Nord")]);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("colortty-state-test-{}", std::process::id()))
+            .join("state.json");
+        assert_eq!(State::load(&path).unwrap(), State::default());
+
+        let mut state = State::default();
+        state.record(applied("alacritty", "Dracula"));
+        state.save(&path).unwrap();
+        assert_eq!(State::load(&path).unwrap(), state);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod stats_tests {
+    use colortty::stats::PaletteStats;
+    use colortty::ColorScheme;
+    use std::fs;
+
+    fn dracula() -> ColorScheme {
+        let content = fs::read_to_string("tests/fixtures/Dracula.itermcolors").unwrap();
+        ColorScheme::from_iterm(&content).unwrap()
+    }
+
+    #[test]
+    fn new() {
+        let stats = PaletteStats::new(&dracula());
+        assert_eq!(stats.count, 1);
+        assert_eq!(stats.hues, [2, 2, 2, 2, 2, 2, 4]);
+        assert_eq!(stats.saturation.0, 0.0);
+        assert!((stats.contrast.1 - 15.34).abs() < 0.01);
+    }
+
+    #[test]
+    fn aggregate() {
+        let stats = PaletteStats::aggregate(&[
+            PaletteStats::new(&dracula()),
+            PaletteStats::new(&dracula()),
+        ])
+        .unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.hues, [4, 4, 4, 4, 4, 4, 8]);
+        assert_eq!(stats.brightness, PaletteStats::new(&dracula()).brightness);
+        assert!(PaletteStats::aggregate(&[]).is_none());
+    }
+}
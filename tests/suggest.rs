@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod suggest_tests {
+    use colortty::suggest::{edit_distance, similar_names};
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("dracula", "dracula"), 0);
+        assert_eq!(edit_distance("dracla", "dracula"), 1);
+        assert_eq!(edit_distance("drcaula", "dracula"), 2);
+        assert_eq!(edit_distance("nord", ""), 4);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn similar_names_closest_first() {
+        let names: Vec<String> = ["Dracula+", "Dracula", "Nord", "Darcula", "Andromeda"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        assert_eq!(
+            similar_names("dracla", &names, 5),
+            vec!["Dracula", "Dracula+"]
+        );
+        assert_eq!(similar_names("dracla", &names, 1), vec!["Dracula"]);
+        assert_eq!(
+            similar_names("drcula", &names, 5),
+            vec!["Darcula", "Dracula", "Dracula+"]
+        );
+        assert_eq!(similar_names("nort", &names, 5), vec!["Nord"]);
+        assert!(similar_names("solarized", &names, 5).is_empty());
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tags_tests {
+    use colortty::tags::{auto_tags, Tags};
+    use colortty::ColorScheme;
+    use std::fs;
+
+    fn read_scheme(path: &str) -> ColorScheme {
+        ColorScheme::from_iterm(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let mut tags = Tags::default();
+        tags.add("Dracula", &["Purple", "work"]);
+        tags.add("Nord", &["work"]);
+        assert_eq!(tags.of("Dracula"), vec!["purple", "work"]);
+        assert_eq!(tags.counts().get("work"), Some(&2));
+
+        tags.remove("Dracula", &["purple", "work"]);
+        assert!(tags.of("Dracula").is_empty());
+        assert_eq!(tags.counts().get("work"), Some(&1));
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("colortty-tags-test-{}", std::process::id()))
+            .join("tags.json");
+        assert_eq!(Tags::load(&path).unwrap(), Tags::default());
+
+        let mut tags = Tags::default();
+        tags.add("Dracula", &["purple"]);
+        tags.save(&path).unwrap();
+        assert_eq!(Tags::load(&path).unwrap(), tags);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn analysis() {
+        let dracula = read_scheme("tests/fixtures/Dracula.itermcolors");
+        assert_eq!(auto_tags(&dracula), vec!["dark", "high-contrast"]);
+        let light = read_scheme("tests/fixtures/two-firewatch-light.itermcolors");
+        assert_eq!(auto_tags(&light)[0], "light");
+
+        let mut tags = Tags::default();
+        tags.add("Dracula", &["purple"]);
+        assert_eq!(
+            tags.all_of("Dracula", &dracula),
+            vec!["dark", "high-contrast", "purple"]
+        );
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod terminal_tests {
+    use colortty::terminal::{palette_query, restore_sequence};
+
+    #[test]
+    fn query_ends_with_device_attributes() {
+        let query = palette_query();
+        assert!(query.starts_with("\x1b]4;0;?\x1b\\"));
+        assert!(query.contains("\x1b]4;15;?\x1b\\\x1b]10;?\x1b\\\x1b]11;?\x1b\\\x1b]12;?\x1b\\"));
+        assert!(query.ends_with("\x1b[c"));
+    }
+
+    #[test]
+    fn restore_answered_colors() {
+        let mut replies = String::new();
+        for index in 0..16 {
+            replies.push_str(&format!(
+                "\x1b]4;{};rgb:{:02x}00/0000/0000\x1b\\",
+                index, index
+            ));
+        }
+        // Some terminals terminate answers with BEL.
+        replies.push_str("\x1b]10;rgb:ffff/ffff/ffff\x07");
+        replies.push_str("\x1b]11;rgb:0000/0000/0000\x07");
+        replies.push_str("\x1b]12;rgb:8080/8080/8080\x1b\\\x1b[?62;22c");
+
+        let sequence = restore_sequence(&replies);
+        assert!(
+            sequence.starts_with("\x1b]4;0;rgb:0000/0000/0000\x1b\\\x1b]4;1;rgb:0100/0000/0000")
+        );
+        assert!(sequence.contains("\x1b]4;15;rgb:0f00/0000/0000\x1b\\"));
+        assert!(sequence.ends_with(
+            "\x1b]10;rgb:ffff/ffff/ffff\x1b\\\x1b]11;rgb:0000/0000/0000\x1b\\\x1b]12;rgb:8080/8080/8080\x1b\\"
+        ));
+        assert!(!sequence.contains("\x1b]104"));
+    }
+
+    #[test]
+    fn reset_unanswered_colors() {
+        let sequence =
+            restore_sequence("\x1b]4;3;rgb:aaaa/bbbb/cccc\x1b\\\x1b]11;?\x1b\\\x1b[?1;2c");
+        assert!(sequence.starts_with("\x1b]104;0\x1b\\\x1b]104;1\x1b\\\x1b]104;2\x1b\\"));
+        assert!(sequence.contains("\x1b]4;3;rgb:aaaa/bbbb/cccc\x1b\\\x1b]104;4\x1b\\"));
+        assert!(sequence.ends_with("\x1b]110\x1b\\\x1b]111\x1b\\\x1b]112\x1b\\"));
+        assert_eq!(restore_sequence("").matches("\x1b]104;").count(), 16);
+    }
+}